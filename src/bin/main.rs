@@ -1,5 +1,153 @@
-use snake_lib::game::Game;
-
-fn main() {
-    Game::default().run().unwrap();
-}
\ No newline at end of file
+use std::time::Duration;
+
+use snake_lib::{Game, RunOptions};
+use snake_lib::challenge::Challenge;
+use snake_lib::level::Level;
+use snake_lib::params::UrlParams;
+use snake_lib::replay::Replay;
+use snake_lib::scripting::Mod;
+use snake_lib::tournament::Tournament;
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().collect();
+
+    // `--ascii` goes along with any of the other options
+    let ascii = args.iter().any(|arg| arg == "--ascii");
+    args.retain(|arg| arg != "--ascii");
+
+    // As does `--font <file>`, a font sheet to draw the window with
+    let font = args.iter().position(|arg| arg == "--font").map(|index| {
+        let path = args.get(index + 1).expect("Expected a font file after --font").clone();
+
+        args.drain(index..=index + 1);
+        path
+    });
+
+    match args.get(1).map(String::as_str) {
+        // Hidden stability test: `--soak [hours]` runs the game headlessly under an autopilot
+        Some("--soak") => {
+            let hours = args.get(2).and_then(|hours| hours.parse::<f64>().ok()).unwrap_or(1.0);
+
+            snake_lib::soak::run(Duration::from_secs_f64(hours * 3600.0));
+        },
+        Some("--level") => {
+            let path = args.get(2).expect("Expected a level file after --level");
+            let mut game = new_game(ascii, font.as_deref());
+
+            match Level::load(path) {
+                Ok(level) => game.load_level(level),
+                Err(error) => game.show_load_error(error)
+            }
+
+            game.run().unwrap();
+        },
+        Some("--mod") => {
+            let name = args.get(2).expect("Expected a mod name after --mod");
+            let script = match Mod::named(name) {
+                Ok(script) => script,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    eprintln!("Mods available: {}", Mod::available().join(", "));
+                    std::process::exit(1);
+                }
+            };
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.play_by(move || script.rules());
+            game.run().unwrap();
+        },
+        Some("--replay") => {
+            let path = args.get(2).expect("Expected a replay file after --replay");
+            let replay = match Replay::load(path) {
+                Ok(replay) => replay,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            };
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.watch(replay);
+            game.run().unwrap();
+        },
+        Some("--challenge") => {
+            let code = args.get(2).expect("Expected a challenge code after --challenge");
+            let challenge = match Challenge::parse(code) {
+                Ok(challenge) => challenge,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            };
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.play_challenge(challenge);
+            game.run().unwrap();
+        },
+        // A hot-seat tournament: `--tournament [players] [best of]`
+        Some("--tournament") => {
+            let players = args.get(2).and_then(|players| players.parse().ok()).unwrap_or(Tournament::PLAYERS);
+            let best_of = args.get(3).and_then(|best_of| best_of.parse().ok()).unwrap_or(Tournament::BEST_OF);
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.start_tournament(players, best_of);
+            game.run().unwrap();
+        },
+        Some("--tutorial") => {
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.start_tutorial();
+            game.run().unwrap();
+        },
+        // A bigger map than the window, `--size <width>x<height>` or a single side, which scrolls to follow
+        // the snake in a window of the usual size
+        Some("--size") => {
+            let size = args.get(2).expect("Expected a map size after --size");
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.apply_url_params(UrlParams::parse(&format!("size={}", size)));
+            game.run_with(RunOptions {
+                view_dimensions: Some(Game::MAP_DIMENSIONS),
+                ..RunOptions::default()
+            }).unwrap();
+        },
+        // Record every run, to be saved as a GIF with [G]
+        Some("--record") => {
+            let mut game = new_game(ascii, font.as_deref());
+
+            game.record_runs();
+            game.run().unwrap();
+        },
+        #[cfg(feature = "terminal")]
+        Some("--terminal") => {
+            let mut terminal = snake_lib::terminal::TerminalGame::new();
+
+            if ascii {
+                terminal.restrict_to_ascii();
+            }
+
+            if let Err(error) = terminal.run() {
+                eprintln!("Terminal error: {}", error);
+                std::process::exit(1);
+            }
+        },
+        _ => new_game(ascii, font.as_deref()).run().unwrap()
+    }
+}
+
+fn new_game(ascii: bool, font: Option<&str>) -> Game {
+    let mut game = Game::new();
+
+    if ascii {
+        game.restrict_to_ascii();
+    }
+
+    if let Some(path) = font {
+        if let Err(error) = game.use_font(path) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    game
+}
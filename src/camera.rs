@@ -0,0 +1,120 @@
+use bracket_terminal::prelude::Point;
+
+use crate::engine::{Grid, Position};
+
+// Which part of the map is on screen. Maps that fit the window are shown whole from the top-left corner,
+// and bigger ones scroll, keeping the tile being followed in the middle of the view as far as they can
+// without showing past the edges of the map. Hex grids are drawn two columns to a tile, with the odd rows
+// shifted a column to the right, so each tile sits between the two it touches on the rows either side
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Camera {
+    map: (u32, u32),
+    view: (u32, u32), // In characters, as many as the window has
+    grid: Grid,
+    offset: Position // The map tile in the view's top-left corner
+}
+
+impl Camera {
+    pub fn new(map: (u32, u32), view: (u32, u32)) -> Self {
+        Self {
+            map,
+            view,
+            grid: Grid::default(),
+            offset: Position::new(0, 0)
+        }
+    }
+
+    // Lay the map out for `grid`. Call before following anything, as it changes how much fits in the view
+    pub fn with_grid(mut self, grid: Grid) -> Self {
+        self.grid = grid;
+        self
+    }
+
+    // A camera that's already following `focus`
+    pub fn following(map: (u32, u32), view: (u32, u32), focus: Position) -> Self {
+        let mut camera = Self::new(map, view);
+
+        camera.follow(focus);
+        camera
+    }
+
+    // Scroll to put `focus` as near the middle of the view as the map allows
+    pub fn follow(&mut self, focus: Position) {
+        let tiles = self.tiles();
+
+        self.offset = Position::new(
+            Self::centre_on(focus.x, self.map.0, tiles.0),
+            Self::centre_on(focus.y, self.map.1, tiles.1)
+        );
+    }
+
+    // Where the view starts along one side of the map, for it to be centred on `focus`
+    fn centre_on(focus: i32, map: u32, view: u32) -> i32 {
+        (focus - view as i32 / 2).clamp(0, (map as i32 - view as i32).max(0))
+    }
+
+    pub fn map(&self) -> (u32, u32) {
+        self.map
+    }
+
+    pub fn view(&self) -> (u32, u32) {
+        self.view
+    }
+
+    pub fn offset(&self) -> Position {
+        self.offset
+    }
+
+    // How many tiles fit across and down the view, leaving room for the shifted rows of a hex grid
+    pub fn tiles(&self) -> (u32, u32) {
+        match self.grid {
+            Grid::Hex => (self.view.0.saturating_sub(1) / 2, self.view.1),
+            _ => self.view
+        }
+    }
+
+    pub fn is_scrolling(&self) -> bool {
+        let tiles = self.tiles();
+
+        self.map.0 > tiles.0 || self.map.1 > tiles.1
+    }
+
+    // How far across the screen a row of the map is shifted
+    fn shift(&self, row: i32) -> i32 {
+        match self.grid {
+            Grid::Hex => row.rem_euclid(2),
+            _ => 0
+        }
+    }
+
+    // Where a tile of the map is drawn on screen, or nothing while it's scrolled out of view
+    pub fn to_screen(&self, position: Position) -> Option<Point> {
+        let tile = Position::new(position.x - self.offset.x, position.y - self.offset.y);
+        let columns = if self.grid == Grid::Hex { 2 } else { 1 };
+
+        if tile.in_bounds(self.tiles()) && position.in_bounds(self.map) {
+            Some(Point::new(tile.x * columns + self.shift(position.y), tile.y))
+        } else {
+            None
+        }
+    }
+
+    // The tile of the map drawn at a place on screen, if there's one there. On a hex grid the columns
+    // between tiles have none
+    pub fn to_map(&self, screen: Point) -> Option<Position> {
+        let row = screen.y + self.offset.y;
+        let column = screen.x - self.shift(row);
+        let x = match self.grid {
+            Grid::Hex if column.rem_euclid(2) == 1 => return None,
+            Grid::Hex => column.div_euclid(2),
+            _ => column
+        };
+        let position = Position::new(x + self.offset.x, row);
+
+        if Position::new(x, screen.y).in_bounds(self.tiles()) && position.in_bounds(self.map) {
+            Some(position)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::engine::{Config, Engine};
+use crate::engine::controller::Controller;
+use crate::engine::goal::{Goal, GoalMode};
+use crate::engine::mode::RunState;
+use crate::engine::mode::{GameMode, Mode};
+use crate::level::Level;
+
+// The campaign's levels, in the order they're played. Each has a goal to meet to move on to the next
+const LEVELS: [&str; 7] = [
+    include_str!("../levels/campaign/1-first-bite.lvl"),
+    include_str!("../levels/campaign/2-beat-the-clock.lvl"),
+    include_str!("../levels/campaign/3-round-table.lvl"),
+    include_str!("../levels/campaign/4-crossroads.lvl"),
+    include_str!("../levels/campaign/5-ring-road.lvl"),
+    include_str!("../levels/campaign/6-the-cutter.lvl"),
+    include_str!("../levels/campaign/7-the-guard.lvl")
+];
+
+// A run through the campaign's levels, one at a time, starting from the one picked on the level select
+// screen
+pub struct Campaign {
+    levels: Vec<Level>,
+    current: usize // The level being played
+}
+
+impl Campaign {
+    // Play the campaign from the level at `index`
+    pub fn new(index: usize) -> Self {
+        let levels = Self::levels();
+
+        Self {
+            current: index.min(levels.len() - 1),
+            levels
+        }
+    }
+
+    pub fn levels() -> Vec<Level> {
+        LEVELS.iter()
+            .map(|text| Level::parse(text).expect("The campaign's levels are valid"))
+            .collect()
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.levels[self.current]
+    }
+
+    // What the current level asks of the player. Levels without a goal are passed by filling the map
+    pub fn goal(&self) -> Option<Goal> {
+        self.level().goal
+    }
+
+    pub const MAX_STARS: u8 = 3;
+
+    // How well the level was passed, in `time` seconds: three stars within par, two within twice par, and
+    // one otherwise or for levels without a par
+    pub fn stars(&self, time: f64) -> u8 {
+        match self.level().par {
+            Some(par) if time <= par => Self::MAX_STARS,
+            Some(par) if time <= par * 2.0 => 2,
+            _ => 1
+        }
+    }
+
+    pub fn describe_stars(stars: u8) -> String {
+        format!("{}{}", "*".repeat(stars as usize), ".".repeat((Self::MAX_STARS - stars) as usize))
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 == self.levels.len()
+    }
+
+    // Move on to the next level, once the current one has been passed
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1).min(self.levels.len() - 1);
+    }
+
+    // The settings the current level is played with: classic play on the standard map, laid out by the
+    // level, keeping to the player's skin and scoring
+    pub fn config(&self, base: &Config) -> Config {
+        let level = self.level().clone();
+
+        Config {
+            seed: base.seed,
+            dimensions: Engine::MAP_DIMENSIONS,
+            mode: Mode::Classic,
+            scoring_policy: base.scoring_policy,
+            skin: base.skin,
+            schedule: level.schedule,
+            tags: level.tags,
+            fruits: level.fruits,
+            arena: level.arena,
+            edges: level.edges,
+            ..Config::default()
+        }
+    }
+
+    // A fresh copy of the current level's rules, for a new run. Boss levels without a goal are passed by
+    // outliving the boss
+    pub fn rules(&self) -> Box<dyn GameMode> {
+        match (self.goal(), self.level().boss) {
+            (Some(goal), None) => Box::new(GoalMode::new(goal)),
+            (goal, Some(_)) => Box::new(GoalMode::against_boss(goal.unwrap_or(Goal::Survive(f64::INFINITY)))),
+            (None, None) => Mode::Classic.rules()
+        }
+    }
+
+    // The bot steering the boss on boss levels, and no others
+    pub fn bots(&self) -> Vec<Box<dyn Controller>> {
+        self.level().boss.map(|boss| boss.controller()).into_iter().collect()
+    }
+
+    fn describe_goal(goal: Option<Goal>) -> String {
+        goal.map_or_else(|| "Fill the map".to_string(), |goal| goal.describe())
+    }
+
+    // The level's goal along the top of the HUD layer, with how far along it the run is
+    pub fn render(&self, ctx: &mut BTerm, dimensions: (u32, u32), run: &RunState) {
+        ctx.print_color_centered_at(dimensions.0 / 2, 1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, Self::describe_goal(self.goal()));
+
+        if let Some(progress) = self.goal().and_then(|goal| goal.progress(run)) {
+            ctx.print_color_centered_at(dimensions.0 / 2, 2, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, progress);
+        }
+    }
+
+    // How the level went, in place of the game over screen, with the stars earned if it was passed
+    pub fn render_result(&self, ctx: &mut BTerm, centre: (u32, u32), stars: Option<u8>) {
+        let white = bracket_terminal::prelude::WHITE;
+        let passed = stars.is_some();
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 4, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("{}. {}", self.current + 1, self.level().name));
+        ctx.print_color_centered_at(centre.0, centre.1 - 2, white, Game::BACKGROUND_COLOUR, if passed { "LEVEL PASSED" } else { "LEVEL FAILED" });
+        ctx.print_color_centered_at(centre.0, centre.1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, Self::describe_goal(self.goal()));
+
+        if let Some(stars) = stars {
+            ctx.print_color_centered_at(centre.0, centre.1 - 1, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, Self::describe_stars(stars));
+        }
+
+        if passed && self.is_last() {
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, "Campaign complete!");
+        } else if passed {
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, white, Game::BACKGROUND_COLOUR, "[Enter] Next level");
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 4, white, Game::BACKGROUND_COLOUR, "[R] Retry [Q] Levels");
+    }
+}
+
+// What the player picked on the level select screen
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LevelChoice {
+    Play(usize), // The level's index in the campaign
+    Close
+}
+
+// Lists the campaign's levels to pick one to play, with the stars earned on those passed. Levels are
+// unlocked one at a time, as the one before them is passed, and locked ones are greyed out
+pub struct LevelSelect {
+    levels: Vec<Level>,
+    stars: Vec<u8>, // Earned on each level passed, in order
+    selected: usize
+}
+
+impl LevelSelect {
+    pub const LOCKED_COLOUR: RGB = RGB {r: 0.4, g: 0.4, b: 0.4};
+
+    pub fn new(stars: &[u8]) -> Self {
+        let levels = Campaign::levels();
+
+        Self {
+            selected: stars.len().min(levels.len() - 1),
+            stars: stars.to_vec(),
+            levels
+        }
+    }
+
+    fn is_unlocked(&self, index: usize) -> bool {
+        index <= self.stars.len()
+    }
+
+    // Respond to a key press: `None` while the screen stays open, then what was picked
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<LevelChoice> {
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.selected = self.selected.saturating_sub(1),
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.selected = (self.selected + 1).min(self.levels.len() - 1),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter if self.is_unlocked(self.selected) => {
+                return Some(LevelChoice::Play(self.selected));
+            },
+            VirtualKeyCode::Escape | VirtualKeyCode::Q => return Some(LevelChoice::Close),
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+        let top = centre.1 - 7;
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "CAMPAIGN");
+
+        for (index, level) in self.levels.iter().enumerate() {
+            let marker = if index == self.selected { '>' } else { ' ' };
+            let (label, colour) = match self.stars.get(index) {
+                Some(stars) => (format!("{} {}. {:14} {}", marker, index + 1, level.name, Campaign::describe_stars(*stars)), Game::FRUIT_COLOUR),
+                None if self.is_unlocked(index) => (format!("{} {}. {}", marker, index + 1, level.name), RGB::named(white)),
+                None => (format!("{} {}. {}", marker, index + 1, level.name), Self::LOCKED_COLOUR)
+            };
+
+            ctx.print_color(centre.0.saturating_sub(11), top + 2 + index as u32, colour, Game::BACKGROUND_COLOUR, label);
+        }
+
+        let row = top + 3 + self.levels.len() as u32;
+
+        if self.is_unlocked(self.selected) {
+            ctx.print_color_centered_at(centre.0, row, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, Campaign::describe_goal(self.levels[self.selected].goal));
+        } else {
+            ctx.print_color_centered_at(centre.0, row, Self::LOCKED_COLOUR, Game::BACKGROUND_COLOUR, "Locked");
+        }
+
+        // Passing some levels unlocks a mode as well as the next level
+        if let Some(mode) = Mode::ALL.iter().find(|mode| mode.levels_to_unlock() == self.selected + 1) {
+            let colour = if self.selected < self.stars.len() { Game::FRUIT_COLOUR } else { Self::LOCKED_COLOUR };
+
+            ctx.print_color_centered_at(centre.0, row + 1, colour, Game::BACKGROUND_COLOUR, format!("Unlocks {} mode", mode.name()));
+        }
+        ctx.print_color_centered_at(centre.0, centre.1 + 6, white, Game::BACKGROUND_COLOUR, "[Enter] Play [Esc] Back");
+    }
+}
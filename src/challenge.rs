@@ -0,0 +1,268 @@
+use std::fmt;
+
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::ui::TextField;
+use crate::engine::{Config, Grid};
+use crate::engine::mode::Mode;
+use crate::engine::scoring::ScoringPolicy;
+use crate::engine::difficulty::Difficulty;
+use crate::engine::versus::WinCondition;
+use crate::engine::arena::Symmetry;
+use crate::engine::speed::LengthSpeed;
+
+// The seed and settings of a run, packed into a short code that can be shared so others race the exact
+// same board. The code is base58, which leaves out characters easily mistaken for one another, over a
+// format byte, the seed, the map size, the mode, the other settings packed into a byte, and a checksum
+// byte that catches most typos
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Challenge {
+    pub seed: u64,
+    pub dimensions: (u32, u32),
+    pub mode: Mode,
+    pub scoring_policy: ScoringPolicy,
+    pub power_ups: bool,
+    pub difficulty: Difficulty,
+    pub win_condition: WinCondition
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ChallengeError {
+    NotACode, // Characters outside base58, or the wrong number of them
+    Mistyped, // The checksum doesn't match
+    NewerFormat(u8), // Written in a format added after this version of the game
+    Invalid(&'static str) // Names the setting that's out of range
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotACode => write!(f, "Not a challenge code"),
+            Self::Mistyped => write!(f, "Code mistyped"),
+            Self::NewerFormat(_) => write!(f, "Needs a newer game"),
+            Self::Invalid(setting) => write!(f, "Unknown {}", setting)
+        }
+    }
+}
+
+impl Challenge {
+    pub const FORMAT: u8 = 1;
+    pub const ALPHABET: &'static [u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    pub const BYTES: usize = 14;
+    pub const MAX_LENGTH: usize = 20; // Base58 characters needed for `BYTES` bytes
+
+    // The challenge of the run `config` sets up
+    pub fn of(config: &Config) -> Self {
+        Self {
+            seed: config.seed,
+            dimensions: config.dimensions,
+            mode: config.mode,
+            scoring_policy: config.scoring_policy,
+            power_ups: config.power_ups,
+            difficulty: config.difficulty,
+            win_condition: config.win_condition
+        }
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        config.seed = self.seed;
+        config.dimensions = self.dimensions;
+        config.mode = self.mode;
+        config.scoring_policy = self.scoring_policy;
+        config.power_ups = self.power_ups;
+        config.difficulty = self.difficulty;
+        config.win_condition = self.win_condition;
+        // Codes don't carry the custom game settings, so challenges are raced without them
+        config.obstacles = 0;
+        config.symmetry = Symmetry::default();
+        config.length_speed = LengthSpeed::default();
+        config.grid = Grid::default();
+    }
+
+    // Whether runs set up by `config` still race this challenge, whatever their seed
+    pub fn is_played_by(&self, config: &Config) -> bool {
+        config.obstacles == 0 && config.symmetry == Symmetry::default() && config.length_speed == LengthSpeed::default() && config.grid == Grid::default() && Self {
+            seed: self.seed,
+            ..Self::of(config)
+        } == *self
+    }
+
+    pub fn code(&self) -> String {
+        let mut bytes = vec![Self::FORMAT];
+
+        bytes.extend_from_slice(&self.seed.to_be_bytes());
+        bytes.push(self.dimensions.0 as u8);
+        bytes.push(self.dimensions.1 as u8);
+        bytes.push(index_of(&Mode::ALL, self.mode) as u8);
+        bytes.push(
+            index_of(&ScoringPolicy::ALL, self.scoring_policy) as u8
+                | (self.power_ups as u8) << 1
+                | (index_of(&Difficulty::ALL, self.difficulty) as u8) << 2
+                | (index_of(&WinCondition::ALL, self.win_condition) as u8) << 4
+        );
+        bytes.push(checksum(&bytes));
+
+        encode(&bytes)
+    }
+
+    // Read a code back, ignoring any whitespace pasted around or inside it
+    pub fn parse(code: &str) -> Result<Self, ChallengeError> {
+        let code: String = code.chars().filter(|character| !character.is_whitespace()).collect();
+
+        if code.is_empty() || code.len() > Self::MAX_LENGTH {
+            return Err(ChallengeError::NotACode);
+        }
+
+        let bytes = decode(&code).ok_or(ChallengeError::NotACode)?;
+
+        if bytes.len() != Self::BYTES {
+            return Err(ChallengeError::NotACode);
+        }
+
+        if checksum(&bytes[..Self::BYTES - 1]) != bytes[Self::BYTES - 1] {
+            return Err(ChallengeError::Mistyped);
+        }
+
+        if bytes[0] != Self::FORMAT {
+            return Err(ChallengeError::NewerFormat(bytes[0]));
+        }
+
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&bytes[1..9]);
+
+        let dimensions = (bytes[9] as u32, bytes[10] as u32);
+        let modifiers = bytes[12] as usize;
+
+        if dimensions.0 == 0 || dimensions.1 == 0 {
+            return Err(ChallengeError::Invalid("map size"));
+        }
+
+        Ok(Self {
+            seed: u64::from_be_bytes(seed),
+            dimensions,
+            mode: *Mode::ALL.get(bytes[11] as usize).ok_or(ChallengeError::Invalid("mode"))?,
+            scoring_policy: ScoringPolicy::ALL[modifiers & 1],
+            power_ups: modifiers & 2 != 0,
+            difficulty: *Difficulty::ALL.get(modifiers >> 2 & 3).ok_or(ChallengeError::Invalid("bot"))?,
+            win_condition: *WinCondition::ALL.get(modifiers >> 4 & 3).ok_or(ChallengeError::Invalid("win condition"))?
+        })
+    }
+}
+
+fn index_of<T: PartialEq>(all: &[T], value: T) -> usize {
+    all.iter().position(|item| *item == value).unwrap_or(0)
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, byte| sum.wrapping_mul(31).wrapping_add(*byte))
+}
+
+// Big-endian base58, each leading zero byte written as a leading '1'
+fn encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    let mut digits: Vec<u8> = Vec::new(); // Least significant first
+
+    for byte in &bytes[zeros..] {
+        let mut carry = *byte as u32;
+
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    std::iter::repeat_n('1', zeros)
+        .chain(digits.iter().rev().map(|digit| Challenge::ALPHABET[*digit as usize] as char))
+        .collect()
+}
+
+fn decode(code: &str) -> Option<Vec<u8>> {
+    let zeros = code.chars().take_while(|character| *character == '1').count();
+    let mut bytes: Vec<u8> = Vec::new(); // Least significant first
+
+    for character in code.bytes().skip(zeros) {
+        let mut carry = Challenge::ALPHABET.iter().position(|letter| *letter == character)? as u32;
+
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    Some(std::iter::repeat_n(0, zeros).chain(bytes.into_iter().rev()).collect())
+}
+
+// The challenge screen, which shows the code of the run just played for sharing, and where a code can
+// be typed in to play
+pub struct ChallengePrompt {
+    played: String, // The code of the run just played
+    code: TextField,
+    error: Option<ChallengeError> // Why the last code entered was turned away
+}
+
+impl ChallengePrompt {
+    pub fn new(played: Challenge) -> Self {
+        Self {
+            played: played.code(),
+            code: TextField::new(Challenge::MAX_LENGTH, |character| character.is_ascii_alphanumeric()),
+            error: None
+        }
+    }
+
+    // Type a character of the code to play. Anything that couldn't be part of one is ignored
+    pub fn type_character(&mut self, character: char) {
+        if self.code.type_character(character) {
+            self.error = None;
+        }
+    }
+
+    // Respond to a key press: `None` while the screen stays open, then whether a challenge was entered
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<Option<Challenge>> {
+        match key_code {
+            VirtualKeyCode::Back => {
+                self.code.backspace();
+                self.error = None;
+            },
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => match Challenge::parse(self.code.text()) {
+                Ok(challenge) => return Some(Some(challenge)),
+                Err(error) => self.error = Some(error)
+            },
+            VirtualKeyCode::Escape => return Some(None),
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 7, white, Game::BACKGROUND_COLOUR, "CHALLENGE");
+        ctx.print_color_centered_at(centre.0, centre.1 - 5, white, Game::BACKGROUND_COLOUR, "Share this run with");
+        ctx.print_color_centered_at(centre.0, centre.1 - 4, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, &self.played);
+        ctx.print_color_centered_at(centre.0, centre.1 - 1, white, Game::BACKGROUND_COLOUR, "Or type a code to play");
+        ctx.print_color_centered_at(centre.0, centre.1 + 1, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, self.code.display());
+
+        if let Some(error) = &self.error {
+            ctx.print_color_centered_at(centre.0, centre.1 + 3, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, error.to_string());
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 5, white, Game::BACKGROUND_COLOUR, "[Enter] Play [Esc] Back");
+    }
+}
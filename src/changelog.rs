@@ -0,0 +1,110 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::engine::skin::Skin;
+use crate::ui::DocumentView;
+
+// The changelog, embedded into the binary when it is built
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// The notes for a single version of the game
+pub struct Release {
+    pub version: &'static str,
+    pub notes: Vec<&'static str>
+}
+
+// Parse the changelog into its releases, newest first. Releases begin with a "## <version>" heading
+// and are followed by their notes, one "- " bullet per line
+pub fn releases() -> Vec<Release> {
+    let mut releases = Vec::<Release>::new();
+
+    for line in CHANGELOG.lines().map(str::trim) {
+        if let Some(version) = line.strip_prefix("## ") {
+            releases.push(Release {
+                version: version.trim(),
+                notes: Vec::new()
+            });
+        } else if line.starts_with("- ") {
+            if let Some(release) = releases.last_mut() {
+                release.notes.push(line);
+            }
+        }
+    }
+
+    releases
+}
+
+// The "What's new" screen, paging through the releases in the changelog
+pub struct ChangelogView {
+    releases: Vec<Release>,
+    selected: usize,
+    document: DocumentView
+}
+
+impl ChangelogView {
+    const DOCUMENT_POSITION: (u32, u32) = (1, 3);
+    const DOCUMENT_DIMENSIONS: (usize, usize) = (
+        Game::MAP_DIMENSIONS.0 as usize - 3, // Leave room for the margins and the scrollbar
+        Game::MAP_DIMENSIONS.1 as usize - 8
+    );
+
+    // Open the changelog at the given version, or the newest release if that version isn't listed
+    pub fn new(version: &str) -> Self {
+        let releases = releases();
+        let selected = releases.iter().position(|release| release.version == version).unwrap_or(0);
+        let document = Self::document(&releases[selected]);
+
+        Self {
+            releases,
+            selected,
+            document
+        }
+    }
+
+    fn document(release: &Release) -> DocumentView {
+        DocumentView::new(&release.notes, Self::DOCUMENT_DIMENSIONS.0, Self::DOCUMENT_DIMENSIONS.1)
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.document = Self::document(&self.releases[index]);
+    }
+
+    // Respond to a key press, returning false once the screen has been closed
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> bool {
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.document.scroll_up(),
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.document.scroll_down(),
+            VirtualKeyCode::Left | VirtualKeyCode::A if self.selected + 1 < self.releases.len() => {
+                self.select(self.selected + 1) // Older release
+            },
+            VirtualKeyCode::Right | VirtualKeyCode::D if self.selected > 0 => {
+                self.select(self.selected - 1) // Newer release
+            },
+            VirtualKeyCode::Return | VirtualKeyCode::Escape => return false,
+            _ => {}
+        }
+
+        true
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, skin: Skin) {
+        let release = &self.releases[self.selected];
+        let title = if release.version == CURRENT_VERSION {
+            format!("What's new in {}", release.version)
+        } else {
+            format!("Version {}", release.version)
+        };
+
+        ctx.print_color_centered_at(Game::MAP_CENTRE.0, 1, bracket_terminal::prelude::WHITE, Game::BACKGROUND_COLOUR, title);
+        self.document.render(ctx, Self::DOCUMENT_POSITION.0, Self::DOCUMENT_POSITION.1, skin);
+        ctx.print_color_centered_at(Game::MAP_CENTRE.0, Game::MAP_DIMENSIONS.1 - 3, bracket_terminal::prelude::WHITE, Game::BACKGROUND_COLOUR, "[Up/Down] Scroll");
+        ctx.print_color_centered_at(Game::MAP_CENTRE.0, Game::MAP_DIMENSIONS.1 - 2, bracket_terminal::prelude::WHITE, Game::BACKGROUND_COLOUR, format!("[Left/Right] {}/{}", self.releases.len() - self.selected, self.releases.len()));
+        ctx.print_color_centered_at(Game::MAP_CENTRE.0, Game::MAP_DIMENSIONS.1 - 1, bracket_terminal::prelude::WHITE, Game::BACKGROUND_COLOUR, "[Enter] Close");
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use bracket_terminal::console;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type Timestamp = Instant;
+#[cfg(target_arch = "wasm32")]
+pub type Timestamp = f64; // Milliseconds, as given by the browser's Performance API
+
+// The platform's monotonic clock
+#[cfg_attr(not(target_arch = "wasm32"), derive(Default))]
+pub struct Clock {
+    #[cfg(target_arch = "wasm32")]
+    performance: web_sys::Performance
+}
+
+impl Clock {
+    pub fn now(&self) -> Timestamp {
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.performance.now()
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Instant::now()
+        }
+    }
+
+    pub fn seconds_since(&self, timestamp: Timestamp) -> f64 {
+        #[cfg(target_arch = "wasm32")]
+        {
+            (self.performance.now() - timestamp) / 1000.0
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            timestamp.elapsed().as_secs_f64()
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Default for Clock {
+    fn default() -> Self {
+        Self {
+            performance: web_sys::window().unwrap().performance().unwrap()
+        }
+    }
+}
+
+// A stopwatch for timing runs, which only counts time while it is running (so pauses aren't included)
+#[derive(Default)]
+pub struct RunTimer {
+    banked_seconds: f64, // Time counted before the timer was last stopped
+    running_since: Option<Timestamp>
+}
+
+impl RunTimer {
+    pub fn start(&mut self, clock: &Clock) {
+        if self.running_since.is_none() {
+            self.running_since = Some(clock.now());
+        }
+    }
+
+    pub fn stop(&mut self, clock: &Clock) {
+        if let Some(start) = self.running_since.take() {
+            self.banked_seconds += clock.seconds_since(start);
+        }
+    }
+
+    pub fn seconds(&self, clock: &Clock) -> f64 {
+        self.banked_seconds + self.running_since.map_or(0.0, |start| clock.seconds_since(start))
+    }
+}
+
+// Measures the slither rate actually achieved against the rate asked for, and works out a correction to
+// the speed of time fed to the timestep, so that a mode's slithers per second mean the same thing on
+// every platform however coarse its clock or uneven its frames. Discrepancies are logged to the console
+pub struct SpeedCalibration {
+    slithers: u32, // Slithers counted since the start of the current measurement window
+    window_start: Timestamp,
+    dropped: f64, // Seconds of the current window the timestep let go, which the snake was never meant to slither
+    measured_rate: Option<f64>, // Slithers per second achieved over the previous window
+    correction: f64
+}
+
+impl SpeedCalibration {
+    pub const MEASUREMENT_WINDOW: f64 = 5.0; // Seconds
+    pub const TOLERANCE: f64 = 0.03; // Fraction the achieved rate may stray from the target before it's reported
+    pub const MAX_CORRECTION: f64 = 1.25;
+
+    pub fn new(clock: &Clock) -> Self {
+        Self {
+            slithers: 0,
+            window_start: clock.now(),
+            dropped: 0.0,
+            measured_rate: None,
+            correction: 1.0
+        }
+    }
+
+    pub fn measured_rate(&self) -> Option<f64> {
+        self.measured_rate
+    }
+
+    // The factor to scale real time by before handing it to the timestep
+    pub fn correction(&self) -> f64 {
+        self.correction
+    }
+
+    pub fn record_slither(&mut self) {
+        self.slithers += 1;
+    }
+
+    // Leave `seconds` of real time out of the window, for time the timestep let go after a stall. Counting
+    // it would have the correction make up the very time the catch-up limit gave up on
+    pub fn record_dropped(&mut self, seconds: f64) {
+        self.dropped += seconds;
+    }
+
+    // Throw away the current window, for when the game stops slithering for a while (such as when paused)
+    pub fn restart(&mut self, clock: &Clock) {
+        self.slithers = 0;
+        self.window_start = clock.now();
+        self.dropped = 0.0;
+    }
+
+    // Start over from no correction, for a new run, so one run's measurements don't speed up the next
+    pub fn reset(&mut self, clock: &Clock) {
+        self.correction = 1.0;
+        self.restart(clock);
+    }
+
+    // Close the measurement window once it has run its length, nudging the correction towards whatever
+    // would have hit `target_rate`
+    pub fn update(&mut self, clock: &Clock, target_rate: f64) {
+        let elapsed = clock.seconds_since(self.window_start) - self.dropped;
+
+        if elapsed < Self::MEASUREMENT_WINDOW {
+            return;
+        }
+
+        let measured_rate = self.slithers as f64 / elapsed;
+
+        if measured_rate > 0.0 {
+            let discrepancy = target_rate / measured_rate;
+
+            // Only move part of the way, as slither counts are lumpy over a short window
+            self.correction = (self.correction * discrepancy.sqrt()).clamp(1.0 / Self::MAX_CORRECTION, Self::MAX_CORRECTION);
+
+            if (discrepancy - 1.0).abs() > Self::TOLERANCE {
+                console::log(format!(
+                    "Slither rate {:.2}/s is off the target of {:.2}/s, speed correction is now x{:.3}",
+                    measured_rate, target_rate, self.correction
+                ));
+            }
+        }
+
+        self.measured_rate = Some(measured_rate);
+        self.restart(clock);
+    }
+}
+
+// Format a number of seconds as minutes, seconds and hundredths (e.g. "1:05.25")
+pub fn format_duration(seconds: f64) -> String {
+    let hundredths = (seconds * 100.0) as u64;
+
+    format!("{}:{:02}.{:02}", hundredths / 6000, hundredths / 100 % 60, hundredths % 100)
+}
@@ -0,0 +1,137 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::minimap::Minimap;
+use crate::engine::{Config, Engine, Grid};
+use crate::engine::arena::Symmetry;
+use crate::engine::speed::LengthSpeed;
+
+// What the player chose on the custom game menu
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CustomChoice {
+    Play {
+        obstacles: u32, // The density walls are scattered at, in percent
+        symmetry: Symmetry,
+        length_speed: LengthSpeed, // How the pace picks up as the snake grows
+        grid: Grid // Which ways the snake can move
+    },
+    Close
+}
+
+// The settings on the menu, in the order they're listed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Setting {
+    Obstacles,
+    Symmetry,
+    LengthSpeed,
+    Grid
+}
+
+impl Setting {
+    const ALL: [Setting; 4] = [Setting::Obstacles, Setting::Symmetry, Setting::LengthSpeed, Setting::Grid];
+
+    // The setting a step up or down the menu, stopping at either end
+    fn step(self, down: bool) -> Self {
+        let index = Self::ALL.iter().position(|setting| *setting == self).unwrap_or(0);
+
+        match down {
+            true => Self::ALL[(index + 1).min(Self::ALL.len() - 1)],
+            false => Self::ALL[index.saturating_sub(1)]
+        }
+    }
+}
+
+// Sets up a custom game, with a sample layout of the map previewed as the settings change
+pub struct CustomGameMenu {
+    config: Config, // The settings the preview is laid out with
+    preview: Minimap,
+    selected: Setting
+}
+
+impl CustomGameMenu {
+    pub const DENSITY_STEP: u32 = 10; // Percent per press of left or right
+    pub const PREVIEW_SEED: u64 = 7; // Keeps the sample layout steady, so only the walls change with the settings
+    pub const PREVIEW_SIZE: u32 = 13;
+
+    pub fn new(base: &Config) -> Self {
+        // Previewed in the mode being played, so rival snakes show where they'd start
+        let config = Config {
+            seed: Self::PREVIEW_SEED,
+            dimensions: base.dimensions,
+            mode: base.mode,
+            arena: base.arena.clone(),
+            edges: base.edges,
+            obstacles: base.obstacles,
+            symmetry: base.symmetry,
+            length_speed: base.length_speed,
+            grid: base.grid,
+            ..Config::default()
+        };
+
+        Self {
+            preview: Self::preview(&config),
+            config,
+            selected: Setting::Obstacles
+        }
+    }
+
+    fn preview(config: &Config) -> Minimap {
+        Minimap::of_size(&Engine::new(config.clone()), Self::PREVIEW_SIZE)
+    }
+
+    // Step the selected setting along, one way or the other
+    fn change(&mut self, forward: bool) {
+        match self.selected {
+            Setting::Obstacles if forward => self.config.obstacles = (self.config.obstacles + Self::DENSITY_STEP).min(100),
+            Setting::Obstacles => self.config.obstacles = self.config.obstacles.saturating_sub(Self::DENSITY_STEP),
+            Setting::Symmetry if forward => self.config.symmetry = self.config.symmetry.next(),
+            Setting::Symmetry => self.config.symmetry = self.config.symmetry.previous(),
+            Setting::LengthSpeed if forward => self.config.length_speed = self.config.length_speed.next(),
+            Setting::LengthSpeed => self.config.length_speed = self.config.length_speed.previous(),
+            Setting::Grid if forward => self.config.grid = self.config.grid.next(),
+            Setting::Grid => self.config.grid = self.config.grid.previous()
+        }
+
+        self.preview = Self::preview(&self.config);
+    }
+
+    // Respond to a key press: `None` while the menu stays open, then what was chosen
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<CustomChoice> {
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.selected = self.selected.step(false),
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.selected = self.selected.step(true),
+            VirtualKeyCode::Left | VirtualKeyCode::A => self.change(false),
+            VirtualKeyCode::Right | VirtualKeyCode::D => self.change(true),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => return Some(CustomChoice::Play {
+                obstacles: self.config.obstacles,
+                symmetry: self.config.symmetry,
+                length_speed: self.config.length_speed,
+                grid: self.config.grid
+            }),
+            VirtualKeyCode::Escape | VirtualKeyCode::Y => return Some(CustomChoice::Close),
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32), ascii_only: bool) {
+        let white = bracket_terminal::prelude::WHITE;
+        let size = self.preview.dimensions();
+        let top = centre.1 - 11;
+        let colour = |setting| if setting == self.selected { Game::FRUIT_COLOUR } else { RGB::named(white) };
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "CUSTOM GAME");
+        ctx.print_color_centered_at(centre.0, top + 2, colour(Setting::Obstacles), Game::BACKGROUND_COLOUR, format!("< Obstacles: {}% >", self.config.obstacles));
+        ctx.print_color_centered_at(centre.0, top + 3, colour(Setting::Symmetry), Game::BACKGROUND_COLOUR, format!("< Symmetry: {} >", self.config.symmetry.name()));
+        ctx.print_color_centered_at(centre.0, top + 4, colour(Setting::LengthSpeed), Game::BACKGROUND_COLOUR, format!("< Speed-up: {} >", self.config.length_speed.name()));
+        ctx.print_color_centered_at(centre.0, top + 5, colour(Setting::Grid), Game::BACKGROUND_COLOUR, format!("< Grid: {} >", self.config.grid.name()));
+        self.preview.render(ctx, centre.0.saturating_sub(size.0 / 2), top + 7, ascii_only);
+        ctx.print_color_centered_at(centre.0, top + 8 + size.1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, "Sample layout");
+        ctx.print_color_centered_at(centre.0, centre.1 + 11, white, Game::BACKGROUND_COLOUR, "[Enter] Play [Esc] Back");
+    }
+}
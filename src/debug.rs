@@ -0,0 +1,91 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB
+};
+
+use crate::clock::{Clock, Timestamp};
+use crate::game::Game;
+use crate::object::Update;
+
+// Diagnostic information for performance investigation and bug reports, toggled with F3
+pub struct DebugOverlay {
+    visible: bool,
+    slithers: u32, // Slithers counted since the start of the current measurement window
+    window_start: Timestamp,
+    slither_rate: f64 // Slithers per second measured over the previous window
+}
+
+// What the game reports to the overlay each frame
+pub struct DebugInfo {
+    pub fps: f32,
+    pub snake_length: usize,
+    pub head_position: Option<(i32, i32)>,
+    pub seed: u64,
+    pub free_cells: usize,
+    pub target_rate: f64, // Slithers per second the run asks for
+    pub calibrated_rate: Option<f64>, // Slithers per second achieved over the last calibration window
+    pub speed_correction: f64
+}
+
+impl DebugOverlay {
+    pub const MEASUREMENT_WINDOW: f64 = 1.0; // Seconds
+    pub const COLOUR: RGB = RGB {r: 1.0, g: 1.0, b: 0.0};
+
+    pub fn new(clock: &Clock) -> Self {
+        Self {
+            visible: false,
+            slithers: 0,
+            window_start: clock.now(),
+            slither_rate: 0.0
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn record_slither(&mut self) {
+        self.slithers += 1;
+    }
+
+    // Draw onto the active (overlay) console
+    pub fn render(&self, ctx: &mut BTerm, info: &DebugInfo) {
+        let lines = [
+            format!("FPS: {:.0}", info.fps),
+            format!("Tick rate: {:.1}/s", self.slither_rate),
+            match info.calibrated_rate {
+                Some(rate) => format!("Calibrated: {:.2}/{}/s", rate, info.target_rate),
+                None => format!("Calibrated: -/{}/s", info.target_rate)
+            },
+            format!("Correction: x{:.3}", info.speed_correction),
+            format!("Length: {}", info.snake_length),
+            match info.head_position {
+                Some((x, y)) => format!("Head: ({}, {})", x, y),
+                None => "Head: -".to_string()
+            },
+            format!("Seed: {}", info.seed),
+            format!("Free cells: {}", info.free_cells)
+        ];
+
+        for (row, line) in lines.iter().enumerate() {
+            ctx.print_color(0, 2 + row as u32, Self::COLOUR, Game::BACKGROUND_COLOUR, line);
+        }
+    }
+}
+
+impl Update for DebugOverlay {
+    // Close the measurement window once it has run its length
+    fn update(&mut self, clock: &Clock) {
+        let elapsed = clock.seconds_since(self.window_start);
+
+        if elapsed >= Self::MEASUREMENT_WINDOW {
+            self.slither_rate = self.slithers as f64 / elapsed;
+            self.slithers = 0;
+            self.window_start = clock.now();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::engine::skin::Skin;
+use crate::level::LevelError;
+use crate::ui::DocumentView;
+
+// What the player chose to do when asked whether to quit
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum QuitChoice {
+    Stay,
+    Quit,
+    SaveAndQuit // Save the run as a replay on the way out
+}
+
+// Asks the player to confirm quitting before the session is summed up, rather than closing on a stray
+// key or click
+pub struct QuitDialog {
+    run_in_progress: bool, // Whether quitting would throw away a run that's still going
+    can_save: bool // Whether the run can be saved as a replay first
+}
+
+impl QuitDialog {
+    pub fn new(run_in_progress: bool, can_save: bool) -> Self {
+        Self {
+            run_in_progress,
+            can_save
+        }
+    }
+
+    // Respond to a key press: `None` while the dialog stays open, then what was chosen
+    pub fn handle_input(&self, key_code: VirtualKeyCode) -> Option<QuitChoice> {
+        match key_code {
+            VirtualKeyCode::Y | VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => Some(QuitChoice::Quit),
+            VirtualKeyCode::S if self.can_save => Some(QuitChoice::SaveAndQuit),
+            VirtualKeyCode::N | VirtualKeyCode::Escape => Some(QuitChoice::Stay),
+            _ => None
+        }
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 3, white, Game::BACKGROUND_COLOUR, "QUIT?");
+
+        if self.run_in_progress {
+            ctx.print_color_centered_at(centre.0, centre.1 - 1, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, "Unsaved run will be lost");
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 1, white, Game::BACKGROUND_COLOUR, "[Y] Quit [N] Stay");
+
+        if self.can_save {
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, white, Game::BACKGROUND_COLOUR, "[S] Save replay, quit");
+        }
+    }
+}
+
+// Explains why a level couldn't be loaded, in place of playing it, until it's dismissed
+pub struct LoadErrorDialog {
+    message: DocumentView
+}
+
+impl LoadErrorDialog {
+    const MESSAGE_DIMENSIONS: (usize, usize) = (Game::MAP_DIMENSIONS.0 as usize - 3, 8);
+
+    pub fn new(error: &LevelError) -> Self {
+        Self {
+            message: DocumentView::new(&[&error.to_string()], Self::MESSAGE_DIMENSIONS.0, Self::MESSAGE_DIMENSIONS.1)
+        }
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32), skin: Skin) {
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 6, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, "CAN'T LOAD LEVEL");
+        self.message.render(ctx, centre.0 - Self::MESSAGE_DIMENSIONS.0 as u32 / 2, centre.1 - 4, skin);
+        ctx.print_color_centered_at(centre.0, centre.1 + 6, white, Game::BACKGROUND_COLOUR, "[Any key] Back");
+    }
+}
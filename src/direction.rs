@@ -1,39 +1,33 @@
-use bracket_terminal::prelude::{
-    Point,
-    VirtualKeyCode
-};
-
-use std::convert::TryFrom;
-
-#[derive(Copy, Clone, PartialEq)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West
-}
-
-impl TryFrom<VirtualKeyCode> for Direction {
-    type Error = ();
-
-    fn try_from(key: VirtualKeyCode) -> Result<Self, ()> {
-        match key {
-            VirtualKeyCode::W | VirtualKeyCode::Up => Ok(Self::North),
-            VirtualKeyCode::A | VirtualKeyCode::Left => Ok(Self::West),
-            VirtualKeyCode::S | VirtualKeyCode::Down => Ok(Self::South),
-            VirtualKeyCode::D | VirtualKeyCode::Right => Ok(Self::East),
-            _ => Err(())
-        }
-    }
-}
-
-impl Into<Point> for Direction {
-    fn into(self) -> Point {
-        Point::from(match self {
-            Self::North => (0, -1),
-            Self::East => (1, 0),
-            Self::South => (0, 1),
-            Self::West => (-1, 0),
-        })
-    }
-}
\ No newline at end of file
+use bracket_terminal::prelude::{Point, VirtualKeyCode};
+
+use std::convert::TryFrom;
+
+pub use crate::engine::Direction;
+use crate::engine::Position;
+
+impl TryFrom<VirtualKeyCode> for Direction {
+    type Error = ();
+
+    fn try_from(key: VirtualKeyCode) -> Result<Self, ()> {
+        match key {
+            VirtualKeyCode::W | VirtualKeyCode::Up | VirtualKeyCode::Numpad8 => Ok(Self::North),
+            VirtualKeyCode::A | VirtualKeyCode::Left | VirtualKeyCode::Numpad4 => Ok(Self::West),
+            VirtualKeyCode::S | VirtualKeyCode::Down | VirtualKeyCode::Numpad2 => Ok(Self::South),
+            VirtualKeyCode::D | VirtualKeyCode::Right | VirtualKeyCode::Numpad6 => Ok(Self::East),
+            VirtualKeyCode::Numpad9 => Ok(Self::NorthEast),
+            VirtualKeyCode::Numpad3 => Ok(Self::SouthEast),
+            VirtualKeyCode::Numpad1 => Ok(Self::SouthWest),
+            VirtualKeyCode::Numpad7 => Ok(Self::NorthWest),
+            _ => Err(())
+        }
+    }
+}
+
+// The way a one-tile step between screen points leads
+impl TryFrom<Point> for Direction {
+    type Error = ();
+
+    fn try_from(delta: Point) -> Result<Self, ()> {
+        Self::try_from(Position::new(delta.x, delta.y))
+    }
+}
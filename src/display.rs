@@ -0,0 +1,399 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    EMBED,
+    RGB,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+
+// The font the window draws its tiles with
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FontChoice {
+    Terminal, // bracket-terminal's own square 8x8 font
+    Vga, // The taller 8x16 VGA font, which draws lettering more crisply
+    Custom // A font sheet loaded from a file
+}
+
+impl FontChoice {
+    pub const ALL: [FontChoice; 3] = [FontChoice::Terminal, FontChoice::Vga, FontChoice::Custom];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FontChoice::Terminal => "Terminal",
+            FontChoice::Vga => "VGA",
+            FontChoice::Custom => "Custom"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FontChoice::Terminal => FontChoice::Vga,
+            FontChoice::Vga => FontChoice::Custom,
+            FontChoice::Custom => FontChoice::Terminal
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            FontChoice::Terminal => FontChoice::Custom,
+            FontChoice::Vga => FontChoice::Terminal,
+            FontChoice::Custom => FontChoice::Vga
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|font| font.name().eq_ignore_ascii_case(name))
+    }
+
+    // Where the font comes among those the window is built with, falling back to the terminal font for a
+    // custom one that wasn't loaded
+    pub fn index(self, custom_loaded: bool) -> usize {
+        match self {
+            FontChoice::Terminal => 0,
+            FontChoice::Vga => 1,
+            FontChoice::Custom if custom_loaded => 2,
+            FontChoice::Custom => 0
+        }
+    }
+}
+
+// How much bigger than its tile size everything is drawn, so the board isn't tiny on high-DPI screens
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Scale {
+    Auto, // Worked out from the screen
+    Percent(u32)
+}
+
+impl Scale {
+    pub const PERCENTS: [u32; 5] = [100, 125, 150, 200, 300]; // Those offered on the display menu
+    pub const MAX_PERCENT: u32 = 400;
+    pub const REFERENCE_HEIGHT: u32 = 1080; // The screen height the tile sizes are made for
+    pub const SCREEN_MARGIN: f64 = 0.9; // The most of the screen a window is scaled up to fill
+
+    pub fn name(self) -> String {
+        match self {
+            Scale::Auto => "Auto".to_string(),
+            Scale::Percent(percent) => format!("{}%", percent)
+        }
+    }
+
+    // The next scale offered, one way or the other, going round from the largest to automatic
+    pub fn step(self, forward: bool) -> Self {
+        let mut choices = vec![Scale::Auto];
+
+        choices.extend(Self::PERCENTS.iter().map(|percent| Scale::Percent(*percent)));
+
+        let index = choices.iter().position(|scale| *scale == self).unwrap_or(0);
+
+        match forward {
+            true => choices[(index + 1) % choices.len()],
+            false => choices[(index + choices.len() - 1) % choices.len()]
+        }
+    }
+
+    // Read "auto" or a percentage, with or without its percent sign
+    pub fn parse(text: &str) -> Option<Self> {
+        if text.eq_ignore_ascii_case("auto") {
+            return Some(Scale::Auto);
+        }
+
+        text.trim_end_matches('%').parse().ok()
+            .filter(|percent| (1..=Self::MAX_PERCENT).contains(percent))
+            .map(Scale::Percent)
+    }
+
+    // How much bigger to draw everything. Automatically that's the DPI factor the backend reports, unless
+    // the screen is tall enough to need more, as high-DPI screens set up at a factor of 1 tend to be
+    pub fn factor(self, dpi_factor: f64, screen_height: Option<u32>) -> f64 {
+        match self {
+            Scale::Auto => {
+                // In quarter steps, so a tile's pixels stay close to whole numbers
+                let screen = screen_height.map_or(1.0, |height| (height as f64 / Self::REFERENCE_HEIGHT as f64 * 4.0).floor() / 4.0);
+
+                dpi_factor.max(screen).max(1.0)
+            },
+            Scale::Percent(percent) => percent as f64 / 100.0
+        }
+    }
+
+    // How much to grow a native window `window` physical pixels in size, built in logical pixels the
+    // backend already multiplies by `dpi_factor`. The window isn't grown past most of the screen
+    pub fn window_factor(self, dpi_factor: f64, screen: Option<(u32, u32)>, window: (u32, u32)) -> f64 {
+        let factor = self.factor(dpi_factor, screen.map(|screen| screen.1)) / dpi_factor;
+
+        match screen {
+            Some(screen) if factor > 1.0 && window.0 > 0 && window.1 > 0 => {
+                let fit = (screen.0 as f64 / window.0 as f64).min(screen.1 as f64 / window.1 as f64) * Self::SCREEN_MARGIN;
+
+                factor.min(fit).max(1.0)
+            },
+            _ => factor
+        }
+    }
+}
+
+// How the window is drawn, kept for the whole machine rather than each profile
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DisplaySettings {
+    pub tile_size: u32, // Pixels along each side of a tile, before scaling
+    pub scale: Scale,
+    pub font: FontChoice,
+    pub font_file: Option<String>, // The custom font last loaded, to load again on the next launch
+    pub vsync: bool, // Whether frames wait for the screen's refresh
+    pub fps_cap: Option<u32> // The most frames drawn per second, if there's a limit
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            tile_size: Game::TILE_DIMENSIONS.0,
+            scale: Scale::Auto,
+            font: FontChoice::Terminal,
+            font_file: None,
+            vsync: true,
+            fps_cap: Some(Game::FRAMES_PER_SECOND as u32)
+        }
+    }
+}
+
+impl DisplaySettings {
+    pub const TILE_SIZES: [u32; 5] = [16, 20, 25, 32, 40]; // Those offered on the display menu
+    pub const MIN_TILE_SIZE: u32 = 8;
+    pub const MAX_TILE_SIZE: u32 = 64;
+    pub const FPS_CAPS: [u32; 4] = [30, 60, 120, 144]; // Those offered on the display menu, along with none
+    pub const MIN_FPS_CAP: u32 = 10;
+
+    // The next tile size offered, larger or smaller, stopping at either end
+    pub fn step_tile_size(&mut self, larger: bool) {
+        let next = match larger {
+            true => Self::TILE_SIZES.iter().copied().find(|size| *size > self.tile_size),
+            false => Self::TILE_SIZES.iter().copied().rev().find(|size| *size < self.tile_size)
+        };
+
+        if let Some(size) = next {
+            self.tile_size = size;
+        }
+    }
+
+    // The next frame rate cap offered, going round from the highest to none
+    pub fn step_fps_cap(&mut self, forward: bool) {
+        let mut caps: Vec<Option<u32>> = Self::FPS_CAPS.iter().copied().map(Some).collect();
+
+        caps.push(None);
+
+        let index = caps.iter().position(|cap| *cap == self.fps_cap).unwrap_or(0);
+
+        self.fps_cap = match forward {
+            true => caps[(index + 1) % caps.len()],
+            false => caps[(index + caps.len() - 1) % caps.len()]
+        };
+    }
+
+    pub fn describe_fps_cap(&self) -> String {
+        self.fps_cap.map_or("Off".to_string(), |cap| cap.to_string())
+    }
+
+    // Whether the window has to be built again for these settings to take effect, when it was built with
+    // `window`. Fonts can change while it's open
+    pub fn needs_restart_from(&self, window: &DisplaySettings) -> bool {
+        self.tile_size != window.tile_size || self.scale != window.scale || self.vsync != window.vsync || self.fps_cap != window.fps_cap
+    }
+
+    // Read a setting saved as `tile_size=<pixels>`, `scale=<auto|percent>`, `font=<name>`,
+    // `font_file=<path>`, `vsync=<on|off>` or `fps_cap=<frames|off>`, ignoring values that aren't understood
+    pub fn parse(&mut self, key: &str, value: &str) {
+        match key {
+            "tile_size" => if let Some(size) = value.parse().ok().filter(|size| (Self::MIN_TILE_SIZE..=Self::MAX_TILE_SIZE).contains(size)) {
+                self.tile_size = size;
+            },
+            "scale" => if let Some(scale) = Scale::parse(value) {
+                self.scale = scale;
+            },
+            "font" => if let Some(font) = FontChoice::parse(value) {
+                self.font = font;
+            },
+            "font_file" if !value.is_empty() => self.font_file = Some(value.to_string()),
+            "vsync" => match value {
+                "on" => self.vsync = true,
+                "off" => self.vsync = false,
+                _ => {}
+            },
+            "fps_cap" if value == "off" => self.fps_cap = None,
+            "fps_cap" => if let Some(cap) = value.parse().ok().filter(|cap| *cap >= Self::MIN_FPS_CAP) {
+                self.fps_cap = Some(cap);
+            },
+            _ => {}
+        }
+    }
+
+    pub fn serialise(&self) -> String {
+        let mut contents = format!(
+            "tile_size={}\nscale={}\nfont={}\nvsync={}\nfps_cap={}\n",
+            self.tile_size,
+            self.scale.name(),
+            self.font.name(),
+            if self.vsync { "on" } else { "off" },
+            self.describe_fps_cap().to_lowercase()
+        );
+
+        if let Some(path) = &self.font_file {
+            contents.push_str(&format!("font_file={}\n", path));
+        }
+
+        contents
+    }
+}
+
+// A font sheet loaded from a file, to draw the window with in place of the built-in fonts
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CustomFont {
+    pub path: String,
+    pub glyph_dimensions: (u32, u32) // The size of each glyph on the sheet, in pixels
+}
+
+impl CustomFont {
+    pub const RESOURCE: &'static str = "custom-font.png"; // What bracket-terminal finds the font under
+    pub const GLYPHS_PER_SIDE: u32 = 16; // Sheets lay out code page 437 in rows of 16, as the built-in fonts do
+
+    // Load the PNG font sheet at `path` and hand it to bracket-terminal, which reads its fonts as the
+    // window is built
+    pub fn load(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|error| format!("Couldn't read {}: {}", path, error))?;
+        let glyph_dimensions = Self::glyph_dimensions(&bytes)?;
+
+        // bracket-terminal keeps its resources for as long as the program runs
+        EMBED.lock().add_resource(format!("resources/{}", Self::RESOURCE), Box::leak(bytes.into_boxed_slice()));
+
+        Ok(Self {
+            path: path.to_string(),
+            glyph_dimensions
+        })
+    }
+
+    // The size of each glyph on a font sheet, or why the image isn't one
+    pub fn glyph_dimensions(bytes: &[u8]) -> Result<(u32, u32), String> {
+        let (info, _) = png::Decoder::new(bytes).read_info().map_err(|error| format!("Not a PNG image: {}", error))?;
+        let (width, height) = (info.width / Self::GLYPHS_PER_SIDE, info.height / Self::GLYPHS_PER_SIDE);
+
+        if width == 0 || height == 0 || info.width % Self::GLYPHS_PER_SIDE != 0 || info.height % Self::GLYPHS_PER_SIDE != 0 {
+            return Err(format!("Expected a sheet of {0}x{0} glyphs, not a {1}x{2} image", Self::GLYPHS_PER_SIDE, info.width, info.height));
+        }
+
+        Ok((width, height))
+    }
+}
+
+// The settings on the menu, in the order they're listed
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Setting {
+    TileSize,
+    Scale,
+    Font,
+    VSync,
+    FpsCap
+}
+
+impl Setting {
+    const ALL: [Setting; 5] = [Setting::TileSize, Setting::Scale, Setting::Font, Setting::VSync, Setting::FpsCap];
+
+    // The setting a step up or down the menu, stopping at either end
+    fn step(self, down: bool) -> Self {
+        let index = Self::ALL.iter().position(|setting| *setting == self).unwrap_or(0);
+
+        match down {
+            true => Self::ALL[(index + 1).min(Self::ALL.len() - 1)],
+            false => Self::ALL[index.saturating_sub(1)]
+        }
+    }
+}
+
+// What the player chose on the display menu
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DisplayChoice {
+    Apply(DisplaySettings),
+    Close
+}
+
+// Picks the tile size, scale, font and frame rate, drawing the game with them as they change. The window
+// keeps the size it was built at, so another tile size is previewed by zooming the map, and all but the
+// font only take effect on the next launch
+pub struct DisplayMenu {
+    settings: DisplaySettings,
+    window: DisplaySettings, // Those the window was built with
+    custom_loaded: bool, // Whether there's a custom font to pick
+    selected: Setting
+}
+
+impl DisplayMenu {
+    pub fn new(settings: &DisplaySettings, window: &DisplaySettings, custom_loaded: bool) -> Self {
+        Self {
+            settings: settings.clone(),
+            window: window.clone(),
+            custom_loaded,
+            selected: Setting::TileSize
+        }
+    }
+
+    // The settings as they stand on the menu, for the game to be drawn with while it's open
+    pub fn settings(&self) -> &DisplaySettings {
+        &self.settings
+    }
+
+    // How much to zoom the map by to preview the chosen tile size
+    pub fn preview_scale(&self) -> f32 {
+        self.settings.tile_size as f32 / self.window.tile_size as f32
+    }
+
+    // Step the selected setting along, one way or the other. A custom font is skipped unless one was loaded
+    fn change(&mut self, forward: bool) {
+        match self.selected {
+            Setting::TileSize => self.settings.step_tile_size(forward),
+            Setting::Scale => self.settings.scale = self.settings.scale.step(forward),
+            Setting::VSync => self.settings.vsync = !self.settings.vsync,
+            Setting::FpsCap => self.settings.step_fps_cap(forward),
+            Setting::Font => loop {
+                self.settings.font = if forward { self.settings.font.next() } else { self.settings.font.previous() };
+
+                if self.settings.font != FontChoice::Custom || self.custom_loaded {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Respond to a key press: `None` while the menu stays open, then what was chosen
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<DisplayChoice> {
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.selected = self.selected.step(false),
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.selected = self.selected.step(true),
+            VirtualKeyCode::Left | VirtualKeyCode::A => self.change(false),
+            VirtualKeyCode::Right | VirtualKeyCode::D => self.change(true),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => return Some(DisplayChoice::Apply(self.settings.clone())),
+            VirtualKeyCode::Escape | VirtualKeyCode::F5 => return Some(DisplayChoice::Close),
+            _ => {}
+        }
+
+        None
+    }
+
+    // Drawn on the HUD layer, over the map being previewed
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+        let top = centre.1 - 6;
+        let colour = |setting| if setting == self.selected { Game::FRUIT_COLOUR } else { RGB::named(white) };
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "DISPLAY");
+        ctx.print_color_centered_at(centre.0, top + 2, colour(Setting::TileSize), Game::BACKGROUND_COLOUR, format!("< Tile size: {}px >", self.settings.tile_size));
+        ctx.print_color_centered_at(centre.0, top + 3, colour(Setting::Scale), Game::BACKGROUND_COLOUR, format!("< Scale: {} >", self.settings.scale.name()));
+        ctx.print_color_centered_at(centre.0, top + 4, colour(Setting::Font), Game::BACKGROUND_COLOUR, format!("< Font: {} >", self.settings.font.name()));
+        ctx.print_color_centered_at(centre.0, top + 5, colour(Setting::VSync), Game::BACKGROUND_COLOUR, format!("< VSync: {} >", if self.settings.vsync { "On" } else { "Off" }));
+        ctx.print_color_centered_at(centre.0, top + 6, colour(Setting::FpsCap), Game::BACKGROUND_COLOUR, format!("< FPS cap: {} >", self.settings.describe_fps_cap()));
+
+        if self.settings.needs_restart_from(&self.window) {
+            ctx.print_color_centered_at(centre.0, top + 8, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, "Applies on restart");
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 6, white, Game::BACKGROUND_COLOUR, "[Enter] Apply [Esc] Back");
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::camera::Camera;
+use crate::object::{Layer, Object, Scene};
+use crate::engine::Position;
+use crate::engine::arena::{Arena, ArenaMask};
+use crate::engine::fruit::FruitKind;
+use crate::engine::skin::Skin;
+use crate::engine::snake::Snake;
+use crate::engine::tags::CellTags;
+use crate::level::Level;
+
+// What painting a tile puts there
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Brush {
+    Wall,
+    Spawn, // Where a snake starts out
+    NoFruit, // Keeps fruit from spawning on the tile
+    Erase
+}
+
+impl Brush {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wall => "Wall",
+            Self::Spawn => "Spawn",
+            Self::NoFruit => "No fruit",
+            Self::Erase => "Erase"
+        }
+    }
+}
+
+// What the player asked of the editor, besides painting
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum EditorAction {
+    Playtest,
+    Save,
+    Close
+}
+
+// Paints a level onto the map, tile by tile, with the keyboard or the mouse, and writes it out in the
+// level file format
+pub struct Editor {
+    dimensions: (u32, u32),
+    walls: HashSet<Position>,
+    spawns: Vec<Position>, // In the order they were painted
+    no_fruit: HashSet<Position>,
+    cursor: Position,
+    brush: Brush
+}
+
+impl Editor {
+    pub const NAME: &'static str = "Edited";
+
+    pub fn new(dimensions: (u32, u32)) -> Self {
+        Self {
+            dimensions,
+            walls: HashSet::new(),
+            spawns: Vec::new(),
+            no_fruit: HashSet::new(),
+            cursor: Position::new(dimensions.0 as i32 / 2, dimensions.1 as i32 / 2),
+            brush: Brush::Wall
+        }
+    }
+
+    // Respond to a key press: the arrow keys move the cursor, space paints under it and the number keys
+    // pick a brush
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<EditorAction> {
+        let step = match key_code {
+            VirtualKeyCode::Up => Position::new(0, -1),
+            VirtualKeyCode::Down => Position::new(0, 1),
+            VirtualKeyCode::Left => Position::new(-1, 0),
+            VirtualKeyCode::Right => Position::new(1, 0),
+            VirtualKeyCode::Space => {
+                self.paint(self.cursor, self.brush);
+                return None;
+            },
+            VirtualKeyCode::Key1 | VirtualKeyCode::Key2 | VirtualKeyCode::Key3 | VirtualKeyCode::Key4 => {
+                self.brush = match key_code {
+                    VirtualKeyCode::Key1 => Brush::Wall,
+                    VirtualKeyCode::Key2 => Brush::Spawn,
+                    VirtualKeyCode::Key3 => Brush::NoFruit,
+                    _ => Brush::Erase
+                };
+                return None;
+            },
+            VirtualKeyCode::P | VirtualKeyCode::Return => return Some(EditorAction::Playtest),
+            VirtualKeyCode::S => return Some(EditorAction::Save),
+            VirtualKeyCode::Escape | VirtualKeyCode::F2 => return Some(EditorAction::Close),
+            _ => return None
+        };
+        let moved = self.cursor + step;
+
+        if moved.in_bounds(self.dimensions) {
+            self.cursor = moved;
+        }
+
+        None
+    }
+
+    // Paint `position` with `brush`, leaving whatever else was there behind
+    pub fn paint(&mut self, position: Position, brush: Brush) {
+        if !position.in_bounds(self.dimensions) {
+            return;
+        }
+
+        self.cursor = position;
+        self.walls.remove(&position);
+        self.spawns.retain(|spawn| *spawn != position);
+        self.no_fruit.remove(&position);
+
+        match brush {
+            Brush::Wall => {
+                self.walls.insert(position);
+            },
+            Brush::Spawn => self.spawns.push(position),
+            Brush::NoFruit => {
+                self.no_fruit.insert(position);
+            },
+            Brush::Erase => {}
+        }
+    }
+
+    // The level painted so far, in the level file format
+    pub fn level_text(&self) -> String {
+        let mut text = format!("# Made with the level editor\nname = {}\n", Self::NAME);
+
+        if !self.walls.is_empty() {
+            text.push('\n');
+
+            for y in 0..self.dimensions.1 as i32 {
+                let row: String = (0..self.dimensions.0 as i32)
+                    .map(|x| if self.walls.contains(&Position::new(x, y)) { Arena::WALL } else { '.' })
+                    .collect();
+
+                text += &format!("mask {}\n", row);
+            }
+        }
+
+        let mut tags: Vec<String> = self.spawns.iter().map(|spawn| format!("tag {} {} {}", spawn.x, spawn.y, CellTags::SPAWN)).collect();
+        let mut no_fruit: Vec<&Position> = self.no_fruit.iter().collect();
+
+        no_fruit.sort_by_key(|position| (position.y, position.x));
+        tags.extend(no_fruit.into_iter().map(|position| format!("tag {} {} {}", position.x, position.y, CellTags::NO_FRUIT)));
+
+        if !tags.is_empty() {
+            text.push('\n');
+            text += &tags.join("\n");
+            text.push('\n');
+        }
+
+        text
+    }
+
+    pub fn level(&self) -> Level {
+        Level::parse(&self.level_text()).expect("The editor wrote a level it can't read back")
+    }
+
+    // The part of the map on screen, following the cursor
+    pub fn camera(&self, view: (u32, u32)) -> Camera {
+        Camera::following(self.dimensions, view, self.cursor)
+    }
+
+    // Paint with the mouse, held over a tile of the map: the left button with the brush, the right to erase
+    pub fn handle_mouse(&mut self, tile: Option<Position>, left: bool, right: bool) {
+        if let (Some(tile), true) = (tile, left || right) {
+            self.paint(tile, if left { self.brush } else { Brush::Erase });
+        }
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, skin: Skin, camera: &Camera) {
+        let mut scene = Scene::default();
+
+        scene.extend(self.walls.iter().map(|wall| Object::new((*wall).into(), skin.wall, ArenaMask::COLOUR).on(Layer::Floor)));
+        scene.extend(self.spawns.iter().map(|spawn| Object::new((*spawn).into(), skin.horizontal, Snake::COLOUR).on(Layer::Body)));
+        scene.extend(self.no_fruit.iter().map(|position| Object::new((*position).into(), skin.fruit(FruitKind::Apple), FruitKind::ROTTEN_COLOUR)));
+        scene.render(ctx, camera);
+
+        if let Some(cursor) = camera.to_screen(self.cursor) {
+            ctx.set_bg(cursor.x, cursor.y, Game::ASSIST_COLOUR);
+        }
+    }
+
+    // What the keys do, along the top and bottom of the HUD layer
+    pub fn render_help(&self, ctx: &mut BTerm, view: (u32, u32)) {
+        let centre = view.0 / 2;
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre, 0, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("EDITOR [1-4] {}", self.brush.name()));
+        ctx.print_color_centered_at(centre, view.1 - 2, white, Game::BACKGROUND_COLOUR, "[Space] Paint [Esc] Back");
+        ctx.print_color_centered_at(centre, view.1 - 1, white, Game::BACKGROUND_COLOUR, "[P] Playtest [S] Save");
+    }
+}
@@ -0,0 +1,405 @@
+use std::convert::TryFrom;
+
+use super::geometry::{Direction, Position};
+use super::schedule::ScriptError;
+
+// The shapes an arena can be generated in, fitted to whatever size the map is
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ArenaShape {
+    #[default]
+    Rectangle, // The whole map
+    Circle,
+    Cross, // Two bands through the middle of the map, one across and one down
+    Donut // A ring, walled off in the middle
+}
+
+impl ArenaShape {
+    pub const ALL: [ArenaShape; 4] = [ArenaShape::Rectangle, ArenaShape::Circle, ArenaShape::Cross, ArenaShape::Donut];
+
+    pub const CROSS_ARM: f64 = 0.4; // How wide the bands of a cross are, as a fraction of the map
+    pub const DONUT_HOLE: f64 = 0.45; // How wide the middle of a donut is, as a fraction of the ring
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rectangle => "Rectangle",
+            Self::Circle => "Circle",
+            Self::Cross => "Cross",
+            Self::Donut => "Donut"
+        }
+    }
+
+    // Whether the tile at `position` is inside the shape, on a map of the given size
+    fn contains(self, position: Position, dimensions: (u32, u32)) -> bool {
+        let (half_width, half_height) = (dimensions.0 as f64 / 2.0, dimensions.1 as f64 / 2.0);
+
+        // How far the middle of the tile is from the middle of the map, where 1 is at the edge
+        let x = (position.x as f64 + 0.5 - half_width) / half_width;
+        let y = (position.y as f64 + 0.5 - half_height) / half_height;
+        let distance = (x * x + y * y).sqrt();
+
+        match self {
+            Self::Rectangle => true,
+            Self::Circle => distance <= 1.0,
+            Self::Cross => x.abs() <= Self::CROSS_ARM || y.abs() <= Self::CROSS_ARM,
+            Self::Donut => (Self::DONUT_HOLE..=1.0).contains(&distance)
+        }
+    }
+}
+
+// Where on the map the snakes can go. Every tile outside the arena is a wall, as deadly as the edges of
+// the map. It's either generated in one of the shapes, or drawn in a level file as a mask, a row of the
+// map at a time, with `#` for walls; tiles past the rows or the ends of rows drawn are walls too
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Arena {
+    Shape(ArenaShape),
+    Mask(Vec<String>)
+}
+
+impl Arena {
+    pub const WALL: char = '#';
+
+    // Lay the arena out on a map of the given size
+    pub fn mask(&self, dimensions: (u32, u32)) -> ArenaMask {
+        let (width, height) = dimensions;
+        let walls = (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| Position::new(x, y)))
+            .map(|position| match self {
+                Self::Shape(shape) => !shape.contains(position, dimensions),
+                Self::Mask(rows) => rows.get(position.y as usize)
+                    .and_then(|row| row.chars().nth(position.x as usize))
+                    .is_none_or(|tile| tile == Self::WALL)
+            })
+            .collect();
+
+        ArenaMask {
+            dimensions,
+            walls,
+            edges: Edges::default()
+        }
+    }
+
+    // Parse a level script line of the form "arena <shape>", or "mask <row>" for the next row of a mask
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: String| ScriptError {
+            line: line_number,
+            message
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["arena", name] => {
+                let shape = ArenaShape::ALL.iter()
+                    .copied()
+                    .find(|shape| shape.name().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| error(format!("unknown arena '{}' (expected rectangle, circle, cross or donut)", name)))?;
+
+                *self = Self::Shape(shape);
+
+                Ok(())
+            },
+            ["mask", row] => {
+                match self {
+                    Self::Mask(rows) => rows.push(row.to_string()),
+                    Self::Shape(_) => *self = Self::Mask(vec![row.to_string()])
+                }
+
+                Ok(())
+            },
+            _ => Err(error("expected 'arena <shape>' or 'mask <row>'".to_string()))
+        }
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::Shape(ArenaShape::default())
+    }
+}
+
+// What lies past an edge of the map
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Edge {
+    #[default]
+    Wall, // As deadly as any other wall
+    Wrap // Leads round to the opposite edge
+}
+
+impl Edge {
+    pub const ALL: [Edge; 2] = [Edge::Wall, Edge::Wrap];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wall => "Wall",
+            Self::Wrap => "Wrap"
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|edge| edge.name().eq_ignore_ascii_case(name))
+    }
+}
+
+// What lies past each of the map's four edges, set one at a time so a map can wrap around one way only,
+// like a cylinder
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Edges {
+    pub north: Edge,
+    pub east: Edge,
+    pub south: Edge,
+    pub west: Edge
+}
+
+impl Edges {
+    // The edge on the `side` of the map the direction heads for. A diagonal heads for a corner, which
+    // only wraps around where both of its sides do
+    pub fn get(self, side: Direction) -> Edge {
+        match side {
+            Direction::North => self.north,
+            Direction::East => self.east,
+            Direction::South => self.south,
+            Direction::West => self.west,
+            corner => {
+                let (across, down) = corner.components();
+
+                match [across, down].iter().flatten().all(|&side| self.get(side) == Edge::Wrap) {
+                    true => Edge::Wrap,
+                    false => Edge::Wall
+                }
+            }
+        }
+    }
+
+    // Set the edge on the `side` of the map, or on both sides of a corner
+    pub fn set(&mut self, side: Direction, edge: Edge) {
+        match side {
+            Direction::North => self.north = edge,
+            Direction::East => self.east = edge,
+            Direction::South => self.south = edge,
+            Direction::West => self.west = edge,
+            corner => {
+                let (across, down) = corner.components();
+
+                for side in [across, down].iter().flatten() {
+                    self.set(*side, edge);
+                }
+            }
+        }
+    }
+
+    // Whether any edge wraps around, or the map is walled in all round
+    pub fn wraps(self) -> bool {
+        Direction::ALL.iter().any(|side| self.get(*side) == Edge::Wrap)
+    }
+
+    // Parse a level script line of the form "edge <north|east|south|west|all> <wall|wrap>"
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: &str| ScriptError {
+            line: line_number,
+            message: message.to_string()
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (side, edge) = match words.as_slice() {
+            ["edge", side, edge] => (side.to_ascii_lowercase(), Edge::parse(edge).ok_or_else(|| error("expected 'wall' or 'wrap'"))?),
+            _ => return Err(error("expected 'edge <side> <wall|wrap>'"))
+        };
+        let sides: &[Direction] = match side.as_str() {
+            "north" => &[Direction::North],
+            "east" => &[Direction::East],
+            "south" => &[Direction::South],
+            "west" => &[Direction::West],
+            "all" => &Direction::ALL,
+            _ => return Err(error("unknown side (expected north, east, south, west or all)"))
+        };
+
+        for side in sides {
+            self.set(*side, edge);
+        }
+
+        Ok(())
+    }
+}
+
+// How the obstacles scattered over the map and the snakes' starting points are matched up, so rival
+// snakes face the same terrain as each other
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Symmetry {
+    #[default]
+    Off,
+    Rotational, // Half a turn about the middle of the map
+    Mirror // Flipped top to bottom
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 3] = [Symmetry::Off, Symmetry::Rotational, Symmetry::Mirror];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Rotational => "Rotational",
+            Self::Mirror => "Mirror"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|symmetry| *symmetry == self).unwrap_or(0);
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let index = Self::ALL.iter().position(|symmetry| *symmetry == self).unwrap_or(0);
+
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    // The tile matching `position` on the other side of a map of the given size, or `None` without symmetry
+    pub fn image(self, position: Position, dimensions: (u32, u32)) -> Option<Position> {
+        let (width, height) = (dimensions.0 as i32, dimensions.1 as i32);
+
+        match self {
+            Self::Off => None,
+            Self::Rotational => Some(Position::new(width - 1 - position.x, height - 1 - position.y)),
+            Self::Mirror => Some(Position::new(position.x, height - 1 - position.y))
+        }
+    }
+
+    // The heading matching `direction` on the other side of the map
+    pub fn facing(self, direction: Direction) -> Direction {
+        match (self, direction) {
+            (Self::Off, direction) => direction,
+            (Self::Rotational, direction) => direction.opposite(),
+            (Self::Mirror, direction) => {
+                let (x, y) = direction.into();
+
+                Direction::try_from(Position::new(x, -y)).unwrap_or(direction)
+            }
+        }
+    }
+}
+
+// An arena laid out on a map, for looking up which tiles are walls as the run plays
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ArenaMask {
+    dimensions: (u32, u32),
+    walls: Vec<bool>, // In row-major order
+    edges: Edges
+}
+
+impl ArenaMask {
+    // The same arena, with the map's edges set to wrap around or not
+    pub fn with_edges(self, edges: Edges) -> Self {
+        Self {
+            edges,
+            ..self
+        }
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    // Where a snake that has just left the map at `position` comes back on, across an edge that wraps
+    // around. Positions on the map, or past an edge that's a wall, are left as they are
+    pub fn wrap(&self, position: Position) -> Position {
+        let (width, height) = (self.dimensions.0 as i32, self.dimensions.1 as i32);
+        let wraps = |side| self.edges.get(side) == Edge::Wrap;
+        let x = match position.x {
+            x if (x < 0 && wraps(Direction::West)) || (x >= width && wraps(Direction::East)) => x.rem_euclid(width),
+            x => x
+        };
+        let y = match position.y {
+            y if (y < 0 && wraps(Direction::North)) || (y >= height && wraps(Direction::South)) => y.rem_euclid(height),
+            y => y
+        };
+
+        Position::new(x, y)
+    }
+
+    // The tiles along the `side` edge of the map, in reading order, or the corner tile for a diagonal
+    pub fn edge_tiles(&self, side: Direction) -> Vec<Position> {
+        let (width, height) = (self.dimensions.0 as i32, self.dimensions.1 as i32);
+
+        match side {
+            Direction::North => (0..width).map(|x| Position::new(x, 0)).collect(),
+            Direction::East => (0..height).map(|y| Position::new(width - 1, y)).collect(),
+            Direction::South => (0..width).map(|x| Position::new(x, height - 1)).collect(),
+            Direction::West => (0..height).map(|y| Position::new(0, y)).collect(),
+            corner => {
+                let (x, y) = corner.into();
+
+                vec![Position::new(if x > 0 { width - 1 } else { 0 }, if y > 0 { height - 1 } else { 0 })]
+            }
+        }
+    }
+
+    // Whether a snake can be at `position`: on the map, and inside the arena
+    pub fn is_open(&self, position: Position) -> bool {
+        position.in_bounds(self.dimensions) && !self.walls[self.index(position)]
+    }
+
+    // Whether `position` is on the map but walled off
+    pub fn is_wall(&self, position: Position) -> bool {
+        position.in_bounds(self.dimensions) && self.walls[self.index(position)]
+    }
+
+    // Every wall on the map, in reading order
+    pub fn walls(&self) -> impl Iterator<Item = Position> + '_ {
+        let width = self.dimensions.0;
+
+        self.walls.iter()
+            .enumerate()
+            .filter(|(_, wall)| **wall)
+            .map(move |(index, _)| Position::new((index as u32 % width) as i32, (index as u32 / width) as i32))
+    }
+
+    // Wall off one more tile of the map, as the run goes on
+    pub fn add_wall(&mut self, position: Position) {
+        if position.in_bounds(self.dimensions) {
+            let index = self.index(position);
+
+            self.walls[index] = true;
+        }
+    }
+
+    // Whether any of the map is walled off, or the arena is the whole of it
+    pub fn has_walls(&self) -> bool {
+        self.walls.contains(&true)
+    }
+
+    // How many tiles of the map are inside the arena
+    pub fn open_tiles(&self) -> usize {
+        self.walls.iter().filter(|wall| !**wall).count()
+    }
+
+    // The first tile inside the arena, in reading order, that can't be reached from the first one, as it's
+    // walled off from the rest. Edges that wrap around join the tiles on either side of them
+    pub fn unreachable(&self) -> Option<Position> {
+        let mut tiles = (0..self.dimensions.1 as i32).flat_map(|y| (0..self.dimensions.0 as i32).map(move |x| Position::new(x, y)));
+        let start = tiles.find(|tile| self.is_open(*tile))?;
+        let mut reached = vec![false; self.walls.len()];
+        let mut frontier = vec![start];
+
+        reached[self.index(start)] = true;
+
+        while let Some(tile) = frontier.pop() {
+            for direction in Direction::ALL.iter().copied() {
+                let next = self.wrap(tile + Position::from(direction));
+
+                if self.is_open(next) && !reached[self.index(next)] {
+                    reached[self.index(next)] = true;
+                    frontier.push(next);
+                }
+            }
+        }
+
+        tiles.find(|tile| self.is_open(*tile) && !reached[self.index(*tile)])
+    }
+
+    fn index(&self, position: Position) -> usize {
+        (position.y as u32 * self.dimensions.0 + position.x as u32) as usize
+    }
+}
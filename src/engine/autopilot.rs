@@ -0,0 +1,79 @@
+use std::collections::VecDeque;
+
+use super::{Direction, Position};
+use super::controller::{BoardView, Controller};
+
+// Heads for the fruit, avoiding any move that would immediately hit a wall, a hazard or the snake. With
+// a lookahead it also passes over moves into pockets with fewer than that many safe tiles to roam, as
+// long as there's somewhere roomier to go
+#[derive(Default)]
+pub struct Autopilot {
+    pub lookahead: usize
+}
+
+impl Autopilot {
+    pub fn with_lookahead(lookahead: usize) -> Self {
+        Self {
+            lookahead
+        }
+    }
+}
+
+impl Controller for Autopilot {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        if !view.snake().alive {
+            return None;
+        }
+
+        steer_towards(view, view.fruit(), self.lookahead)
+    }
+}
+
+// The safe move that gets the snake's head nearest `target`, among those leaving at least `lookahead` safe
+// tiles to roam, or as many as any move leaves
+pub fn steer_towards(view: &BoardView, target: Position, lookahead: usize) -> Option<Direction> {
+    let (head, grid) = (view.head()?, view.grid());
+
+    // Each safe move with how much room it leaves, up to the lookahead
+    let moves: Vec<(Direction, usize)> = grid.directions().iter()
+        .copied()
+        .filter(|&direction| view.is_safe(grid.step(head, direction)))
+        .map(|direction| (direction, room(view, grid.step(head, direction), lookahead)))
+        .collect();
+
+    let roomiest = moves.iter().map(|&(_, room)| room).max()?;
+
+    moves.into_iter()
+        .filter(|&(_, room)| room >= roomiest.min(lookahead))
+        .min_by_key(|&(direction, _)| grid.distance(grid.step(head, direction), target))
+        .map(|(direction, _)| direction)
+}
+
+// How many safe tiles can be reached from `start`, counting no further than `limit`
+fn room(view: &BoardView, start: Position, limit: usize) -> usize {
+    let (width, height) = view.dimensions();
+    let mut seen = vec![false; (width * height) as usize];
+    let mut open = VecDeque::from(vec![start]);
+    let mut count = 0;
+
+    let index = |position: Position| (position.y as u32 * width + position.x as u32) as usize;
+
+    seen[index(start)] = true;
+
+    while let Some(tile) = open.pop_front() {
+        count += 1;
+
+        if count >= limit {
+            break;
+        }
+
+        for next in view.grid().neighbours(tile) {
+            if view.is_safe(next) && !seen[index(next)] {
+                seen[index(next)] = true;
+                open.push_back(next);
+            }
+        }
+    }
+
+    count
+}
@@ -0,0 +1,287 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{Direction, Engine, Position};
+use super::skin::Skin;
+
+// A snapshot of what's on the board, which can be written out as text and read back in, for bug reports,
+// puzzles and tests. In text, every tile is one character:
+//
+//   .  empty             *  fruit             +  bonus fruit       #  hazard
+//   ^ > v <  the head, pointing the way the snake is heading, or X if it's dead
+//   9 3 1 7  the head heading north-east, south-east, south-west or north-west, as on a numpad
+//   ═ ║ ╔ ╗ ╚ ╝ / \  the body, joined up the way the segments are
+//
+// Where the body bends between a diagonal and a straight line it's drawn as whichever line it lies
+// closest to, so only boards whose diagonal stretches join up end to end can be read back in
+//
+// A snake coiled up on itself, as it is when it spawns, only shows its uppermost segments
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Board {
+    pub dimensions: (u32, u32),
+    pub snake: Vec<Position>, // Head first
+    pub heading: Direction,
+    pub alive: bool,
+    pub fruit: Option<Position>,
+    pub bonus_fruits: Vec<Position>, // In row-major order
+    pub hazards: Vec<Position> // Every tile covered by a hazard, in row-major order
+}
+
+// A problem with a board's text
+#[derive(Debug)]
+pub struct BoardError {
+    pub line: usize,
+    pub message: String
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Board {
+    pub const EMPTY: char = '.';
+    pub const FRUIT: char = '*';
+    pub const BONUS_FRUIT: char = '+';
+    pub const HAZARD: char = '#';
+    pub const DEAD_HEAD: char = 'X';
+
+    pub fn capture(engine: &Engine) -> Self {
+        let dimensions = engine.config().dimensions;
+
+        let mut bonus_fruits = engine.frenzy().fruits().to_vec();
+        let mut hazards: Vec<Position> = engine.hazards().iter()
+            .flat_map(|hazard| hazard.tiles(dimensions))
+            .collect();
+
+        for positions in [&mut bonus_fruits, &mut hazards].iter_mut() {
+            positions.sort_by_key(|position| (position.y, position.x));
+            positions.dedup();
+        }
+
+        Self {
+            dimensions,
+            snake: engine.snake().iter().map(|segment| segment.position).collect(),
+            heading: engine.snake().direction(),
+            alive: engine.snake().alive,
+            fruit: Some(engine.fruit()).filter(|fruit| fruit.in_bounds(dimensions)),
+            bonus_fruits,
+            hazards
+        }
+    }
+
+    fn head_glyph(&self) -> char {
+        if !self.alive {
+            return Self::DEAD_HEAD;
+        }
+
+        match self.heading {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::NorthEast => '9',
+            Direction::SouthEast => '3',
+            Direction::SouthWest => '1',
+            Direction::NorthWest => '7'
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, BoardError> {
+        let rows: Vec<Vec<char>> = text.lines()
+            .map(|line| line.trim_end().chars().collect())
+            .filter(|row: &Vec<char>| !row.is_empty())
+            .collect();
+
+        let width = rows.first().map_or(0, Vec::len);
+
+        if width == 0 {
+            return Err(BoardError {line: 1, message: "The board is empty".to_string()});
+        }
+
+        let mut board = Self {
+            dimensions: (width as u32, rows.len() as u32),
+            snake: Vec::new(),
+            heading: Direction::East,
+            alive: true,
+            fruit: None,
+            bonus_fruits: Vec::new(),
+            hazards: Vec::new()
+        };
+        let mut head = None;
+        let mut body = HashMap::new();
+
+        for (y, row) in rows.iter().enumerate() {
+            let error = |message: String| BoardError {line: y + 1, message};
+
+            if row.len() != width {
+                return Err(error(format!("Expected {} tiles, found {}", width, row.len())));
+            }
+
+            for (x, glyph) in row.iter().copied().enumerate() {
+                let position = Position::new(x as i32, y as i32);
+                let heading = match glyph {
+                    '^' => Some(Direction::North),
+                    '>' => Some(Direction::East),
+                    'v' => Some(Direction::South),
+                    '<' => Some(Direction::West),
+                    '9' => Some(Direction::NorthEast),
+                    '3' => Some(Direction::SouthEast),
+                    '1' => Some(Direction::SouthWest),
+                    '7' => Some(Direction::NorthWest),
+                    _ => None
+                };
+
+                if heading.is_some() || glyph == Self::DEAD_HEAD {
+                    if head.is_some() {
+                        return Err(error("There's more than one head".to_string()));
+                    }
+
+                    head = Some(position);
+                    board.alive = heading.is_some();
+                    board.heading = heading.unwrap_or(board.heading);
+                    continue;
+                }
+
+                match glyph {
+                    Self::EMPTY => {},
+                    Self::FRUIT => if board.fruit.replace(position).is_some() {
+                        return Err(error("There's more than one fruit".to_string()));
+                    },
+                    Self::BONUS_FRUIT => board.bonus_fruits.push(position),
+                    Self::HAZARD => board.hazards.push(position),
+                    _ => match connections(glyph) {
+                        Some(joins) => {
+                            body.insert(position, joins);
+                        },
+                        None => return Err(error(format!("Unknown tile '{}'", glyph)))
+                    }
+                }
+            }
+        }
+
+        let head = head.ok_or_else(|| BoardError {line: 1, message: "There's no head".to_string()})?;
+
+        board.snake = trace_body(head, &body)?;
+
+        Ok(board)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (width, height) = self.dimensions;
+        let mut tiles = vec![Self::EMPTY; (width * height) as usize];
+
+        let mut draw = |position: Position, glyph: char| {
+            if position.in_bounds(self.dimensions) {
+                tiles[(position.y as u32 * width + position.x as u32) as usize] = glyph;
+            }
+        };
+
+        for hazard in &self.hazards {
+            draw(*hazard, Self::HAZARD);
+        }
+
+        for fruit in &self.bonus_fruits {
+            draw(*fruit, Self::BONUS_FRUIT);
+        }
+
+        if let Some(fruit) = self.fruit {
+            draw(fruit, Self::FRUIT);
+        }
+
+        // Drawn from the tail up, so the segments nearer the head end up on top
+        for (index, position) in self.snake.iter().enumerate().rev() {
+            if index == 0 {
+                draw(*position, self.head_glyph());
+                continue;
+            }
+
+            let joins = [self.snake.get(index - 1), self.snake.get(index + 1)].iter()
+                .filter_map(|neighbour| neighbour.and_then(|neighbour| Direction::try_from(*neighbour - *position).ok()))
+                .collect::<Vec<_>>();
+
+            draw(*position, glyph(&joins));
+        }
+
+        for row in tiles.chunks(width as usize) {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+// The body glyph joining a segment to its neighbours in `joins`, in the glyphs of the board's text
+fn glyph(joins: &[Direction]) -> char {
+    let skin = Skin {
+        horizontal: '═',
+        vertical: '║',
+        corners: ('╔', '╗', '╚', '╝'),
+        diagonals: ('/', '\\'),
+        ..Skin::default()
+    };
+
+    skin.joining(joins)
+}
+
+// The directions a body glyph joins up with
+fn connections(glyph: char) -> Option<[Direction; 2]> {
+    use Direction::*;
+
+    match glyph {
+        '═' => Some([East, West]),
+        '║' => Some([North, South]),
+        '╔' => Some([East, South]),
+        '╗' => Some([South, West]),
+        '╚' => Some([North, East]),
+        '╝' => Some([North, West]),
+        '/' => Some([NorthEast, SouthWest]),
+        '\\' => Some([NorthWest, SouthEast]),
+        _ => None
+    }
+}
+
+// Follow the body from the head to the tail, one joined-up segment at a time
+fn trace_body(head: Position, body: &HashMap<Position, [Direction; 2]>) -> Result<Vec<Position>, BoardError> {
+    let error = |message: &str| BoardError {line: head.y as usize + 1, message: message.to_string()};
+    let joins_back = |position: Position, to: Position| {
+        body.get(&position).is_some_and(|joins| joins.iter().any(|&direction| position + Position::from(direction) == to))
+    };
+
+    let mut snake = vec![head];
+    let mut visited = HashSet::new();
+
+    let necks: Vec<Position> = Direction::EIGHT_WAYS.iter()
+        .map(|&direction| head + Position::from(direction))
+        .filter(|&neighbour| joins_back(neighbour, head))
+        .collect();
+
+    if necks.len() > 1 {
+        return Err(error("More than one segment joins the head"));
+    }
+
+    let (mut previous, mut current) = (head, necks.first().copied());
+
+    while let Some(position) = current {
+        if !visited.insert(position) {
+            return Err(error("The body loops back on itself"));
+        }
+
+        snake.push(position);
+
+        current = body[&position].iter()
+            .map(|&direction| position + Position::from(direction))
+            .find(|&next| next != previous && joins_back(next, position) && !visited.contains(&next));
+        previous = position;
+    }
+
+    if visited.len() != body.len() {
+        return Err(error("Some of the body isn't joined to the head"));
+    }
+
+    Ok(snake)
+}
@@ -0,0 +1,92 @@
+use super::{Direction, Position};
+use super::autopilot;
+use super::controller::{BoardView, Controller};
+
+// The bosses a campaign level can pit the player against, each a bot with a way of playing all its own
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BossKind {
+    Cutter, // Heads for the tiles just ahead of the player's snake, to cut it off
+    Guard // Keeps between the player's snake and the fruit, and snatches the fruit once it comes close
+}
+
+impl BossKind {
+    pub const ALL: [BossKind; 2] = [BossKind::Cutter, BossKind::Guard];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Cutter => "Cutter",
+            Self::Guard => "Guard"
+        }
+    }
+
+    // A fresh bot that plays as the boss
+    pub fn controller(self) -> Box<dyn Controller> {
+        match self {
+            Self::Cutter => Box::new(Cutter),
+            Self::Guard => Box::new(Guard)
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.name().eq_ignore_ascii_case(name))
+    }
+}
+
+// Tiles of room a boss wants ahead of it, so it doesn't corner itself chasing the player
+const LOOKAHEAD: usize = 20;
+
+// The player's snake's head and heading, as long as it's alive
+fn player(view: &BoardView) -> Option<(Position, Direction)> {
+    view.snakes().first()
+        .filter(|snake| snake.alive)
+        .and_then(|snake| snake.front().map(|head| (head.position, snake.direction())))
+}
+
+pub struct Cutter;
+
+impl Cutter {
+    pub const AHEAD: i32 = 3; // How far ahead of the player's head the cutter aims
+}
+
+impl Controller for Cutter {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        if !view.snake().alive {
+            return None;
+        }
+
+        let target = match player(view) {
+            Some((head, heading)) => head + Position::from(heading) * Self::AHEAD,
+            None => view.fruit()
+        };
+
+        autopilot::steer_towards(view, target, LOOKAHEAD)
+    }
+}
+
+pub struct Guard;
+
+impl Guard {
+    pub const DISTANCE: i32 = 2; // How far from the fruit the guard keeps watch, on the player's side
+    pub const SNATCH_RADIUS: i32 = 6; // How close the player gets to the fruit before the guard goes for it
+}
+
+impl Controller for Guard {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        if !view.snake().alive {
+            return None;
+        }
+
+        let fruit = view.fruit();
+        let target = match player(view) {
+            Some((head, _)) if head.manhattan_distance(fruit) > Self::SNATCH_RADIUS => {
+                // Step from the fruit towards the player, one axis at a time
+                let offset = Position::new((head.x - fruit.x).signum(), (head.y - fruit.y).signum());
+
+                fruit + offset * Self::DISTANCE
+            },
+            _ => fruit
+        };
+
+        autopilot::steer_towards(view, target, LOOKAHEAD)
+    }
+}
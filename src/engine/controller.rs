@@ -0,0 +1,150 @@
+use super::{Engine, Direction, Grid, Position};
+use super::hazard::Hazard;
+use super::arena::ArenaMask;
+use super::mode::InputPolicy;
+use super::power_up::PowerUp;
+use super::snake::Snake;
+
+// Whatever steers a snake, be it a player at the keyboard or a bot. It's asked for a direction before
+// every slither, and `None` keeps the snake heading the same way
+pub trait Controller {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction>;
+}
+
+// A read-only look at the board, as the controller of one of its snakes sees it
+pub struct BoardView<'a> {
+    engine: &'a Engine,
+    index: usize // Where the controller's snake comes in `Engine::snakes`
+}
+
+impl<'a> BoardView<'a> {
+    pub fn new(engine: &'a Engine, index: usize) -> Self {
+        Self {
+            engine,
+            index
+        }
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.engine.config().dimensions
+    }
+
+    // How the tiles are laid out, and so which ways the snake can move
+    pub fn grid(&self) -> Grid {
+        self.engine.config().grid
+    }
+
+    // The controller's own snake
+    pub fn snake(&self) -> &'a Snake {
+        &self.engine.snakes()[self.index]
+    }
+
+    // Every snake on the map, the controller's own included
+    pub fn snakes(&self) -> &'a [Snake] {
+        self.engine.snakes()
+    }
+
+    // Whether any snake but the controller's own is on `position`
+    pub fn is_taken_by_others(&self, position: Position) -> bool {
+        self.engine.segments_at(position) > self.snake().segments_at(position)
+    }
+
+    pub fn head(&self) -> Option<Position> {
+        self.snake().front().map(|segment| segment.position)
+    }
+
+    pub fn tail(&self) -> Option<Position> {
+        self.snake().back().map(|segment| segment.position)
+    }
+
+    // The way the snake will move on its next slither
+    pub fn heading(&self) -> Direction {
+        self.snake().direction()
+    }
+
+    // The fruit nearest the snake's head
+    pub fn fruit(&self) -> Position {
+        let head = self.head();
+
+        self.engine.fruits().iter()
+            .map(|fruit| fruit.position)
+            .min_by_key(|fruit| head.map_or(0, |head| head.manhattan_distance(*fruit)))
+            .unwrap_or_else(|| self.engine.fruit())
+    }
+
+    // Frenzy fruit on the board, if a frenzy is underway
+    pub fn bonus_fruits(&self) -> &'a [Position] {
+        self.engine.frenzy().fruits()
+    }
+
+    pub fn arena(&self) -> &'a ArenaMask {
+        self.engine.arena()
+    }
+
+    pub fn hazards(&self) -> &'a [Hazard] {
+        self.engine.hazards()
+    }
+
+    pub fn input_policy(&self) -> InputPolicy {
+        self.engine.rules().input_policy()
+    }
+
+    // Whether the head could move onto `position` on the next slither and live. The tail moves out of
+    // the way as the head moves, so its tile is safe unless the snake is coiled up on it. Other snakes'
+    // bodies are given a wide berth, tails and all
+    pub fn is_safe(&self, position: Position) -> bool {
+        let tail_segments = if Some(position) == self.tail() { 1 } else { 0 };
+
+        self.arena().is_open(position) &&
+        self.snake().segments_at(position) == tail_segments &&
+        !self.is_taken_by_others(position) &&
+        !self.hazards().iter().any(|hazard| hazard.covers(position))
+    }
+}
+
+// Steers by the player's key presses. Presses are collected as they happen and played back before the
+// next slither, under the same input policy the engine applies to steering. While the snake is confused
+// every press is taken the opposite way
+#[derive(Default)]
+pub struct KeyboardController {
+    presses: Vec<Direction>
+}
+
+impl KeyboardController {
+    pub fn press(&mut self, direction: Direction) {
+        self.presses.push(direction);
+    }
+
+    pub fn clear(&mut self) {
+        self.presses.clear();
+    }
+
+    // Whether any presses are waiting for the next slither
+    pub fn has_presses(&self) -> bool {
+        !self.presses.is_empty()
+    }
+}
+
+impl Controller for KeyboardController {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        let mut presses = std::mem::take(&mut self.presses);
+
+        if view.snake().has_effect(PowerUp::Confusion) {
+            presses.iter_mut().for_each(|direction| *direction = direction.opposite());
+        }
+
+        match view.input_policy() {
+            InputPolicy::SingleBuffered => presses.first().copied(),
+            InputPolicy::Immediate => {
+                let head = view.head()?;
+                let neck = view.snake().get(1).map(|segment| segment.position);
+
+                // Every press takes effect as it comes, apart from those turning back on the neck, so the last
+                // one that can be taken wins
+                presses.into_iter()
+                    .rev()
+                    .find(|&direction| Some(view.grid().step(head, direction)) != neck)
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use super::Engine;
+use super::autopilot::Autopilot;
+use super::controller::Controller;
+use super::pathfinding::AStarBot;
+
+// How well the computer plays the snakes it steers, from a bot that chases the fruit with no thought for
+// what comes after to one that plans every path and keeps a way out
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Difficulty {
+    Greedy, // Straight for the fruit, looking no further than the next tile
+    SafeGreedy, // Straight for the fruit, steering clear of pockets too small to turn around in
+    #[default]
+    NearPerfect // Plans a path to the fruit, only taking it if the snake can still reach its tail
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Greedy, Difficulty::SafeGreedy, Difficulty::NearPerfect];
+
+    pub const SAFE_LOOKAHEAD: usize = 20; // Tiles of room the safe-greedy bot wants ahead of it
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Greedy => "Greedy",
+            Self::SafeGreedy => "Safe",
+            Self::NearPerfect => "Expert"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|difficulty| *difficulty == self).unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    // A fresh bot that plays at this difficulty
+    pub fn controller(self) -> Box<dyn Controller> {
+        match self {
+            Self::Greedy => Box::new(Autopilot::default()),
+            Self::SafeGreedy => Box::new(Autopilot::with_lookahead(Self::SAFE_LOOKAHEAD)),
+            Self::NearPerfect => Box::new(AStarBot::default())
+        }
+    }
+
+    // A fresh bot for each of `engine`'s snakes that no player steers, in the order they come after the
+    // players' snakes
+    pub fn bots_for(self, engine: &Engine) -> Vec<Box<dyn Controller>> {
+        (engine.rules().players()..engine.snakes().len())
+            .map(|_| self.controller())
+            .collect()
+    }
+}
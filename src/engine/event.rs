@@ -0,0 +1,50 @@
+use super::{Direction, Position};
+use super::power_up::PowerUp;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DeathCause {
+    Wall,
+    Tail, // The snake ran into its own body
+    Hazard,
+    Snake // The snake ran into another snake
+}
+
+/// Something notable that happened during a run, for observers to react to
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    /// Fruit was eaten, earning `points` after any combo multiplier
+    FruitEaten { points: usize },
+    /// Fruit was left to rot, and has spawned again somewhere else
+    FruitRotted,
+    /// A snake died, whether or not it had lives to spare. `snake` is where it comes in
+    /// [`Engine::snakes`](super::Engine::snakes), the player's being 0, and `position` is where its head
+    /// was, kept on the map for deaths against the wall
+    Died { cause: DeathCause, snake: usize, position: Position },
+    /// A snake that lost a life found nowhere on the map to respawn, losing the life it would have
+    /// come back with as well
+    Crowded { snake: usize },
+    /// The snake changed direction
+    Turned(Direction),
+    /// The player paused the game. Only a `Game` reports this, as the engine has no notion of pausing
+    Paused,
+    /// The player unpaused the game
+    Resumed,
+    /// The snake collected a power-up
+    PowerUpCollected(PowerUp),
+    /// A shield saved the snake from running into a wall or itself, stopping it for a slither
+    Shielded,
+    /// The snake filled the map
+    Won
+}
+
+/// Something that wants to hear about the events of a run, such as audio, achievements or stats.
+/// Closures taking a `&GameEvent` can be used as observers directly
+pub trait Observer {
+    fn notify(&mut self, event: &GameEvent);
+}
+
+impl<F: FnMut(&GameEvent)> Observer for F {
+    fn notify(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,78 @@
+use rand::Rng;
+
+use super::geometry::Position;
+use super::occupancy::Occupancy;
+
+// The cells of the map with nothing on them, kept up to date as things come and go so that a free cell
+// can be picked in constant time. Tiles outside the map are ignored
+pub struct FreeCells {
+    occupants: Occupancy,
+    free: Vec<Position>,
+    free_index: Vec<Option<usize>> // Where each cell sits in `free`, if it's free
+}
+
+impl FreeCells {
+    pub fn new(dimensions: (u32, u32)) -> Self {
+        let free: Vec<Position> = (0..dimensions.1 as i32)
+            .flat_map(|y| (0..dimensions.0 as i32).map(move |x| Position::new(x, y)))
+            .collect();
+
+        Self {
+            occupants: Occupancy::new(dimensions),
+            free_index: (0..free.len()).map(Some).collect(),
+            free
+        }
+    }
+
+    pub fn occupy(&mut self, position: Position) {
+        self.occupants.add(position);
+
+        if let Some(cell) = self.occupants.index(position) {
+            if let Some(index) = self.free_index[cell].take() {
+                self.free.swap_remove(index);
+
+                // The last free cell was moved into the gap
+                if let Some(moved) = self.free.get(index).copied() {
+                    let moved_cell = self.occupants.index(moved).unwrap();
+
+                    self.free_index[moved_cell] = Some(index);
+                }
+            }
+        }
+    }
+
+    pub fn vacate(&mut self, position: Position) {
+        self.occupants.remove(position);
+
+        if let Some(cell) = self.occupants.index(position) {
+            if !self.occupants.is_occupied(position) && self.free_index[cell].is_none() {
+                self.free_index[cell] = Some(self.free.len());
+                self.free.push(position);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.occupants.index(position).is_some_and(|cell| self.free_index[cell].is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[Position] {
+        &self.free
+    }
+
+    pub fn choose<R: Rng>(&self, rng: &mut R) -> Option<Position> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.free[rng.gen_range(0..self.free.len())])
+        }
+    }
+}
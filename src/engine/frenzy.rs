@@ -0,0 +1,56 @@
+use super::geometry::Position;
+
+// A feeding frenzy: every so often a burst of extra fruit appears for a short while, and whatever
+// isn't eaten in time vanishes again
+pub struct Frenzy {
+    fruits: Vec<Position>,
+    active: bool
+}
+
+impl Frenzy {
+    pub const INTERVAL: f64 = 120.0; // Seconds between the end of one frenzy and the start of the next
+    pub const DURATION: f64 = 10.0;
+    pub const FRUIT_COUNT: usize = 8;
+    pub const FRUIT_POINTS: usize = 1;
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn fruits(&self) -> &[Position] {
+        &self.fruits
+    }
+
+    // Scatter fruit over the given spawn points
+    pub fn start(&mut self, spawn_points: &[Position]) {
+        self.fruits = spawn_points.iter()
+            .take(Self::FRUIT_COUNT)
+            .copied()
+            .collect();
+        self.active = true;
+    }
+
+    // Clear away any fruit left over once the frenzy is over
+    pub fn end(&mut self) {
+        self.fruits.clear();
+        self.active = false;
+    }
+
+    // Remove the fruit at `position`, returning whether there was one to eat
+    pub fn eat(&mut self, position: Position) -> bool {
+        let count = self.fruits.len();
+
+        self.fruits.retain(|fruit| *fruit != position);
+
+        self.fruits.len() < count
+    }
+}
+
+impl Default for Frenzy {
+    fn default() -> Self {
+        Self {
+            fruits: Vec::with_capacity(Self::FRUIT_COUNT),
+            active: false
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use super::geometry::Position;
+use super::schedule::ScriptError;
+
+// The kinds of fruit that can spawn, each with its own worth
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum FruitKind {
+    #[default]
+    Apple,
+    Cherry,
+    Grape,
+    Mouse // Runs away from the snake
+}
+
+// A fruit on the map
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Fruit {
+    pub position: Position,
+    pub kind: FruitKind,
+    pub spawned_at: f64, // Run time, in seconds
+    pub age: u32 // Slithers since it spawned, for timing the steps of fleeing fruit
+}
+
+// What's left of a rival snake that died, one for each tile of its body, for the others to eat
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pellet {
+    pub position: Position,
+    pub snake: usize // Where the snake it came from comes in `Engine::snakes`
+}
+
+impl Pellet {
+    pub const POINTS: usize = 1;
+}
+
+// How many points a kind of fruit is worth under flat scoring, and how often it spawns relative to
+// the other kinds
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FruitSpec {
+    pub points: usize,
+    pub weight: f64
+}
+
+// What every kind of fruit is worth and how likely it is to spawn, and whether it rots, as set by the
+// level being played with lines such as "fruit cherry points=2 weight=3" and "fruit rot 20s"
+#[derive(Clone, PartialEq, Debug)]
+pub struct FruitTable {
+    specs: [FruitSpec; FruitKind::ALL.len()], // In the order of `FruitKind::ALL`
+    rot_seconds: Option<f64> // How long fruit stays fresh, if it rots at all
+}
+
+impl FruitKind {
+    pub const ALL: [FruitKind; 4] = [FruitKind::Apple, FruitKind::Cherry, FruitKind::Grape, FruitKind::Mouse];
+
+    pub const FLEE_INTERVAL: u32 = 3; // Slithers between each step a fleeing fruit takes
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Apple => "Apple",
+            Self::Cherry => "Cherry",
+            Self::Grape => "Grape",
+            Self::Mouse => "Mouse"
+        }
+    }
+
+    // Whether the fruit moves away from the snake
+    pub fn flees(self) -> bool {
+        self == Self::Mouse
+    }
+
+    // Where the kind comes in `ALL`
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|kind| *kind == self).unwrap()
+    }
+}
+
+impl FruitTable {
+    pub const ROTTEN_SECONDS: f64 = 5.0; // How long rotten fruit lingers before spawning somewhere else
+
+    pub fn rot_seconds(&self) -> Option<f64> {
+        self.rot_seconds
+    }
+
+    pub fn set_rot_seconds(&mut self, seconds: Option<f64>) {
+        self.rot_seconds = seconds;
+    }
+
+    pub fn spec(&self, kind: FruitKind) -> FruitSpec {
+        self.specs[kind.index()]
+    }
+
+    pub fn set(&mut self, kind: FruitKind, spec: FruitSpec) {
+        self.specs[kind.index()] = spec;
+    }
+
+    // Pick the kind of the next fruit by weight, falling back to apples if every weight is zero
+    pub fn choose(&self, rng: &mut impl Rng) -> FruitKind {
+        FruitKind::ALL.choose_weighted(rng, |kind| self.spec(*kind).weight)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Parse a level script line of the form "fruit <kind> points=<n> weight=<n>", where either setting
+    // can be left out to keep its default, or "fruit rot <seconds>s"
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: String| ScriptError {
+            line: line_number,
+            message
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let (name, settings) = match words.as_slice() {
+            ["fruit", "rot", seconds] => {
+                let seconds = seconds.strip_suffix('s')
+                    .and_then(|number| number.parse::<f64>().ok())
+                    .filter(|seconds| *seconds > 0.0)
+                    .ok_or_else(|| error(format!("invalid time '{}' (expected e.g. '20s')", seconds)))?;
+
+                self.rot_seconds = Some(seconds);
+
+                return Ok(());
+            },
+            ["fruit", name, settings @ ..] => (name, settings),
+            _ => return Err(error("expected 'fruit <kind> points=<n> weight=<n>' or 'fruit rot <seconds>s'".to_string()))
+        };
+        let kind = FruitKind::ALL.iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| error(format!("unknown fruit '{}' (expected apple, cherry, grape or mouse)", name)))?;
+        let mut spec = self.spec(kind);
+
+        for setting in settings {
+            match setting.split_once('=') {
+                Some(("points", points)) => spec.points = points.parse()
+                    .map_err(|_| error(format!("invalid points '{}'", points)))?,
+                Some(("weight", weight)) => spec.weight = weight.parse::<f64>()
+                    .ok()
+                    .filter(|weight| *weight >= 0.0)
+                    .ok_or_else(|| error(format!("invalid weight '{}'", weight)))?,
+                _ => return Err(error(format!("unknown fruit setting '{}' (expected points or weight)", setting)))
+            }
+        }
+
+        self.set(kind, spec);
+
+        Ok(())
+    }
+}
+
+impl Default for FruitTable {
+    fn default() -> Self {
+        Self {
+            specs: [
+                FruitSpec {points: 1, weight: 6.0},
+                FruitSpec {points: 2, weight: 3.0},
+                FruitSpec {points: 3, weight: 1.0},
+                FruitSpec {points: 5, weight: 0.5}
+            ],
+            rot_seconds: None
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::convert::TryFrom;
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+// A tile on the map, with (0, 0) at the top-left corner
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32
+}
+
+impl Position {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self {
+            x,
+            y
+        }
+    }
+
+    // Whether the position lies on a map of the given dimensions
+    pub fn in_bounds(self, dimensions: (u32, u32)) -> bool {
+        self.x >= 0 && self.x < dimensions.0 as i32 &&
+        self.y >= 0 && self.y < dimensions.1 as i32
+    }
+
+    pub fn manhattan_distance(self, other: Position) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Position {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Position {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Position {
+    type Output = Self;
+
+    fn mul(self, factor: i32) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl From<(i32, i32)> for Position {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+    // Only taken on diagonal and hex grids
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West]; // Along the grid
+    pub const DIAGONALS: [Direction; 4] = [Direction::NorthEast, Direction::SouthEast, Direction::SouthWest, Direction::NorthWest];
+    pub const EIGHT_WAYS: [Direction; 8] = [
+        Direction::North, Direction::NorthEast, Direction::East, Direction::SouthEast,
+        Direction::South, Direction::SouthWest, Direction::West, Direction::NorthWest
+    ];
+    pub const HEX_WAYS: [Direction; 6] = [
+        Direction::East, Direction::SouthEast, Direction::SouthWest, Direction::West, Direction::NorthWest, Direction::NorthEast
+    ];
+
+    // The direction taking a step of (x, y), which has to be one of the eight
+    fn of_step(x: i32, y: i32) -> Self {
+        Self::try_from(Position::new(x, y)).expect("A step to a neighbouring tile")
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Self::DIAGONALS.contains(&self)
+    }
+
+    pub fn opposite(self) -> Self {
+        let (x, y) = self.into();
+
+        Self::of_step(-x, -y)
+    }
+
+    // A quarter turn anticlockwise, as seen looking down on the map
+    pub fn turn_left(self) -> Self {
+        let (x, y) = self.into();
+
+        Self::of_step(y, -x)
+    }
+
+    // A quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        let (x, y) = self.into();
+
+        Self::of_step(-y, x)
+    }
+
+    // The ways along the grid a direction is made up of: east and west, then north and south. North-east
+    // is made up of east and north, while north is made up of north alone
+    pub fn components(self) -> (Option<Direction>, Option<Direction>) {
+        let (x, y) = self.into();
+        let part = |x, y| Some(Self::of_step(x, y)).filter(|_| (x, y) != (0, 0));
+
+        (part(x, 0), part(0, y))
+    }
+}
+
+impl From<Direction> for (i32, i32) {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthEast => (1, -1),
+            Direction::SouthEast => (1, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::NorthWest => (-1, -1)
+        }
+    }
+}
+
+impl From<Direction> for Position {
+    fn from(direction: Direction) -> Self {
+        Position::from(<(i32, i32)>::from(direction))
+    }
+}
+
+// The way one step of `delta` leads, for a delta between neighbouring tiles, diagonals included
+impl TryFrom<Position> for Direction {
+    type Error = ();
+
+    fn try_from(delta: Position) -> Result<Self, ()> {
+        Self::EIGHT_WAYS.iter()
+            .copied()
+            .find(|&direction| Position::from(direction) == delta)
+            .ok_or(())
+    }
+}
+
+// How the map's tiles are laid out, and so which ways the snake can move from one to the next
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Grid {
+    #[default]
+    Square, // Four ways, along the grid
+    Diagonal, // Eight ways, along the grid and diagonally
+    Hex // Six ways, with the odd rows set half a tile to the right of the even ones
+}
+
+impl Grid {
+    pub const ALL: [Grid; 3] = [Grid::Square, Grid::Diagonal, Grid::Hex];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Square => "Square",
+            Self::Diagonal => "Diagonal",
+            Self::Hex => "Hex"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Square => Self::Diagonal,
+            Self::Diagonal => Self::Hex,
+            Self::Hex => Self::Square
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            Self::Square => Self::Hex,
+            Self::Diagonal => Self::Square,
+            Self::Hex => Self::Diagonal
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|grid| grid.name().eq_ignore_ascii_case(name))
+    }
+
+    // The ways the snake can move
+    pub fn directions(self) -> &'static [Direction] {
+        match self {
+            Self::Square => &Direction::ALL,
+            Self::Diagonal => &Direction::EIGHT_WAYS,
+            Self::Hex => &Direction::HEX_WAYS
+        }
+    }
+
+    // The way the grid has for a press of `direction` by a snake heading along `heading`. A hex grid has
+    // no straight up or down, so those lean whichever way the snake is heading across. Directions the
+    // grid has nothing close to are turned away
+    pub fn nearest(self, direction: Direction, heading: Direction) -> Option<Direction> {
+        let ((x, y), (heading_x, _)) = (direction.into(), heading.into());
+
+        match self {
+            _ if self.directions().contains(&direction) => Some(direction),
+            Self::Hex if x == 0 => Direction::try_from(Position::new(if heading_x < 0 { -1 } else { 1 }, y)).ok(),
+            _ => None
+        }
+    }
+
+    // The step `direction` takes from a tile on `row`. On a hex grid a step up or down from an odd row
+    // lands a tile further right than it would from an even one, as the odd rows are set to the right
+    pub fn offset(self, row: i32, direction: Direction) -> Position {
+        let step = Position::from(direction);
+        let odd = row.rem_euclid(2);
+
+        match self {
+            Self::Hex if step.y != 0 => Position::new(if step.x > 0 { odd } else { odd - 1 }, step.y),
+            _ => step
+        }
+    }
+
+    // The tile a step along `direction` from `tile` leads to, without wrapping around the map
+    pub fn step(self, tile: Position, direction: Direction) -> Position {
+        tile + self.offset(tile.y, direction)
+    }
+
+    // The tiles a step away from `tile`, in the order of `directions`
+    pub fn neighbours(self, tile: Position) -> impl Iterator<Item = Position> {
+        self.directions().iter().map(move |&direction| self.step(tile, direction))
+    }
+
+    // The way from `from` to `to`, for tiles next to each other. Tiles either side of an edge that wraps
+    // around count as next to each other, so this is only meant for tiles known to be close, such as the
+    // segments of a snake
+    pub fn direction_between(self, from: Position, to: Position) -> Option<Direction> {
+        let unwrap = |offset: i32| if offset.abs() > 1 { -offset.signum() } else { offset };
+        let delta = Position::new(unwrap(to.x - from.x), unwrap(to.y - from.y));
+
+        self.directions().iter().copied().find(|&direction| self.offset(from.y, direction) == delta)
+    }
+
+    // How many moves it takes to get from `from` to `to`, walls aside
+    pub fn distance(self, from: Position, to: Position) -> i32 {
+        // Hex tiles are counted along three axes: across, down, and the diagonal the two make
+        let axial = |tile: Position| (tile.x - (tile.y - tile.y.rem_euclid(2)) / 2, tile.y);
+
+        match self {
+            Self::Square => from.manhattan_distance(to),
+            Self::Diagonal => (from.x - to.x).abs().max((from.y - to.y).abs()),
+            Self::Hex => {
+                let ((q, r), (to_q, to_r)) = (axial(from), axial(to));
+
+                ((q - to_q).abs() + (r - to_r).abs() + (q + r - to_q - to_r).abs()) / 2
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::Status;
+use super::mode::{GameMode, RunState};
+use super::schedule::ScriptError;
+
+// What a level of the campaign asks of the player before moving on to the next one
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Goal {
+    Length(usize), // Grow the snake to this many segments
+    Score {
+        points: usize,
+        seconds: Option<f64> // The time limit, if there is one
+    },
+    Survive(f64), // Stay alive this many seconds, or outlive the boss
+    OutEat(usize) // Be the first to eat this many fruit, before the boss does
+}
+
+impl Goal {
+    // The goal in a few words, short enough to fit across the map
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Length(length) => format!("Grow to {} long", length),
+            Self::Score {points, seconds: None} => format!("Score {} points", points),
+            Self::Score {points, seconds: Some(seconds)} => format!("Score {} in {}s", points, seconds),
+            Self::Survive(seconds) => format!("Survive {}s", seconds),
+            Self::OutEat(fruits) => format!("Eat {} before the boss", fruits)
+        }
+    }
+
+    // How far along the goal is, for goals against the clock or the boss
+    pub fn progress(&self, run: &RunState) -> Option<String> {
+        match *self {
+            Self::Score {seconds: Some(seconds), ..} | Self::Survive(seconds) => Some(format!("{}s left", (seconds - run.time).max(0.0).ceil())),
+            Self::OutEat(_) => Some(format!("Fruit: {} v {}", run.fruits_eaten, run.rival_fruits)),
+            _ => None
+        }
+    }
+
+    pub fn is_met(&self, run: &RunState) -> bool {
+        match *self {
+            Self::Length(length) => run.snake_length >= length,
+            Self::Score {points, ..} => run.points >= points,
+            Self::Survive(seconds) => run.time >= seconds,
+            Self::OutEat(fruits) => run.fruits_eaten >= fruits
+        }
+    }
+
+    // Whether the time limit has run out, or the boss has eaten its fill, with the goal still to be met
+    pub fn is_failed(&self, run: &RunState) -> bool {
+        match *self {
+            Self::Score {seconds: Some(seconds), ..} => run.time >= seconds && !self.is_met(run),
+            Self::OutEat(fruits) => run.rival_fruits >= fruits && !self.is_met(run),
+            _ => false
+        }
+    }
+
+    // Parse a level script line of the form "goal length <segments>", "goal score <points> [within
+    // <seconds>s]", "goal survive <seconds>s" or "goal outeat <fruits>"
+    pub fn parse_line(line_number: usize, line: &str) -> Result<Self, ScriptError> {
+        let error = |message: &str| ScriptError {
+            line: line_number,
+            message: message.to_string()
+        };
+        let number = |word: &str| word.trim_end_matches('s').parse::<f64>().ok().filter(|number| *number > 0.0);
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["goal", "length", length] => length.parse()
+                .map(Self::Length)
+                .map_err(|_| error("expected a whole number of segments")),
+            ["goal", "score", points] => points.parse()
+                .map(|points| Self::Score {points, seconds: None})
+                .map_err(|_| error("expected a whole number of points")),
+            ["goal", "score", points, "within", seconds] => match (points.parse(), number(seconds)) {
+                (Ok(points), Some(seconds)) => Ok(Self::Score {points, seconds: Some(seconds)}),
+                _ => Err(error("expected 'goal score <points> within <seconds>s'"))
+            },
+            ["goal", "survive", seconds] => number(seconds)
+                .map(Self::Survive)
+                .ok_or_else(|| error("expected a number of seconds")),
+            ["goal", "outeat", fruits] => fruits.parse()
+                .map(Self::OutEat)
+                .map_err(|_| error("expected a whole number of fruit")),
+            _ => Err(error("expected 'goal length', 'goal score', 'goal survive' or 'goal outeat'"))
+        }
+    }
+}
+
+// Classic play towards a level's goal: the run is won as soon as the goal is met, and lost once the snake
+// dies or the time limit runs out. Against a boss, a bot's snake shares the map as a rival, and outliving
+// it meets the goal too
+pub struct GoalMode {
+    goal: Goal,
+    boss: bool
+}
+
+impl GoalMode {
+    pub fn new(goal: Goal) -> Self {
+        Self {
+            goal,
+            boss: false
+        }
+    }
+
+    pub fn against_boss(goal: Goal) -> Self {
+        Self {
+            goal,
+            boss: true
+        }
+    }
+}
+
+impl GameMode for GoalMode {
+    fn name(&self) -> &str {
+        "Campaign"
+    }
+
+    fn snakes(&self) -> usize {
+        1 + self.boss as usize
+    }
+
+    fn players(&self) -> usize {
+        1
+    }
+
+    fn rivals(&self) -> bool {
+        self.boss
+    }
+
+    fn status(&self, run: &RunState) -> Status {
+        if !run.player_alive || self.goal.is_failed(run) {
+            Status::Lost
+        } else if self.goal.is_met(run) || (self.boss && run.rivals_alive == 0) {
+            Status::Won
+        } else {
+            Status::Playing
+        }
+    }
+}
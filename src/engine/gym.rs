@@ -0,0 +1,167 @@
+use super::{Config, Direction, Engine, Outcome, Position};
+
+// A gym-style environment over the engine for training agents: `reset` starts an episode and `step`
+// plays one slither, handing back what the agent can see, its reward and whether the episode is over.
+// Each episode gets its own seed, counting up from the one in the config, so a sequence of episodes is
+// as reproducible as a single run
+pub struct Env {
+    config: Config,
+    engine: Engine,
+    episodes: u64
+}
+
+// What the agent can do on each step, as a discrete action space
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Action {
+    Continue, // Keep heading the same way
+    North,
+    East,
+    South,
+    West
+}
+
+// The contents of a tile, as stored in an observation
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Cell {
+    Empty = 0,
+    Head = 1,
+    Body = 2,
+    Fruit = 3,
+    BonusFruit = 4, // Frenzy fruit
+    Hazard = 5
+}
+
+// The board as a flat grid of `Cell` values in row-major order, along with the way the snake is heading
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Observation {
+    pub width: u32,
+    pub height: u32,
+    pub cells: Vec<u8>,
+    pub heading: Direction
+}
+
+impl Action {
+    pub const ALL: [Action; 5] = [Action::Continue, Action::North, Action::East, Action::South, Action::West];
+
+    // The action at `index` of `ALL`, for agents that pick actions by number
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    pub fn direction(self) -> Option<Direction> {
+        match self {
+            Self::Continue => None,
+            Self::North => Some(Direction::North),
+            Self::East => Some(Direction::East),
+            Self::South => Some(Direction::South),
+            Self::West => Some(Direction::West)
+        }
+    }
+}
+
+impl Observation {
+    pub fn cell(&self, position: Position) -> Option<u8> {
+        if position.in_bounds((self.width, self.height)) {
+            Some(self.cells[(position.y as u32 * self.width + position.x as u32) as usize])
+        } else {
+            None
+        }
+    }
+}
+
+impl Env {
+    pub const REWARD_PER_POINT: f64 = 1.0;
+    pub const REWARD_DEATH: f64 = -10.0; // For every death, not just the last
+    pub const REWARD_WIN: f64 = 100.0;
+
+    pub fn new(config: Config) -> Self {
+        Self {
+            engine: Engine::new(config.clone()),
+            config,
+            episodes: 0
+        }
+    }
+
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    // Start a new episode, returning its first observation
+    pub fn reset(&mut self) -> Observation {
+        self.episodes += 1;
+        self.engine = Engine::new(Config {
+            seed: self.config.seed.wrapping_add(self.episodes),
+            ..self.config.clone()
+        });
+
+        self.observe()
+    }
+
+    // Play one slither, returning the observation afterwards, the reward for it, and whether the episode is
+    // over. Stepping a finished episode changes nothing and earns nothing
+    pub fn step(&mut self, action: Action) -> (Observation, f64, bool) {
+        let was_over = self.engine.is_over();
+        let points = self.engine.score().points();
+        let outcome = self.engine.advance(action.direction());
+
+        let mut reward = (self.engine.score().points() - points) as f64 * Self::REWARD_PER_POINT;
+
+        if !was_over {
+            reward += match outcome {
+                Outcome::Died | Outcome::Lost => Self::REWARD_DEATH,
+                Outcome::Won => Self::REWARD_WIN,
+                Outcome::Moved | Outcome::Ate | Outcome::Respawning => 0.0
+            };
+        }
+
+        (self.observe(), reward, self.engine.is_over())
+    }
+
+    // The board as it stands
+    pub fn observe(&self) -> Observation {
+        let (width, height) = self.config.dimensions;
+        let mut cells = vec![Cell::Empty as u8; (width * height) as usize];
+
+        let mut mark = |position: Position, cell: Cell| {
+            if position.in_bounds((width, height)) {
+                cells[(position.y as u32 * width + position.x as u32) as usize] = cell as u8;
+            }
+        };
+
+        for hazard in self.engine.hazards() {
+            for tile in hazard.tiles((width, height)) {
+                mark(tile, Cell::Hazard);
+            }
+        }
+
+        for fruit in self.engine.frenzy().fruits() {
+            mark(*fruit, Cell::BonusFruit);
+        }
+
+        for pellet in self.engine.pellets() {
+            mark(pellet.position, Cell::BonusFruit);
+        }
+
+        for fruit in self.engine.fruits() {
+            mark(fruit.position, Cell::Fruit);
+        }
+
+        // Any other snakes are all body, as far as the agent is concerned
+        for segment in self.engine.snakes().iter().skip(1).flat_map(|snake| snake.iter()) {
+            mark(segment.position, Cell::Body);
+        }
+
+        // Drawn from the tail up, so the head wins out over any body segments beneath it
+        for (index, segment) in self.engine.snake().iter().enumerate().rev() {
+            mark(segment.position, if index == 0 { Cell::Head } else { Cell::Body });
+        }
+
+        Observation {
+            width,
+            height,
+            cells,
+            heading: self.engine.snake().direction()
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use super::{Direction, Grid, Position};
+use super::controller::{BoardView, Controller};
+use super::pathfinding::AStarBot;
+
+// A closed tour through every tile of the map, visiting each exactly once. One exists whenever either
+// side of the map has an even length of at least two
+pub struct HamiltonianCycle {
+    dimensions: (u32, u32),
+    tour: Vec<Position>,
+    order: Vec<usize> // Where each tile (in row-major order) comes in the tour
+}
+
+impl HamiltonianCycle {
+    pub fn new(dimensions: (u32, u32)) -> Option<Self> {
+        let (width, height) = dimensions;
+
+        if width < 2 || height < 2 {
+            return None;
+        }
+
+        // Built with an even number of rows, transposing the map if only its width is even
+        let tour = if height % 2 == 0 {
+            Self::tour(width, height, Position::new)
+        } else if width % 2 == 0 {
+            Self::tour(height, width, |x, y| Position::new(y, x))
+        } else {
+            return None;
+        };
+
+        let mut order = vec![0; tour.len()];
+
+        for (index, position) in tour.iter().enumerate() {
+            order[(position.y as u32 * width + position.x as u32) as usize] = index;
+        }
+
+        Some(Self {
+            dimensions,
+            tour,
+            order
+        })
+    }
+
+    // Along the top row, then snaking back and forth through every other column, and finally up the
+    // first column to where it began. `rows` must be even
+    fn tour(columns: u32, rows: u32, position: impl Fn(i32, i32) -> Position) -> Vec<Position> {
+        let (columns, rows) = (columns as i32, rows as i32);
+        let mut tour = Vec::with_capacity((columns * rows) as usize);
+
+        tour.extend((0..columns).map(|x| position(x, 0)));
+
+        for y in 1..rows {
+            if y % 2 == 1 {
+                tour.extend((1..columns).rev().map(|x| position(x, y)));
+            } else {
+                tour.extend((1..columns).map(|x| position(x, y)));
+            }
+        }
+
+        tour.extend((1..rows).rev().map(|y| position(0, y)));
+
+        tour
+    }
+
+    pub fn len(&self) -> usize {
+        self.tour.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tour.is_empty()
+    }
+
+    // Where `position` comes in the tour, if it's on the map
+    pub fn index_of(&self, position: Position) -> Option<usize> {
+        if position.in_bounds(self.dimensions) {
+            Some(self.order[(position.y as u32 * self.dimensions.0 + position.x as u32) as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn next(&self, position: Position) -> Option<Position> {
+        self.index_of(position).map(|index| self.tour[(index + 1) % self.len()])
+    }
+
+    // How many steps along the tour it takes to get from `from` to `to`
+    pub fn distance(&self, from: Position, to: Position) -> Option<usize> {
+        Some((self.index_of(to)? + self.len() - self.index_of(from)?) % self.len())
+    }
+}
+
+// Follows a Hamiltonian cycle, which lets it fill the whole map without ever trapping itself. While the
+// snake is short it cuts corners towards the fruit, as long as the shortcut doesn't skip past the fruit
+// or any of its own body along the cycle. Maps with no cycle (both sides odd) are left to an `AStarBot`
+#[derive(Default)]
+pub struct HamiltonianBot {
+    cycle: Option<HamiltonianCycle>,
+    fallback: AStarBot
+}
+
+impl HamiltonianBot {
+    pub const SHORTCUT_LIMIT: f64 = 0.5; // Shortcuts are only taken while the snake covers less than this share of the map
+    pub const SHORTCUT_MARGIN: usize = 4; // Extra steps kept between a shortcut and the body, leaving room to grow
+
+    fn shortcut(&self, cycle: &HamiltonianCycle, view: &BoardView, head: Position) -> Option<Direction> {
+        if view.snake().len() as f64 >= cycle.len() as f64 * Self::SHORTCUT_LIMIT {
+            return None;
+        }
+
+        let to_fruit = cycle.distance(head, view.fruit())?;
+        let to_body = view.snake().iter()
+            .skip(1)
+            .filter_map(|segment| cycle.distance(head, segment.position))
+            .min()
+            .unwrap_or(cycle.len());
+
+        Direction::ALL.iter()
+            .copied()
+            .filter(|&direction| view.is_safe(head + Position::from(direction)))
+            .filter_map(|direction| cycle.distance(head, head + Position::from(direction)).map(|distance| (direction, distance)))
+            .filter(|&(_, distance)| distance <= to_fruit && distance + Self::SHORTCUT_MARGIN < to_body)
+            .max_by_key(|&(_, distance)| distance)
+            .map(|(direction, _)| direction)
+    }
+}
+
+impl Controller for HamiltonianBot {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        if self.cycle.as_ref().is_none_or(|cycle| cycle.dimensions != view.dimensions()) {
+            self.cycle = HamiltonianCycle::new(view.dimensions());
+        }
+
+        // The cycle runs over the whole map along the grid, so arenas with walls, and other grids, are
+        // left to the fallback
+        let cycle = match &self.cycle {
+            Some(cycle) if !view.arena().has_walls() && view.grid() == Grid::Square => cycle,
+            _ => return self.fallback.decide(view)
+        };
+
+        if !view.snake().alive {
+            return None;
+        }
+
+        let head = view.head()?;
+
+        if let Some(direction) = self.shortcut(cycle, view, head) {
+            return Some(direction);
+        }
+
+        // Keep to the cycle, or, until the body has straightened out along it, the safe move that goes
+        // the least far around it
+        Direction::ALL.iter()
+            .copied()
+            .filter(|&direction| view.is_safe(head + Position::from(direction)))
+            .min_by_key(|&direction| cycle.distance(head, head + Position::from(direction)))
+    }
+}
@@ -0,0 +1,76 @@
+use super::geometry::Position;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HazardKind {
+    LaserRow(i32), // Sweeps a whole row of the map
+    LaserColumn(i32), // Sweeps a whole column of the map
+    Bomb(Position)
+}
+
+// A deadly map feature. Hazards blink harmlessly while they warm up, then kill the snake if its head
+// touches them, until they expire
+pub struct Hazard {
+    pub kind: HazardKind,
+    spawned_at: f64, // Run time, in seconds
+    age: f64
+}
+
+impl Hazard {
+    pub fn new(kind: HazardKind, spawned_at: f64) -> Self {
+        Self {
+            kind,
+            spawned_at,
+            age: 0.0
+        }
+    }
+
+    // Seconds of warning before the hazard becomes deadly
+    pub fn warning(&self) -> f64 {
+        match self.kind {
+            HazardKind::LaserRow(_) | HazardKind::LaserColumn(_) => 1.5,
+            HazardKind::Bomb(_) => 1.0
+        }
+    }
+
+    // Seconds the hazard remains once armed
+    pub fn lifetime(&self) -> f64 {
+        match self.kind {
+            HazardKind::LaserRow(_) | HazardKind::LaserColumn(_) => 3.0,
+            HazardKind::Bomb(_) => 10.0
+        }
+    }
+
+    // Seconds since the hazard spawned
+    pub fn age(&self) -> f64 {
+        self.age
+    }
+
+    pub fn update(&mut self, run_time: f64) {
+        self.age = run_time - self.spawned_at;
+    }
+
+    pub fn is_armed(&self) -> bool {
+        self.age >= self.warning()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.age >= self.warning() + self.lifetime()
+    }
+
+    pub fn covers(&self, point: Position) -> bool {
+        match self.kind {
+            HazardKind::LaserRow(y) => point.y == y,
+            HazardKind::LaserColumn(x) => point.x == x,
+            HazardKind::Bomb(position) => point == position
+        }
+    }
+
+    // The tiles the hazard covers on a map of the given dimensions
+    pub fn tiles(&self, dimensions: (u32, u32)) -> Vec<Position> {
+        match self.kind {
+            HazardKind::LaserRow(y) => (0..dimensions.0 as i32).map(|x| Position::new(x, y)).collect(),
+            HazardKind::LaserColumn(x) => (0..dimensions.1 as i32).map(|y| Position::new(x, y)).collect(),
+            HazardKind::Bomb(position) => vec![position]
+        }
+    }
+}
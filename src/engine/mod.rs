@@ -0,0 +1,1336 @@
+// The rules of the game, free of any rendering, input or platform code. An `Engine` is advanced one
+// slither at a time and keeps its own count of simulated run time, so a run plays out the same way for
+// a given seed however (or whether) it is drawn
+mod geometry;
+pub mod snake;
+pub mod arena;
+pub mod mode;
+pub mod goal;
+pub mod boss;
+pub mod scoring;
+pub mod frenzy;
+pub mod hazard;
+pub mod schedule;
+pub mod autopilot;
+pub mod difficulty;
+pub mod event;
+pub mod tags;
+pub mod free_cells;
+pub mod occupancy;
+pub mod gym;
+pub mod controller;
+pub mod pathfinding;
+pub mod hamiltonian;
+pub mod board;
+pub mod skin;
+pub mod fruit;
+pub mod power_up;
+pub mod speed;
+pub mod versus;
+pub mod spawn;
+pub mod puzzle;
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+pub use self::geometry::{Direction, Grid, Position};
+
+use self::snake::Snake;
+use self::skin::Skin;
+use self::fruit::{Fruit, FruitKind, FruitTable, Pellet};
+use self::power_up::{Pickup, PowerUp};
+use self::speed::{LengthSpeed, SpeedCurve};
+use self::difficulty::Difficulty;
+use self::versus::WinCondition;
+use self::mode::{Mode, InputPolicy, GameMode, RunState, Spawn};
+use self::scoring::{Score, ScoringPolicy};
+use self::frenzy::Frenzy;
+use self::hazard::{Hazard, HazardKind};
+use self::schedule::{Schedule, HazardSpec};
+use self::event::{GameEvent, DeathCause};
+use self::tags::CellTags;
+use self::arena::{Arena, ArenaMask, Edges, Symmetry};
+use self::free_cells::FreeCells;
+use self::occupancy::Occupancy;
+use self::controller::BoardView;
+use self::spawn::{SpawnContext, SpawnPolicy};
+use self::puzzle::Scenario;
+
+// Everything that decides how a run plays out
+#[derive(Clone)]
+pub struct Config {
+    pub dimensions: (u32, u32),
+    pub mode: Mode,
+    pub scoring_policy: ScoringPolicy,
+    pub seed: u64,
+    pub speed: f64, // Multiplies the pace set by the mode
+    pub speed_curve: SpeedCurve, // How the pace picks up as fruit is eaten
+    pub length_speed: LengthSpeed, // How the pace picks up as the snake grows
+    pub skin: Skin, // The glyphs the snake and fruit are drawn with
+    pub fruits: FruitTable, // The kinds of fruit that spawn, as set by the level being played
+    pub power_ups: bool, // Whether power-ups turn up on the map
+    pub difficulty: Difficulty, // How well the computer plays the snakes it steers
+    pub win_condition: WinCondition, // What decides a match against rival snakes
+    pub schedule: Schedule, // Hazards scripted by the level being played
+    pub tags: CellTags, // Custom per-cell metadata from the level being played
+    pub arena: Arena, // The shape of the playing area, walled off everywhere outside it
+    pub obstacles: u32, // How densely walls are scattered over the arena at the start, from 0 to 100 percent
+    pub edges: Edges, // Which edges of the map are walls and which wrap around to the opposite edge
+    pub symmetry: Symmetry, // How obstacles and starting points are matched up across the map, for fair matches
+    pub grid: Grid, // How the tiles are laid out, and so which ways snakes can move
+    pub scenario: Option<Scenario> // The puzzle being played, laying out the snake and every fruit in advance
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            dimensions: Engine::MAP_DIMENSIONS,
+            mode: Mode::default(),
+            scoring_policy: ScoringPolicy::default(),
+            seed: 0,
+            speed: 1.0,
+            speed_curve: SpeedCurve::default(),
+            length_speed: LengthSpeed::default(),
+            skin: Skin::default(),
+            fruits: FruitTable::default(),
+            power_ups: true,
+            difficulty: Difficulty::default(),
+            win_condition: WinCondition::default(),
+            schedule: Schedule::default(),
+            tags: CellTags::default(),
+            arena: Arena::default(),
+            obstacles: 0,
+            edges: Edges::default(),
+            symmetry: Symmetry::default(),
+            grid: Grid::default(),
+            scenario: None
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Status {
+    Playing,
+    Won, // The snake filled the map
+    Lost // The snake died with no lives to spare
+}
+
+/// What happened to the run during a single call to [`Engine::advance`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// The snake slithered one tile without incident
+    Moved,
+    /// The snake ate fruit and grew by one segment
+    Ate,
+    /// The snake died but has lives to spare. It respawns once its body has finished disappearing
+    Died,
+    /// A dead snake's body is still disappearing ahead of a respawn
+    Respawning,
+    /// The snake filled the map. Further calls do nothing and keep returning `Won`
+    Won,
+    /// The snake died on its last life. Further calls keep returning `Lost`
+    Lost
+}
+
+// A change of direction asked of one of the snakes, and the slither it came before, so a run can be
+// played back from its config
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Steer {
+    pub tick: usize, // Slithers run before it
+    pub snake: usize, // Where the snake comes in `Engine::snakes`
+    pub direction: Direction
+}
+
+// The size of one of the engine's internal buffers, alongside the most it should ever hold
+pub struct BufferSize {
+    pub name: &'static str,
+    pub len: usize,
+    pub capacity: usize
+}
+
+pub struct Engine {
+    config: Config,
+    rng: StdRng,
+    fruit_rng: StdRng, // Picks fruit kinds apart from `rng`, so a seed places everything where it always has
+    schedule: Schedule,
+    tags: CellTags,
+    arena: ArenaMask,
+    snakes: Vec<Snake>, // The player's first, then any others sharing the map
+    buffered_directions: Vec<Option<Direction>>, // For each snake, the direction held back until the next slither, for modes with buffered input
+    headings: Vec<Direction>, // For each snake, the direction of its last slither, for spotting turns
+    fruits: Vec<Fruit>, // One for each snake
+    clusters: Vec<Vec<Fruit>>, // For each snake, the rest of the cluster its fruit spawned in, still to be eaten
+    pellets: Vec<Pellet>, // Left along the bodies of rival snakes that died
+    bodies: Occupancy, // Every snake's segments together, so one lookup tells whether any snake is on a tile
+    frenzy: Frenzy,
+    frenzy_clock: f64, // When the last frenzy started or ended, for timing the next change
+    pickup: Option<Pickup>,
+    pickup_clock: f64, // When the last pickup went, for timing the next
+    hazards: Vec<Hazard>,
+    free_cells: FreeCells,
+    spawn_policy: Box<dyn SpawnPolicy>, // Where fruit goes, as the mode would have it
+    rules: Box<dyn GameMode>, // The rules of the mode being played
+    scores: Vec<Score>, // One for each snake, though only the first counts when the snakes work together
+    longest_length: usize, // The longest the snake has been this run
+    lives: usize, // Lives remaining, including the current one
+    time: f64, // Simulated seconds since the run started
+    ticks: usize, // Slithers run since the run started
+    steering: Vec<Steer>, // Every change of direction asked for, in order
+    status: Status,
+    events: Vec<GameEvent> // What happened during the latest slither
+}
+
+impl Engine {
+    pub const MAP_DIMENSIONS: (u32, u32) = (25, 25);
+    pub const SLITHERS_PER_SECOND: u32 = 15;
+    pub const MAX_HAZARDS: usize = 64;
+    pub const FRUIT_SEED_SALT: u64 = 0x66_72_75_69_74; // "fruit"
+    pub const MAX_OBSTACLES: f64 = 0.2; // The share of the arena walled off at 100% obstacle density
+
+    pub fn new(config: Config) -> Self {
+        let rules = config.mode.rules();
+
+        Self::with_rules(config, rules)
+    }
+
+    // Play by rules other than those of `config.mode`, such as a mode supplied by an embedder
+    pub fn with_rules(mut config: Config, rules: Box<dyn GameMode>) -> Self {
+        let mut schedule = config.schedule.clone();
+
+        config.dimensions = rules.map_dimensions(config.dimensions);
+
+        schedule.restart();
+
+        let arena = config.arena.mask(config.dimensions).with_edges(config.edges);
+        let snakes: Vec<Snake> = match &config.scenario {
+            Some(scenario) => vec![Snake::from_body(&scenario.snake, config.dimensions, config.grid, config.skin)],
+            None => Self::starting_points(&arena, &config.tags, config.symmetry, rules.snakes()).into_iter()
+                .map(|(spawn_point, direction)| Snake::new(spawn_point, direction, config.dimensions, config.grid, config.skin))
+                .collect()
+        };
+        let missing_fruit = Fruit {
+            position: Position::new(-1, -1), // Initally positioned outside of map
+            kind: FruitKind::default(),
+            spawned_at: 0.0,
+            age: 0
+        };
+
+        let mut engine = Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            fruit_rng: StdRng::seed_from_u64(config.seed ^ Self::FRUIT_SEED_SALT),
+            schedule,
+            tags: config.tags.clone(),
+            arena,
+            free_cells: FreeCells::new(config.dimensions),
+            spawn_policy: rules.spawn_policy(),
+            buffered_directions: vec![None; snakes.len()],
+            headings: snakes.iter().map(|snake| snake.direction()).collect(),
+            fruits: vec![missing_fruit; snakes.len()],
+            clusters: vec![Vec::new(); snakes.len()],
+            pellets: Vec::new(),
+            bodies: Occupancy::new(config.dimensions),
+            snakes,
+            frenzy: Frenzy::default(),
+            frenzy_clock: 0.0,
+            pickup: None,
+            pickup_clock: 0.0,
+            hazards: Vec::new(),
+            scores: (0..rules.snakes()).map(|_| Score::default()).collect(),
+            longest_length: Snake::STARTING_LENGTH,
+            lives: rules.lives(),
+            rules,
+            time: 0.0,
+            ticks: 0,
+            steering: Vec::new(),
+            status: Status::Playing,
+            events: Vec::new(),
+            config
+        };
+
+        for wall in engine.arena.walls() {
+            engine.free_cells.occupy(wall);
+        }
+
+        for segment in engine.snakes.iter().flat_map(|snake| snake.iter()) {
+            engine.free_cells.occupy(segment.position);
+            engine.bodies.add(segment.position);
+        }
+
+        // Obstacles are scattered once the snakes are in place, to keep them out of the snakes' way
+        let obstacles = engine.arena.open_tiles() as f64 * Self::MAX_OBSTACLES * engine.config.obstacles.min(100) as f64 / 100.0;
+
+        engine.raise_walls(obstacles as usize);
+
+        match engine.config.scenario.clone() {
+            Some(scenario) => engine.lay_out_fruit(&scenario.fruit),
+            None => for index in 0..engine.fruits.len() {
+                engine.spawn_fruit(index);
+            }
+        }
+
+        let run = engine.run_state();
+
+        engine.rules.on_start(&run);
+        engine.place_spawns();
+
+        engine
+    }
+
+    // Where each of `count` snakes starts out and which way it heads: on the cells the level tagged as
+    // spawn points, then spaced evenly down the middle column, heading east and west in turn. A lone
+    // snake starts in the middle of the map heading east. Snakes that would start in or facing a wall of
+    // the arena start at the nearest tile clear of walls instead. With symmetry, every other snake left
+    // to place starts at the image of the one before it, as long as that's clear
+    fn starting_points(arena: &ArenaMask, tags: &CellTags, symmetry: Symmetry, count: usize) -> Vec<(Position, Direction)> {
+        let dimensions = arena.dimensions();
+        let spawn_points = tags.spawn_points();
+        let mut points: Vec<(Position, Direction)> = Vec::with_capacity(count);
+
+        for index in 0..count {
+            let position = spawn_points.get(index).copied().unwrap_or(Position::new(
+                (dimensions.0 / 2) as i32,
+                ((index + 1) * dimensions.1 as usize / (count + 1)) as i32
+            ));
+            let direction = if index % 2 == 0 {
+                Snake::STARTING_DIRECTIN
+            } else {
+                Snake::STARTING_DIRECTIN.opposite()
+            };
+            let fits_heading = |point: Position, direction: Direction| {
+                arena.is_open(point) && arena.is_open(point + Position::from(direction)) &&
+                (0..=Snake::SAFE_RUNWAY)
+                    .map(|distance| point + Position::from(direction) * distance)
+                    .all(|tile| !arena.is_wall(tile) && points.iter().all(|(taken, _)| *taken != tile))
+            };
+            let fits = |point: Position| fits_heading(point, direction);
+
+            let image = points.last()
+                .filter(|_| index >= spawn_points.len() && (index - spawn_points.len()) % 2 == 1)
+                .and_then(|(previous, heading)| Some((symmetry.image(*previous, dimensions)?, symmetry.facing(*heading))));
+
+            if let Some((position, direction)) = image.filter(|(position, direction)| fits_heading(*position, *direction)) {
+                points.push((position, direction));
+                continue;
+            }
+
+            let position = if fits(position) {
+                position
+            } else {
+                let mut tiles: Vec<Position> = (0..dimensions.1 as i32)
+                    .flat_map(|y| (0..dimensions.0 as i32).map(move |x| Position::new(x, y)))
+                    .collect();
+
+                tiles.sort_by_key(|tile| tile.manhattan_distance(position));
+                tiles.into_iter().find(|tile| fits(*tile)).unwrap_or(position)
+            };
+
+            points.push((position, direction));
+        }
+
+        points
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    // Draw the snakes and fruit with other glyphs, without otherwise touching the run
+    pub fn set_skin(&mut self, skin: Skin) {
+        self.config.skin = skin;
+
+        for snake in &mut self.snakes {
+            snake.set_skin(skin);
+        }
+    }
+
+    // The board as the player's controller sees it
+    pub fn view(&self) -> BoardView<'_> {
+        BoardView::new(self, 0)
+    }
+
+    // The board as seen by the controller of the snake at `index`
+    pub fn view_of(&self, index: usize) -> BoardView<'_> {
+        BoardView::new(self, index)
+    }
+
+    pub fn rules(&self) -> &dyn GameMode {
+        self.rules.as_ref()
+    }
+
+    // A snapshot of the run for the rules to judge
+    pub fn run_state(&self) -> RunState {
+        RunState {
+            time: self.time,
+            snake_length: self.snakes.iter().map(|snake| snake.len()).sum(),
+            alive: self.snakes.iter().all(|snake| snake.alive),
+            lives: self.lives,
+            points: self.score().points(),
+            fruits_eaten: self.score().fruits_eaten(),
+            cells: self.arena.open_tiles(),
+            player_alive: self.snake().alive,
+            rivals_alive: self.snakes[1..].iter().filter(|snake| self.rules.rivals() && snake.alive).count(),
+            rival_points: self.scores[1..].iter().map(|score| score.points()).max().unwrap_or(0),
+            rival_fruits: self.scores[1..].iter().map(|score| score.fruits_eaten()).max().unwrap_or(0),
+            win_condition: self.config.win_condition
+        }
+    }
+
+    // The player's snake
+    pub fn snake(&self) -> &Snake {
+        &self.snakes[0]
+    }
+
+    // Every snake on the map, starting with the player's
+    pub fn snakes(&self) -> &[Snake] {
+        &self.snakes
+    }
+
+    // The first fruit, which is the only one unless several snakes share the map
+    pub fn fruit(&self) -> Position {
+        self.fruits[0].position
+    }
+
+    pub fn fruit_kind(&self) -> FruitKind {
+        self.fruits[0].kind
+    }
+
+    pub fn fruits(&self) -> &[Fruit] {
+        &self.fruits
+    }
+
+    // The fruit spawned in clusters along with the snakes' fruit, for spawn policies that place them so
+    pub fn cluster_fruits(&self) -> impl Iterator<Item = &Fruit> {
+        self.clusters.iter().flatten()
+    }
+
+    pub fn pellets(&self) -> &[Pellet] {
+        &self.pellets
+    }
+
+    // How many segments of any snake lie on `position`
+    pub fn segments_at(&self, position: Position) -> u16 {
+        self.bodies.count(position)
+    }
+
+    // How far gone the first fruit is (see `Engine::rot`)
+    pub fn fruit_rot(&self) -> Option<f64> {
+        self.rot(&self.fruits[0])
+    }
+
+    // How far gone `fruit` is, from 0 as it turns rotten to 1 as it's about to spawn elsewhere, or nothing
+    // while it's still fresh (or never rots)
+    pub fn rot(&self, fruit: &Fruit) -> Option<f64> {
+        let fresh_seconds = self.config.fruits.rot_seconds()?;
+        let rotten_seconds = self.time - fruit.spawned_at - fresh_seconds;
+
+        if rotten_seconds >= 0.0 {
+            Some((rotten_seconds / FruitTable::ROTTEN_SECONDS).min(1.0))
+        } else {
+            None
+        }
+    }
+
+    // Move the first fruit to `position`, for scripted scenarios such as tests
+    pub fn set_fruit(&mut self, position: Position) {
+        let fruit = &mut self.fruits[0];
+
+        self.free_cells.vacate(fruit.position);
+        self.free_cells.occupy(position);
+        fruit.position = position;
+        fruit.spawned_at = self.time;
+        fruit.age = 0;
+    }
+
+    pub fn frenzy(&self) -> &Frenzy {
+        &self.frenzy
+    }
+
+    // The power-up waiting on the map, if there is one
+    pub fn pickup(&self) -> Option<Pickup> {
+        self.pickup
+    }
+
+    // Put a power-up at `position`, in place of any already waiting
+    pub fn set_pickup(&mut self, position: Position, power_up: PowerUp) {
+        if let Some(pickup) = self.pickup {
+            self.free_cells.vacate(pickup.position);
+        }
+
+        self.free_cells.occupy(position);
+        self.pickup = Some(Pickup {
+            position,
+            power_up,
+            spawned_at: self.time
+        });
+    }
+
+    pub fn hazards(&self) -> &[Hazard] {
+        &self.hazards
+    }
+
+    pub fn arena(&self) -> &ArenaMask {
+        &self.arena
+    }
+
+    pub fn cell_tags(&self) -> &CellTags {
+        &self.tags
+    }
+
+    // For mods and scripts to change what cells mean mid-run
+    pub fn cell_tags_mut(&mut self) -> &mut CellTags {
+        &mut self.tags
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.scores[0]
+    }
+
+    // The score of each snake, which only the first of keeps when the snakes work together
+    pub fn scores(&self) -> &[Score] {
+        &self.scores
+    }
+
+    pub fn longest_length(&self) -> usize {
+        self.longest_length
+    }
+
+    pub fn lives(&self) -> usize {
+        self.lives
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    // Every change of direction asked of the snakes so far, for replays
+    pub fn steering(&self) -> &[Steer] {
+        &self.steering
+    }
+
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    // The events of the most recent slither
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    // Simulated seconds that pass with each slither
+    pub fn seconds_per_tick(&self) -> f64 {
+        1.0 / self.slithers_per_second()
+    }
+
+    // The pace of the run: the mode's, scaled by the speed in the config, how far along its speed curve
+    // the snake has eaten and how long the player's snake has grown
+    pub fn slithers_per_second(&self) -> f64 {
+        self.rules.slithers_per_second() as f64 * self.config.speed * self.config.speed_curve.factor(self.score().fruits_eaten()) *
+            self.length_factor()
+    }
+
+    // How much faster the player's snake has sped up for its length
+    pub fn length_factor(&self) -> f64 {
+        self.config.length_speed.factor(self.snake().len())
+    }
+
+    // Seconds until the current feeding frenzy ends, if one is underway
+    pub fn frenzy_seconds_left(&self) -> Option<f64> {
+        if self.frenzy.is_active() {
+            Some((Frenzy::DURATION - (self.time - self.frenzy_clock)).max(0.0))
+        } else {
+            None
+        }
+    }
+
+    // Flag the run as having been played by the autopilot, so its score can be told apart
+    pub fn mark_assisted(&mut self) {
+        self.scores[0].mark_assisted();
+    }
+
+    // Change the player's direction, following the input policy of the current mode
+    pub fn steer(&mut self, direction: Direction) {
+        self.steer_snake(0, direction);
+    }
+
+    // Change the direction of the snake at `index`, following the input policy of the current mode.
+    // Directions the grid doesn't have are taken as the nearest it does, if any
+    pub fn steer_snake(&mut self, index: usize, direction: Direction) {
+        if self.is_over() || !self.snakes.get(index).is_some_and(|snake| snake.alive) {
+            return;
+        }
+
+        let direction = match self.config.grid.nearest(direction, self.snakes[index].direction()) {
+            Some(direction) => direction,
+            None => return
+        };
+
+        let steer = Steer {
+            tick: self.ticks,
+            snake: index,
+            direction
+        };
+
+        match self.rules.input_policy() {
+            InputPolicy::Immediate => {
+                if direction != self.snakes[index].direction() {
+                    self.steering.push(steer);
+                }
+
+                self.snakes[index].set_direction(direction);
+            },
+            InputPolicy::SingleBuffered => if self.buffered_directions[index].is_none() {
+                self.buffered_directions[index] = Some(direction);
+                self.steering.push(steer);
+            }
+        }
+    }
+
+    /// Steer the snake (or keep it heading the same way, given `None`), then advance the run by a
+    /// single slither and report what came of it. The direction is subject to the mode's input
+    /// policy and to the ways [`Config::grid`] has, and turning straight back on the snake's neck is
+    /// ignored. Each call moves simulated time on by [`Engine::seconds_per_tick`], so the caller
+    /// decides how fast the game runs
+    pub fn advance(&mut self, direction: Option<Direction>) -> Outcome {
+        let was_alive = self.snake().alive;
+        let fruits_eaten = self.score().fruits_eaten();
+
+        if let Some(direction) = direction {
+            self.steer(direction);
+        }
+
+        self.tick();
+
+        match self.status {
+            Status::Won => Outcome::Won,
+            Status::Lost => Outcome::Lost,
+            Status::Playing if was_alive && !self.snake().alive => Outcome::Died,
+            Status::Playing if !self.snake().alive => Outcome::Respawning,
+            Status::Playing if self.score().fruits_eaten() > fruits_eaten => Outcome::Ate,
+            Status::Playing => Outcome::Moved
+        }
+    }
+
+    // Advance the run by a single slither
+    pub fn tick(&mut self) {
+        self.events.clear();
+
+        // A won game stands still, while a lost one lets the dead snakes finish disappearing
+        if self.status == Status::Won || (self.status == Status::Lost && self.snakes.iter().all(|snake| snake.is_empty())) {
+            return;
+        }
+
+        self.time += self.seconds_per_tick();
+        self.ticks += 1;
+
+        for index in 0..self.snakes.len() {
+            self.slither(index);
+        }
+
+        self.collide_snakes();
+        self.longest_length = self.longest_length.max(self.snake().len());
+
+        if self.is_over() {
+            return;
+        }
+
+        if self.snakes.iter().any(|snake| snake.alive) {
+            self.eat_fruit();
+            self.flee();
+            self.attract();
+            self.rot_fruit();
+            self.update_pickup();
+            self.update_frenzy();
+            self.update_hazards();
+        }
+
+        self.drop_pellets();
+        self.update_status();
+    }
+
+    // Slither the snake at `index` and update its corner tiles
+    fn slither(&mut self, index: usize) {
+        let snake = &mut self.snakes[index];
+
+        if let Some(direction) = self.buffered_directions[index].take() {
+            snake.set_direction(direction);
+        }
+
+        let previous_head = snake.front().map(|segment| segment.position);
+        let death = snake.slither(&self.arena);
+
+        if snake.is_stalled() {
+            self.events.push(GameEvent::Shielded);
+        } else if snake.alive {
+            self.free_cells.occupy(snake[0].position);
+            self.bodies.add(snake[0].position);
+
+            if let Some(tail) = snake.popped_tail() {
+                self.free_cells.vacate(tail.position);
+                self.bodies.remove(tail.position);
+            }
+        } else if let Some(head) = previous_head {
+            // A dead snake loses its head each slither
+            self.free_cells.vacate(head);
+            self.bodies.remove(head);
+        }
+
+        if let (Some(cause), Some(head)) = (death, previous_head) {
+            let (width, height) = self.config.dimensions;
+
+            self.events.push(GameEvent::Died {
+                cause,
+                snake: index,
+                position: Position::new(head.x.clamp(0, width as i32 - 1), head.y.clamp(0, height as i32 - 1))
+            });
+        } else if snake.alive && snake.direction() != self.headings[index] {
+            self.headings[index] = snake.direction();
+            self.events.push(GameEvent::Turned(self.headings[index]));
+        }
+    }
+
+    // Kill every snake whose head has run into another snake, including both of a head-on collision. A
+    // head is on another snake wherever there are more segments than its own snake accounts for
+    fn collide_snakes(&mut self) {
+        if self.snakes.len() < 2 {
+            return;
+        }
+
+        let crashed: Vec<(usize, Position)> = self.live_heads().into_iter()
+            .filter(|&(index, head)| self.bodies.count(head) > self.snakes[index].segments_at(head))
+            .collect();
+
+        for (index, head) in crashed {
+            self.snakes[index].kill();
+            self.events.push(GameEvent::Died {
+                cause: DeathCause::Snake,
+                snake: index,
+                position: head
+            });
+        }
+    }
+
+    // The heads of the snakes still alive, along with where each comes in `snakes`
+    fn live_heads(&self) -> Vec<(usize, Position)> {
+        self.snakes.iter()
+            .enumerate()
+            .filter(|(_, snake)| snake.alive)
+            .filter_map(|(index, snake)| snake.front().map(|head| (index, head.position)))
+            .collect()
+    }
+
+    fn eat_fruit(&mut self) {
+        // Let the combo lapse if the previous fruit was eaten too long ago
+        for score in &mut self.scores {
+            score.expire_combo(self.time);
+        }
+
+        for (snake, head) in self.live_heads() {
+            let score = if self.rules.rivals() {
+                snake
+            } else {
+                0
+            };
+
+            // If the snake collides with a fruit, grow the snake and respawn the fruit
+            if let Some(index) = self.fruits.iter().position(|fruit| fruit.position == head) {
+                self.award_fruit(snake, score, self.fruits[index]);
+                self.next_fruit(index); // Must respawn the fruit after the snake grows
+            } else if let Some((owner, place)) = self.cluster_fruit_at(head) {
+                let fruit = self.clusters[owner].swap_remove(place);
+
+                self.free_cells.vacate(head);
+                self.award_fruit(snake, score, fruit);
+            }
+
+            // Frenzy fruit is worth a fixed amount, without affecting the combo
+            if self.frenzy.eat(head) {
+                self.free_cells.vacate(head);
+                self.scores[score].bonus_fruit_eaten(Frenzy::FRUIT_POINTS);
+                self.events.push(GameEvent::FruitEaten {
+                    points: Frenzy::FRUIT_POINTS
+                });
+                self.grow_snake(snake);
+            }
+
+            if let Some(index) = self.pellets.iter().position(|pellet| pellet.position == head) {
+                self.pellets.swap_remove(index);
+                self.free_cells.vacate(head);
+                self.scores[score].bonus_fruit_eaten(Pellet::POINTS);
+                self.events.push(GameEvent::FruitEaten {
+                    points: Pellet::POINTS
+                });
+                self.grow_snake(snake);
+            }
+        }
+
+        self.longest_length = self.longest_length.max(self.snake().len());
+    }
+
+    // Score a fruit for the snake that ate it, and grow the snake
+    fn award_fruit(&mut self, snake: usize, score: usize, fruit: Fruit) {
+        let points = self.rules.fruit_points(self.config.scoring_policy.fruit_points(
+            self.config.fruits.spec(fruit.kind).points,
+            self.time - fruit.spawned_at,
+            self.snakes[snake].len()
+        ));
+        let previous_points = self.scores[score].points();
+
+        self.scores[score].fruit_eaten(points, self.time);
+        self.events.push(GameEvent::FruitEaten {
+            points: self.scores[score].points() - previous_points
+        });
+        self.grow_snake(snake);
+    }
+
+    // Which snake's cluster has a fruit at `position`, and where it comes in the cluster
+    fn cluster_fruit_at(&self, position: Position) -> Option<(usize, usize)> {
+        self.clusters.iter()
+            .enumerate()
+            .find_map(|(owner, cluster)| cluster.iter().position(|fruit| fruit.position == position).map(|place| (owner, place)))
+    }
+
+    // Replace the fruit at `index` once it's been eaten: with the nearest fruit left in its cluster, or a
+    // fresh fruit once the cluster has been cleared
+    fn next_fruit(&mut self, index: usize) {
+        let eaten = self.fruits[index].position;
+        let nearest = (0..self.clusters[index].len()).min_by_key(|place| self.clusters[index][*place].position.manhattan_distance(eaten));
+
+        match nearest {
+            Some(place) => {
+                self.free_cells.vacate(eaten);
+                self.fruits[index] = self.clusters[index].swap_remove(place);
+            },
+            None if self.rules.respawns_fruit() => self.spawn_fruit(index),
+            None => {
+                self.free_cells.vacate(eaten);
+                self.fruits[index].position = Position::new(-1, -1);
+            }
+        }
+    }
+
+    // Put a puzzle's fruit out, all at once: the first as the snake's fruit, and the rest as its cluster
+    fn lay_out_fruit(&mut self, tiles: &[Position]) {
+        let fruit = |position| Fruit {
+            position,
+            kind: FruitKind::default(),
+            spawned_at: 0.0,
+            age: 0
+        };
+
+        for tile in tiles {
+            self.free_cells.occupy(*tile);
+        }
+
+        if let Some((first, rest)) = tiles.split_first() {
+            self.fruits[0] = fruit(*first);
+            self.clusters[0] = rest.iter().copied().map(fruit).collect();
+        }
+    }
+
+    // Put out a power-up when one is due, hand it to a snake if its head is on it, or clear it away if
+    // it has waited too long. Timed effects count down here too
+    fn update_pickup(&mut self) {
+        let seconds = self.seconds_per_tick();
+
+        for snake in &mut self.snakes {
+            snake.age_effects(seconds);
+        }
+
+        let collector = self.pickup.and_then(|pickup| {
+            self.live_heads().into_iter().find(|&(_, head)| head == pickup.position)
+        });
+
+        match (self.pickup, collector) {
+            (Some(pickup), Some((snake, _))) => {
+                self.free_cells.vacate(pickup.position); // Still taken by the head
+                self.snakes[snake].add_effect(pickup.power_up.effect());
+                self.events.push(GameEvent::PowerUpCollected(pickup.power_up));
+                self.pickup = None;
+                self.pickup_clock = self.time;
+            },
+            (Some(pickup), _) if self.time - pickup.spawned_at >= PowerUp::PICKUP_LIFETIME => {
+                self.free_cells.vacate(pickup.position);
+                self.pickup = None;
+                self.pickup_clock = self.time;
+            },
+            (None, _) if self.config.power_ups && self.time - self.pickup_clock >= PowerUp::INTERVAL => {
+                let power_up = *PowerUp::ALL.choose(&mut self.rng).unwrap();
+
+                if let Some(position) = self.free_cells.choose(&mut self.rng) {
+                    self.free_cells.occupy(position);
+                    self.pickup = Some(Pickup {
+                        position,
+                        power_up,
+                        spawned_at: self.time
+                    });
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // The head of the live snake nearest `position`, out of those `wanted`
+    fn nearest_head(&self, position: Position, wanted: impl Fn(&Snake) -> bool) -> Option<Position> {
+        self.live_heads().into_iter()
+            .filter(|&(index, _)| wanted(&self.snakes[index]))
+            .map(|(_, head)| head)
+            .min_by_key(|head| head.manhattan_distance(position))
+    }
+
+    // Every few slithers, fruit that flees takes a step further away from the nearest snake's head, if
+    // there's a free tile to take it to
+    fn flee(&mut self) {
+        for index in 0..self.fruits.len() {
+            let fruit = &mut self.fruits[index];
+
+            if !fruit.kind.flees() {
+                continue;
+            }
+
+            fruit.age += 1;
+
+            if !fruit.age.is_multiple_of(FruitKind::FLEE_INTERVAL) {
+                continue;
+            }
+
+            let fruit = fruit.position;
+            let head = match self.nearest_head(fruit, |_| true) {
+                Some(head) => head,
+                None => continue
+            };
+            let distance = |tile: Position| self.config.grid.distance(tile, head);
+            let escape = self.config.grid.neighbours(fruit)
+                .filter(|&tile| self.is_pathable(tile) && !self.tags.has(tile, CellTags::NO_FRUIT))
+                .filter(|&tile| distance(tile) > distance(fruit))
+                .max_by_key(|&tile| distance(tile));
+
+            if let Some(tile) = escape {
+                self.move_fruit(index, tile);
+            }
+        }
+    }
+
+    // While a snake has a magnet, fruit is pulled a tile closer to its head every slither, if there's a
+    // free tile to pull it to
+    fn attract(&mut self) {
+        for index in 0..self.fruits.len() {
+            let fruit = self.fruits[index].position;
+            let head = match self.nearest_head(fruit, |snake| snake.has_effect(PowerUp::Magnet)) {
+                Some(head) => head,
+                None => continue
+            };
+            let distance = |tile: Position| self.config.grid.distance(tile, head);
+            let pull = self.config.grid.neighbours(fruit)
+                .filter(|&tile| self.is_pathable(tile) && !self.tags.has(tile, CellTags::NO_FRUIT))
+                .filter(|&tile| distance(tile) < distance(fruit))
+                .min_by_key(|&tile| distance(tile));
+
+            if let Some(tile) = pull {
+                self.move_fruit(index, tile);
+            }
+        }
+    }
+
+    // Move the fruit at `index` to `tile` without it counting as a new fruit
+    fn move_fruit(&mut self, index: usize, tile: Position) {
+        self.free_cells.vacate(self.fruits[index].position);
+        self.fruits[index].position = tile;
+        self.free_cells.occupy(tile);
+    }
+
+    // Fruit left to rot for too long spawns again somewhere else
+    fn rot_fruit(&mut self) {
+        for index in 0..self.fruits.len() {
+            if self.rot(&self.fruits[index]).is_some_and(|rot| rot >= 1.0) {
+                self.spawn_fruit(index);
+                self.events.push(GameEvent::FruitRotted);
+            }
+        }
+    }
+
+    fn grow_snake(&mut self, index: usize) {
+        let snake = &mut self.snakes[index];
+
+        if let Some(tail) = snake.popped_tail() {
+            snake.grow();
+            self.free_cells.occupy(tail.position);
+            self.bodies.add(tail.position);
+        }
+    }
+
+    // Turn the bodies of rival snakes that have died into pellets, on whichever of their tiles are free.
+    // The player's snake dying ends the match, so it's left to disappear as usual
+    fn drop_pellets(&mut self) {
+        if !self.rules.rivals() {
+            return;
+        }
+
+        for index in 1..self.snakes.len() {
+            if self.snakes[index].alive || self.snakes[index].is_empty() {
+                continue;
+            }
+
+            let tiles = self.snakes[index].shed();
+
+            for tile in &tiles {
+                self.free_cells.vacate(*tile);
+                self.bodies.remove(*tile);
+            }
+
+            for tile in tiles {
+                if self.free_cells.contains(tile) {
+                    self.free_cells.occupy(tile);
+                    self.pellets.push(Pellet {
+                        position: tile,
+                        snake: index
+                    });
+                }
+            }
+        }
+    }
+
+    // Check whether the run has been won or lost, and respawn any snake that lost a life
+    fn update_status(&mut self) {
+        let run = self.run_state();
+
+        self.rules.on_tick(&run);
+        self.place_spawns();
+
+        let status = self.rules.status(&self.run_state());
+
+        // With lives to spare, respawn once a dead snake has finished disappearing. Rivals stay out
+        for index in 0..self.snakes.len() {
+            if status == Status::Playing && !self.rules.rivals() && !self.snakes[index].alive && self.snakes[index].is_empty() {
+                self.lives -= 1;
+
+                // With nowhere to come back, the life is spent too, and the next goes the same way until
+                // there's room or none are left
+                if !self.respawn_snake(index) {
+                    self.events.push(GameEvent::Crowded { snake: index });
+                }
+            }
+        }
+
+        // Snakes go down together, any left alive disappearing along with the one that died
+        if status == Status::Lost {
+            for snake in &mut self.snakes {
+                snake.kill();
+            }
+        }
+
+        if status == Status::Won {
+            self.events.push(GameEvent::Won);
+        }
+
+        self.status = status;
+    }
+
+    // Put whatever the rules asked for on the map
+    fn place_spawns(&mut self) {
+        for spawn in self.rules.take_spawns() {
+            match spawn {
+                Spawn::Fruit(position) => if self.free_cells.contains(position) {
+                    self.set_fruit(position);
+                },
+                Spawn::Bomb(position) => if self.arena.is_open(position) {
+                    self.spawn_hazard(HazardKind::Bomb(position));
+                },
+                Spawn::Wall => self.raise_walls(1)
+            }
+        }
+    }
+
+    // Wall off up to `count` random free tiles, passing over any that would cut part of the arena off from
+    // the rest or go up in the runway ahead of a snake. With symmetry, walls go up in pairs, each tile with
+    // its image on the other side of the map
+    fn raise_walls(&mut self, count: usize) {
+        if count == 0 {
+            return; // Without touching the RNG, so runs without obstacles play out as they always have
+        }
+
+        let mut tiles = self.free_cells.as_slice().to_vec();
+        let ahead: Vec<Position> = self.snakes.iter()
+            .filter(|snake| snake.alive)
+            .filter_map(|snake| snake.front().map(|head| (head.position, Position::from(snake.direction()))))
+            .flat_map(|(head, step)| (1..=Snake::SAFE_RUNWAY).map(move |distance| head + step * distance))
+            .collect();
+        let mut raised = 0;
+
+        tiles.shuffle(&mut self.rng);
+
+        for tile in tiles {
+            if raised >= count {
+                break;
+            }
+
+            let image = self.config.symmetry.image(tile, self.config.dimensions).filter(|image| *image != tile);
+            let walls: Vec<Position> = std::iter::once(tile).chain(image).collect();
+
+            if walls.iter().any(|wall| ahead.contains(wall) || !self.free_cells.contains(*wall)) {
+                continue;
+            }
+
+            let mut arena = self.arena.clone();
+
+            for wall in &walls {
+                arena.add_wall(*wall);
+            }
+
+            if arena.unreachable().is_none() {
+                self.arena = arena;
+
+                for wall in &walls {
+                    self.free_cells.occupy(*wall);
+                }
+
+                raised += walls.len();
+            }
+        }
+    }
+
+    fn spawn_fruit(&mut self, index: usize) {
+        let tags = &self.tags;
+        let rules = &self.rules;
+        let policy = &self.spawn_policy;
+        let heads: Vec<Option<Position>> = self.snakes.iter()
+            .map(|snake| snake.front().filter(|_| snake.alive).map(|head| head.position))
+            .collect();
+        let context = SpawnContext {
+            snake: index,
+            heads: &heads
+        };
+
+        self.free_cells.vacate(self.fruits[index].position);
+
+        // A fruit spawning afresh takes the rest of its cluster with it, such as when it rots
+        for fruit in self.clusters[index].drain(..) {
+            self.free_cells.vacate(fruit.position);
+        }
+
+        // Cells only need weighing up when the level, mode or spawn policy has a say in where fruit goes
+        let spawn_point = if tags.affects_spawning() || rules.weighs_spawns() || !policy.is_uniform() {
+            self.free_cells.as_slice()
+                .choose_weighted(&mut self.rng, |point| rules.spawn_weight(*point, tags.spawn_weight(*point)) * policy.weight(*point, &context))
+                .ok()
+                .copied()
+        } else {
+            self.free_cells.choose(&mut self.rng)
+        };
+
+        // Once the snake fills the map (or every free cell is off limits) there is nowhere left for fruit to go
+        let fruit = Fruit {
+            position: spawn_point.unwrap_or_else(|| Position::new(-1, -1)),
+            kind: self.config.fruits.choose(&mut self.fruit_rng),
+            spawned_at: self.time,
+            age: 0
+        };
+
+        self.free_cells.occupy(fruit.position);
+        self.fruits[index] = fruit;
+
+        let cluster: Vec<Fruit> = self.spawn_policy.cluster(fruit.position).into_iter()
+            .filter(|tile| self.free_cells.contains(*tile) && !self.tags.has(*tile, CellTags::NO_FRUIT))
+            .map(|position| Fruit {
+                position,
+                ..fruit
+            })
+            .collect();
+
+        for fruit in &cluster {
+            self.free_cells.occupy(fruit.position);
+        }
+
+        self.clusters[index] = cluster;
+    }
+
+    // Start a feeding frenzy when it's due, and clear it away once it has run its course
+    fn update_frenzy(&mut self) {
+        let elapsed = self.time - self.frenzy_clock;
+
+        if self.frenzy.is_active() && elapsed >= Frenzy::DURATION {
+            for fruit in self.frenzy.fruits() {
+                self.free_cells.vacate(*fruit);
+            }
+
+            self.frenzy.end();
+            self.frenzy_clock = self.time;
+        } else if !self.frenzy.is_active() && elapsed >= Frenzy::INTERVAL {
+            let mut spawn_locations = self.empty_points().to_vec();
+
+            spawn_locations.retain(|point| !self.tags.has(*point, CellTags::NO_FRUIT));
+            spawn_locations.shuffle(&mut self.rng);
+            self.frenzy.start(&spawn_locations);
+            self.frenzy_clock = self.time;
+
+            for fruit in self.frenzy.fruits() {
+                self.free_cells.occupy(*fruit);
+            }
+        }
+    }
+
+    // Spawn the hazards the level's script has scheduled, age the existing ones, and kill the snake if
+    // its head has run into one that's armed
+    fn update_hazards(&mut self) {
+        for spec in self.schedule.due(self.time) {
+            let kind = match spec {
+                HazardSpec::LaserRow(y) => Some(HazardKind::LaserRow(y)),
+                HazardSpec::LaserColumn(x) => Some(HazardKind::LaserColumn(x)),
+                HazardSpec::Bomb(Some(position)) => Some(HazardKind::Bomb(position)),
+                HazardSpec::Bomb(None) => self.free_cells.choose(&mut self.rng).map(HazardKind::Bomb),
+                HazardSpec::BombNearFruit => {
+                    let fruit = self.fruit();
+                    let sites: Vec<Position> = self.config.grid.neighbours(fruit)
+                        .filter(|&tile| self.is_pathable(tile))
+                        .collect();
+
+                    sites.choose(&mut self.rng).copied().map(HazardKind::Bomb)
+                }
+            };
+
+            if let Some(kind) = kind {
+                self.spawn_hazard(kind);
+            }
+        }
+
+        for hazard in &mut self.hazards {
+            hazard.update(self.time);
+        }
+
+        let dimensions = self.config.dimensions;
+        let arena = &self.arena;
+        let free_cells = &mut self.free_cells;
+
+        // Walls stay taken after the hazards over them have gone
+        self.hazards.retain(|hazard| {
+            if hazard.is_expired() {
+                for tile in hazard.tiles(dimensions).into_iter().filter(|tile| arena.is_open(*tile)) {
+                    free_cells.vacate(tile);
+                }
+            }
+
+            !hazard.is_expired()
+        });
+
+        for (index, head) in self.live_heads() {
+            if self.hazards.iter().any(|hazard| hazard.is_armed() && hazard.covers(head)) {
+                self.snakes[index].kill();
+                self.events.push(GameEvent::Died {
+                    cause: DeathCause::Hazard,
+                    snake: index,
+                    position: head
+                });
+            }
+        }
+    }
+
+    fn spawn_hazard(&mut self, kind: HazardKind) {
+        if self.hazards.len() < Self::MAX_HAZARDS {
+            let hazard = Hazard::new(kind, self.time);
+
+            for tile in hazard.tiles(self.config.dimensions) {
+                if self.arena.is_open(tile) {
+                    self.free_cells.occupy(tile);
+                }
+            }
+
+            self.hazards.push(hazard);
+        }
+    }
+
+    // Bring the snake at `index` back at its starting length, somewhere with room to move, after losing
+    // a life. On a crowded map it settles for less room ahead, down to just enough for its head, and
+    // returns false if there isn't even that
+    fn respawn_snake(&mut self, index: usize) -> bool {
+        let dimensions = self.config.dimensions;
+        let spawns = (1..=Snake::SAFE_RUNWAY)
+            .rev()
+            .map(|runway| self.spawns_with_runway(runway))
+            .find(|spawns| !spawns.is_empty())
+            .unwrap_or_default();
+        let (spawn_point, direction) = match spawns.choose(&mut self.rng) {
+            Some(spawn) => *spawn,
+            None => return false
+        };
+
+        for segment in self.snakes[index].iter() {
+            self.free_cells.vacate(segment.position);
+            self.bodies.remove(segment.position);
+        }
+
+        self.snakes[index] = Snake::new(spawn_point, direction, dimensions, self.config.grid, self.config.skin);
+        self.buffered_directions[index] = None;
+
+        for segment in self.snakes[index].iter() {
+            self.free_cells.occupy(segment.position);
+            self.bodies.add(segment.position);
+        }
+        self.headings[index] = direction;
+
+        true
+    }
+
+    // Every empty tile a snake could start on, and the way it would face, with `runway` clear tiles ahead
+    fn spawns_with_runway(&self, runway: i32) -> Vec<(Position, Direction)> {
+        let grid = self.config.grid;
+        let mut spawns = Vec::new();
+
+        for point in self.empty_points().iter().copied() {
+            for direction in grid.directions().iter().copied() {
+                let runway_clear = std::iter::successors(Some(point), |tile| Some(grid.step(*tile, direction)))
+                    .take(runway as usize + 1)
+                    .all(|tile| {
+                        self.arena.is_open(tile) &&
+                        !self.fruits.iter().any(|fruit| fruit.position == tile) &&
+                        self.snakes.iter().all(|snake| snake.segments_at(tile) == 0)
+                    });
+
+                if runway_clear {
+                    spawns.push((point, direction));
+                }
+            }
+        }
+
+        spawns
+    }
+
+    // Every tile with nothing on it
+    pub fn empty_points(&self) -> &[Position] {
+        self.free_cells.as_slice()
+    }
+
+    // Whether something could move onto `position`: it's on the map, empty, and clear of hazards
+    pub fn is_pathable(&self, position: Position) -> bool {
+        self.free_cells.contains(position) && !self.hazards.iter().any(|hazard| hazard.covers(position))
+    }
+
+    // The current and maximum sizes of every buffer the engine holds on to, for spotting unbounded growth
+    pub fn buffer_sizes(&self) -> Vec<BufferSize> {
+        vec![
+            BufferSize {
+                name: "snake body",
+                len: self.snakes.iter().map(|snake| snake.len()).sum(),
+                capacity: (self.config.dimensions.0 * self.config.dimensions.1) as usize
+            },
+            BufferSize {
+                name: "frenzy fruit",
+                len: self.frenzy.fruits().len(),
+                capacity: Frenzy::FRUIT_COUNT
+            },
+            BufferSize {
+                name: "free cells",
+                len: self.free_cells.len(),
+                capacity: (self.config.dimensions.0 * self.config.dimensions.1) as usize
+            },
+            BufferSize {
+                name: "hazards",
+                len: self.hazards.len(),
+                capacity: Self::MAX_HAZARDS
+            }
+        ]
+    }
+}
@@ -0,0 +1,407 @@
+use super::{Engine, Position, Status};
+use super::versus::WinCondition;
+use super::spawn::{Clusters, Fair, SpawnPolicy, Uniform};
+
+// Which set of rules a run is played by. Kept as a plain value so it can be cycled through in menus and
+// used to file high scores; the rules themselves live in the `GameMode` it builds
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Mode {
+    #[default]
+    Classic,
+    Hardcore,
+    Lives,
+    Coop,
+    Versus,
+    Royale,
+    Casual,
+    Practice,
+    Obstacles,
+    Clusters
+}
+
+// How directional input is applied to the snake
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputPolicy {
+    Immediate, // Every key press changes the snake's direction straight away
+    SingleBuffered // Only the first key press between slithers is kept, and applied on the next slither
+}
+
+impl Mode {
+    pub const ALL: [Mode; 10] = [
+        Mode::Classic, Mode::Hardcore, Mode::Lives, Mode::Coop, Mode::Versus, Mode::Royale, Mode::Casual, Mode::Practice,
+        Mode::Obstacles, Mode::Clusters
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "Classic",
+            Self::Hardcore => "Hardcore",
+            Self::Lives => "Lives",
+            Self::Coop => "Co-op",
+            Self::Versus => "Versus",
+            Self::Royale => "Royale",
+            Self::Casual => "Casual",
+            Self::Practice => "Practice",
+            Self::Obstacles => "Obstacles",
+            Self::Clusters => "Clusters"
+        }
+    }
+
+    // A fresh copy of the mode's rules, for a new run
+    pub fn rules(self) -> Box<dyn GameMode> {
+        match self {
+            Self::Classic => Box::new(Classic),
+            Self::Hardcore => Box::new(Hardcore),
+            Self::Lives => Box::new(Lives),
+            Self::Coop => Box::new(Coop),
+            Self::Versus => Box::new(Versus),
+            Self::Royale => Box::new(Royale),
+            Self::Casual => Box::new(Casual),
+            Self::Practice => Box::new(Practice),
+            Self::Obstacles => Box::new(Obstacles::default()),
+            Self::Clusters => Box::new(Clustered)
+        }
+    }
+
+    // How many levels of the campaign have to be passed before the mode can be picked
+    pub fn levels_to_unlock(self) -> usize {
+        match self {
+            Self::Hardcore => 2,
+            Self::Royale => 4,
+            _ => 0
+        }
+    }
+
+    // The mode following this one, for cycling through modes in menus
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+// What a mode gets to see of a run when applying its rules
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RunState {
+    pub time: f64, // Simulated seconds since the run started
+    pub snake_length: usize, // Of every snake put together
+    pub alive: bool, // Whether every snake is alive
+    pub lives: usize, // Lives remaining, including the current one
+    pub points: usize, // The player's, or everyone's when the snakes work together
+    pub fruits_eaten: usize,
+    pub cells: usize, // Tiles on the map
+    pub player_alive: bool,
+    pub rivals_alive: usize, // Rival snakes still alive, in modes where the snakes play against each other
+    pub rival_points: usize, // The best score among the rivals
+    pub rival_fruits: usize, // The most fruit eaten by any rival
+    pub win_condition: WinCondition // What decides a match between rivals
+}
+
+// Something a mode asks to have placed on the map
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Spawn {
+    Fruit(Position), // Moves the fruit, if the tile is free
+    Bomb(Position),
+    Wall // Walls off a free tile of the engine's choosing, never one that would cut off part of the arena
+}
+
+// The rules of a way to play. The defaults are those of classic play, so a mode only needs to spell
+// out where it differs. Hooks are called by the engine: `on_start` once the run is set up, `on_tick`
+// after every slither, and `status` straight after to decide whether the run is over. Whatever
+// `take_spawns` hands back after a hook is placed on the map
+pub trait GameMode {
+    fn name(&self) -> &str;
+
+    fn slithers_per_second(&self) -> u32 {
+        Engine::SLITHERS_PER_SECOND
+    }
+
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    fn input_policy(&self) -> InputPolicy {
+        InputPolicy::Immediate
+    }
+
+    // Whether the snakes wait for the player to pick a way to go before each slither, rather than keeping
+    // to the clock
+    fn is_turn_based(&self) -> bool {
+        false
+    }
+
+    // The size of map the mode is played on, given the size the run's config asks for
+    fn map_dimensions(&self, requested: (u32, u32)) -> (u32, u32) {
+        requested
+    }
+
+    // The number of times the snake can die before the game is over
+    fn lives(&self) -> usize {
+        1
+    }
+
+    // How many snakes share the map, the first of them being the player's. Each gets a fruit of its own
+    // to chase
+    fn snakes(&self) -> usize {
+        1
+    }
+
+    // How many of the snakes are steered from the keyboard, the rest being left to bots
+    fn players(&self) -> usize {
+        self.snakes()
+    }
+
+    // How many times a run can be rewound a moment back from the snake's death and carried on with
+    fn undos(&self) -> usize {
+        0
+    }
+
+    // Whether runs are only practice: the pace can be changed mid-run, and the scores aren't recorded
+    fn is_practice(&self) -> bool {
+        false
+    }
+
+    // Whether the snakes play against each other, each keeping a score of its own and leaving its body
+    // behind as pellets once dead, rather than working together on one
+    fn rivals(&self) -> bool {
+        false
+    }
+
+    // Points actually awarded for a fruit the scoring policy valued at `points`
+    fn fruit_points(&self, points: usize) -> usize {
+        points
+    }
+
+    // Whether the mode has a say in where fruit spawns, through `spawn_weight`
+    fn weighs_spawns(&self) -> bool {
+        false
+    }
+
+    // Relative chance of fruit spawning on a free tile the level gave `weight`
+    fn spawn_weight(&self, _position: Position, weight: f64) -> f64 {
+        weight
+    }
+
+    // Whether a fresh fruit spawns once the last one on the map has been eaten
+    fn respawns_fruit(&self) -> bool {
+        true
+    }
+
+    // How fruit is placed around the snakes, called once at the start of each run
+    fn spawn_policy(&self) -> Box<dyn SpawnPolicy> {
+        Box::new(Uniform)
+    }
+
+    fn take_spawns(&mut self) -> Vec<Spawn> {
+        Vec::new()
+    }
+
+    fn on_start(&mut self, _run: &RunState) {}
+
+    fn on_tick(&mut self, _run: &RunState) {}
+
+    // Whether the run has been won, lost or is still going. A snake filling the map wins, and dying
+    // on the last life loses
+    fn status(&self, run: &RunState) -> Status {
+        if run.snake_length == run.cells {
+            Status::Won
+        } else if !run.alive && run.lives <= 1 {
+            Status::Lost
+        } else {
+            Status::Playing
+        }
+    }
+}
+
+pub struct Classic;
+
+impl GameMode for Classic {
+    fn name(&self) -> &str {
+        "Classic"
+    }
+}
+
+// Twice the speed, no pausing, and only one turn per slither
+pub struct Hardcore;
+
+impl GameMode for Hardcore {
+    fn name(&self) -> &str {
+        "Hardcore"
+    }
+
+    fn slithers_per_second(&self) -> u32 {
+        Engine::SLITHERS_PER_SECOND * 2
+    }
+
+    fn can_pause(&self) -> bool {
+        false
+    }
+
+    fn input_policy(&self) -> InputPolicy {
+        InputPolicy::SingleBuffered
+    }
+}
+
+// Classic play with a few lives to lose
+pub struct Lives;
+
+impl GameMode for Lives {
+    fn name(&self) -> &str {
+        "Lives"
+    }
+
+    fn lives(&self) -> usize {
+        3
+    }
+}
+
+// Two players at one keyboard, each with a snake, working together on one score. Should either snake
+// die, the run is lost for both
+pub struct Coop;
+
+impl GameMode for Coop {
+    fn name(&self) -> &str {
+        "Co-op"
+    }
+
+    fn snakes(&self) -> usize {
+        2
+    }
+}
+
+// The player against a bot, with a fruit each, played to the win condition of the run's config
+pub struct Versus;
+
+impl GameMode for Versus {
+    fn name(&self) -> &str {
+        "Versus"
+    }
+
+    fn snakes(&self) -> usize {
+        2
+    }
+
+    fn players(&self) -> usize {
+        1
+    }
+
+    fn rivals(&self) -> bool {
+        true
+    }
+
+    fn spawn_policy(&self) -> Box<dyn SpawnPolicy> {
+        Box::new(Fair)
+    }
+
+    fn status(&self, run: &RunState) -> Status {
+        run.win_condition.status(run)
+    }
+}
+
+// The player against a crowd of bots on a larger map, played to the win condition of the run's config
+pub struct Royale;
+
+impl Royale {
+    pub const SNAKES: usize = 6;
+    pub const MAP_DIMENSIONS: (u32, u32) = (40, 40); // At the least
+}
+
+impl GameMode for Royale {
+    fn name(&self) -> &str {
+        "Royale"
+    }
+
+    fn map_dimensions(&self, requested: (u32, u32)) -> (u32, u32) {
+        (requested.0.max(Self::MAP_DIMENSIONS.0), requested.1.max(Self::MAP_DIMENSIONS.1))
+    }
+
+    fn snakes(&self) -> usize {
+        Self::SNAKES
+    }
+
+    fn players(&self) -> usize {
+        1
+    }
+
+    fn rivals(&self) -> bool {
+        true
+    }
+
+    fn spawn_policy(&self) -> Box<dyn SpawnPolicy> {
+        Box::new(Fair)
+    }
+
+    fn status(&self, run: &RunState) -> Status {
+        run.win_condition.status(run)
+    }
+}
+
+// Classic play, forgiving enough to take back a few deaths a run
+pub struct Casual;
+
+impl GameMode for Casual {
+    fn name(&self) -> &str {
+        "Casual"
+    }
+
+    fn undos(&self) -> usize {
+        3
+    }
+}
+
+// Classic play at whatever pace the player sets, to get the feel of it without it counting
+pub struct Practice;
+
+impl GameMode for Practice {
+    fn name(&self) -> &str {
+        "Practice"
+    }
+
+    fn is_practice(&self) -> bool {
+        true
+    }
+}
+
+// Classic play on a map that closes in: a wall goes up on a free tile every few seconds, never walling off
+// any part of the arena from the rest
+#[derive(Default)]
+pub struct Obstacles {
+    walls: usize, // Raised so far
+    spawns: Vec<Spawn>
+}
+
+impl Obstacles {
+    pub const WALL_SECONDS: f64 = 5.0;
+}
+
+impl GameMode for Obstacles {
+    fn name(&self) -> &str {
+        "Obstacles"
+    }
+
+    fn take_spawns(&mut self) -> Vec<Spawn> {
+        std::mem::take(&mut self.spawns)
+    }
+
+    fn on_tick(&mut self, run: &RunState) {
+        let due = (run.time / Self::WALL_SECONDS) as usize;
+
+        if due > self.walls {
+            self.walls = due;
+            self.spawns.push(Spawn::Wall);
+        }
+    }
+}
+
+// Classic play with fruit in clusters, each to be cleared before the next turns up
+pub struct Clustered;
+
+impl GameMode for Clustered {
+    fn name(&self) -> &str {
+        "Clusters"
+    }
+
+    fn spawn_policy(&self) -> Box<dyn SpawnPolicy> {
+        Box::new(Clusters)
+    }
+}
@@ -0,0 +1,47 @@
+use super::geometry::Position;
+
+// How many things cover each tile of the map, so asking whether a tile is taken doesn't mean searching
+// everything that could be on it. Counts rather than flags, since pieces can share a tile (a freshly
+// spawned snake is coiled up on one). Tiles outside the map are never occupied
+pub struct Occupancy {
+    dimensions: (u32, u32),
+    counts: Vec<u16> // In row-major order
+}
+
+impl Occupancy {
+    pub fn new(dimensions: (u32, u32)) -> Self {
+        Self {
+            dimensions,
+            counts: vec![0; (dimensions.0 * dimensions.1) as usize]
+        }
+    }
+
+    // Where the tile sits in row-major order, if it's on the map
+    pub fn index(&self, position: Position) -> Option<usize> {
+        if position.in_bounds(self.dimensions) {
+            Some(position.y as usize * self.dimensions.0 as usize + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn add(&mut self, position: Position) {
+        if let Some(index) = self.index(position) {
+            self.counts[index] += 1;
+        }
+    }
+
+    pub fn remove(&mut self, position: Position) {
+        if let Some(index) = self.index(position) {
+            self.counts[index] = self.counts[index].saturating_sub(1);
+        }
+    }
+
+    pub fn count(&self, position: Position) -> u16 {
+        self.index(position).map_or(0, |index| self.counts[index])
+    }
+
+    pub fn is_occupied(&self, position: Position) -> bool {
+        self.count(position) > 0
+    }
+}
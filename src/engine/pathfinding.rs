@@ -0,0 +1,160 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::{Direction, Position};
+use super::controller::{BoardView, Controller};
+
+// Plans a shortest safe path to the fruit with A*, following it until the fruit moves or something
+// gets in the way, then planning again. A path is only taken if the snake could still reach its own
+// tail after eating, so it doesn't box itself in. Otherwise it chases its tail, which always moves out
+// of the way, and failing that takes whichever safe move leaves the most room
+#[derive(Default)]
+pub struct AStarBot {
+    path: VecDeque<Position>, // The tiles still to be visited, starting with the next one
+    target: Option<Position>
+}
+
+impl AStarBot {
+    fn next_step_is_safe(&self, view: &BoardView, head: Position) -> bool {
+        self.path.front().is_some_and(|&next| {
+            view.grid().distance(head, next) == 1 && view.is_safe(next)
+        })
+    }
+
+    fn replan(&mut self, view: &BoardView) {
+        let fruit = view.fruit();
+        let body: Vec<Position> = view.snake().iter().map(|segment| segment.position).collect();
+
+        self.path.clear();
+        self.target = None;
+
+        match find_path(view, &body, fruit) {
+            Some(path) if tail_reachable_after(view, &body, &path) => {
+                self.path = path;
+                self.target = Some(fruit);
+            },
+            _ => if let Some(path) = body.last().and_then(|&tail| find_path(view, &body, tail)) {
+                self.path = path.into_iter().take(1).collect(); // Reconsider the fruit after every step
+            }
+        }
+    }
+}
+
+impl Controller for AStarBot {
+    fn decide(&mut self, view: &BoardView) -> Option<Direction> {
+        if !view.snake().alive {
+            self.path.clear();
+            return None;
+        }
+
+        let head = view.head()?;
+
+        if self.target != Some(view.fruit()) || !self.next_step_is_safe(view, head) {
+            self.replan(view);
+        }
+
+        match self.path.pop_front() {
+            Some(next) => view.grid().direction_between(head, next),
+            None => roomiest_safe_direction(view, head)
+        }
+    }
+}
+
+// Whether a snake with `body` that follows `path` to the fruit, growing as it eats, could then reach its tail
+fn tail_reachable_after(view: &BoardView, body: &[Position], path: &VecDeque<Position>) -> bool {
+    let after: Vec<Position> = path.iter()
+        .rev()
+        .chain(body.iter())
+        .copied()
+        .take(body.len() + 1)
+        .collect();
+
+    after.last().is_some_and(|&tail| find_path(view, &after, tail).is_some())
+}
+
+// How many slithers until each tile under `body` is free again, indexed in row-major order
+fn vacate_times(dimensions: (u32, u32), body: &[Position]) -> Vec<usize> {
+    let mut times = vec![0; (dimensions.0 * dimensions.1) as usize];
+
+    for (index, position) in body.iter().enumerate() {
+        if position.in_bounds(dimensions) {
+            let cell = (position.y as u32 * dimensions.0 + position.x as u32) as usize;
+
+            times[cell] = times[cell].max(body.len() - index);
+        }
+    }
+
+    times
+}
+
+// The shortest path for a snake with `body` from its head to `goal`, not including the head, over tiles
+// that will be clear by the time the head gets to them
+fn find_path(view: &BoardView, body: &[Position], goal: Position) -> Option<VecDeque<Position>> {
+    let (width, height) = view.dimensions();
+    let grid = view.grid();
+    let start = *body.first()?;
+
+    if !start.in_bounds((width, height)) || !goal.in_bounds((width, height)) {
+        return None;
+    }
+
+    let index = |position: Position| (position.y as u32 * width + position.x as u32) as usize;
+    let position = |index: usize| Position::new((index as u32 % width) as i32, (index as u32 / width) as i32);
+
+    let vacate_times = vacate_times((width, height), body);
+    let passable = |tile: Position, step: usize| {
+        view.arena().is_open(tile) &&
+        step >= vacate_times[index(tile)] &&
+        !view.is_taken_by_others(tile) &&
+        !view.hazards().iter().any(|hazard| hazard.covers(tile))
+    };
+
+    let mut steps = vec![usize::MAX; (width * height) as usize];
+    let mut came_from = vec![None; (width * height) as usize];
+    let mut open = BinaryHeap::new();
+
+    steps[index(start)] = 0;
+    open.push(Reverse((grid.distance(start, goal) as usize, 0, index(start))));
+
+    while let Some(Reverse((_, step, current))) = open.pop() {
+        if current == index(goal) {
+            let mut path = VecDeque::new();
+            let mut tile = current;
+
+            while tile != index(start) {
+                path.push_front(position(tile));
+                tile = came_from[tile]?;
+            }
+
+            return Some(path);
+        }
+
+        if step > steps[current] {
+            continue; // A shorter way here has already been explored
+        }
+
+        for neighbour in grid.neighbours(position(current)) {
+            if passable(neighbour, step + 1) && step + 1 < steps[index(neighbour)] {
+                steps[index(neighbour)] = step + 1;
+                came_from[index(neighbour)] = Some(current);
+                open.push(Reverse((step + 1 + grid.distance(neighbour, goal) as usize, step + 1, index(neighbour))));
+            }
+        }
+    }
+
+    None
+}
+
+// The safe move with the most safe tiles around where it ends up, to put off being boxed in
+fn roomiest_safe_direction(view: &BoardView, head: Position) -> Option<Direction> {
+    let grid = view.grid();
+
+    grid.directions().iter()
+        .copied()
+        .filter(|&direction| view.is_safe(grid.step(head, direction)))
+        .max_by_key(|&direction| {
+            grid.neighbours(grid.step(head, direction))
+                .filter(|&next| next != head && view.is_safe(next))
+                .count()
+        })
+}
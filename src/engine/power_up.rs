@@ -0,0 +1,73 @@
+use super::geometry::Position;
+
+// Pickups that turn up on the map every so often, each giving the snake a helpful effect, apart from
+// confusion, which is best steered around
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PowerUp {
+    Shield, // Survives the next run into a wall or the snake's own body
+    Phase, // Lets the snake pass through its own body for a few seconds
+    Magnet, // Pulls the fruit towards the snake's head for a while
+    Confusion // Turns the player's controls back to front for a few seconds
+}
+
+// A power-up's effect on the snake, for as long as it lasts
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Effect {
+    pub power_up: PowerUp,
+    pub seconds_left: Option<f64> // Nothing for effects that last until they're used
+}
+
+// A power-up waiting on the map to be collected
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pickup {
+    pub position: Position,
+    pub power_up: PowerUp,
+    pub spawned_at: f64 // Run time, in seconds
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 4] = [PowerUp::Shield, PowerUp::Phase, PowerUp::Magnet, PowerUp::Confusion];
+
+    pub const INTERVAL: f64 = 30.0; // Seconds from one pickup going to the next appearing
+    pub const PICKUP_LIFETIME: f64 = 10.0; // Seconds a pickup waits to be collected
+    pub const PHASE_SECONDS: f64 = 5.0;
+    pub const MAGNET_SECONDS: f64 = 8.0;
+    pub const CONFUSION_SECONDS: f64 = 5.0;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Shield => "Shield",
+            Self::Phase => "Phase",
+            Self::Magnet => "Magnet",
+            Self::Confusion => "Confused"
+        }
+    }
+
+    // Where the power-up comes in `ALL`
+    pub fn index(self) -> usize {
+        Self::ALL.iter().position(|power_up| *power_up == self).unwrap()
+    }
+
+    // The effect the power-up has once collected
+    pub fn effect(self) -> Effect {
+        Effect {
+            power_up: self,
+            seconds_left: match self {
+                Self::Shield => None,
+                Self::Phase => Some(Self::PHASE_SECONDS),
+                Self::Magnet => Some(Self::MAGNET_SECONDS),
+                Self::Confusion => Some(Self::CONFUSION_SECONDS)
+            }
+        }
+    }
+}
+
+impl Effect {
+    // The effect's name, with the seconds it has left if it runs out over time
+    pub fn describe(&self) -> String {
+        match self.seconds_left {
+            Some(seconds) => format!("{} {:.0}s", self.power_up.name(), seconds.ceil()),
+            None => self.power_up.name().to_string()
+        }
+    }
+}
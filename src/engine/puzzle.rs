@@ -0,0 +1,157 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{Direction, Position, Status};
+use super::arena::ArenaMask;
+use super::mode::{GameMode, RunState};
+use super::schedule::ScriptError;
+
+// A hand-crafted puzzle: the snake laid out in a fixed shape, and fruit on fixed tiles, all to be eaten
+// without dying in as few moves as possible. The level's walls make up the rest of it
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Scenario {
+    pub snake: Vec<Position>, // Head first, each segment next to the one before it
+    pub fruit: Vec<Position>
+}
+
+impl Scenario {
+    pub const MAX_FRUIT: usize = 32; // Each fruit takes a bit of the solver's state
+    pub const MAX_STATES: usize = 500_000; // How far the solver searches before giving up on a scenario
+
+    pub fn is_empty(&self) -> bool {
+        self.snake.is_empty() && self.fruit.is_empty()
+    }
+
+    // Parse a level script line of the form "puzzle snake <x> <y> <x> <y> ...", head first, or
+    // "puzzle fruit <x> <y>"
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: &str| ScriptError {
+            line: line_number,
+            message: message.to_string()
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let coordinates: Option<Vec<i32>> = words.iter().skip(2).map(|word| word.parse().ok()).collect();
+        let positions: Vec<Position> = coordinates
+            .filter(|coordinates| !coordinates.is_empty() && coordinates.len() % 2 == 0)
+            .ok_or_else(|| error("expected pairs of whole-number coordinates"))?
+            .chunks(2)
+            .map(|pair| Position::new(pair[0], pair[1]))
+            .collect();
+
+        match words.get(1) {
+            Some(&"snake") if positions.len() < 2 => Err(error("the snake needs at least two segments")),
+            Some(&"snake") if positions.windows(2).any(|pair| pair[0].manhattan_distance(pair[1]) != 1) => {
+                Err(error("each segment of the snake must be next to the one before it"))
+            },
+            Some(&"snake") => {
+                self.snake = positions;
+                Ok(())
+            },
+            Some(&"fruit") if self.fruit.len() + positions.len() > Self::MAX_FRUIT => Err(error("too much fruit")),
+            Some(&"fruit") => {
+                self.fruit.extend(positions);
+                Ok(())
+            },
+            _ => Err(error("expected 'puzzle snake' or 'puzzle fruit'"))
+        }
+    }
+
+    // Whether a snake and its fruit are laid out where they can be: on open tiles of `arena`, without the
+    // snake crossing itself or fruit under it
+    pub fn fits(&self, arena: &ArenaMask) -> Result<(), Position> {
+        let mut taken = HashSet::new();
+
+        match self.snake.iter().chain(&self.fruit).find(|tile| !arena.is_open(**tile) || !taken.insert(**tile)) {
+            Some(tile) => Err(*tile),
+            None => Ok(())
+        }
+    }
+
+    // The fewest moves that eat every fruit without dying, found by a breadth-first search of the ways
+    // the snake can move, or nothing if there's no way to or the search gives up. Moves follow the
+    // engine's rules: the tail moves out of the way of the head, unless the snake grows
+    pub fn solve(&self, arena: &ArenaMask) -> Option<Vec<Direction>> {
+        if self.snake.len() < 2 || self.fruit.is_empty() {
+            return None;
+        }
+
+        let start = (VecDeque::from(self.snake.clone()), (1u64 << self.fruit.len()) - 1); // Fruit still to eat
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        seen.insert(start.clone());
+        queue.push_back((start, Vec::new()));
+
+        while let Some(((body, left), moves)) = queue.pop_front() {
+            for direction in Direction::ALL.iter().copied() {
+                let head = arena.wrap(body[0] + Position::from(direction));
+
+                // Turning back on the neck isn't a move, and a move into a wall or the body is fatal
+                if head == body[1] || !arena.is_open(head) || body.iter().take(body.len() - 1).any(|segment| *segment == head) {
+                    continue;
+                }
+
+                let eaten = self.fruit.iter().position(|fruit| *fruit == head).filter(|index| left & (1 << index) != 0);
+                let mut next = body.clone();
+
+                next.push_front(head);
+
+                if eaten.is_none() {
+                    next.pop_back();
+                }
+
+                let mut path = moves.clone();
+                let state = (next, eaten.map_or(left, |index| left & !(1 << index)));
+
+                path.push(direction);
+
+                if state.1 == 0 {
+                    return Some(path);
+                }
+
+                if seen.len() < Self::MAX_STATES && seen.insert(state.clone()) {
+                    queue.push_back((state, path));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// Played on a puzzle level: won once every fruit has been eaten, and lost if the snake dies. The snake
+// only moves when the player moves it, so each move can be thought through
+pub struct PuzzleMode {
+    fruit: usize // How many fruit the scenario has
+}
+
+impl PuzzleMode {
+    pub fn new(scenario: &Scenario) -> Self {
+        Self {
+            fruit: scenario.fruit.len()
+        }
+    }
+}
+
+impl GameMode for PuzzleMode {
+    fn name(&self) -> &str {
+        "Puzzle"
+    }
+
+    fn is_turn_based(&self) -> bool {
+        true
+    }
+
+    fn respawns_fruit(&self) -> bool {
+        false
+    }
+
+    fn status(&self, run: &RunState) -> Status {
+        if !run.player_alive {
+            Status::Lost
+        } else if run.fruits_eaten >= self.fruit {
+            Status::Won
+        } else {
+            Status::Playing
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+
+use super::Engine;
+use super::geometry::Position;
+
+// A hazard to spawn, as written in a level's script
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HazardSpec {
+    LaserRow(i32),
+    LaserColumn(i32),
+    Bomb(Option<Position>), // No position means a random free tile
+    BombNearFruit // On a random free tile next to the fruit, for when a level wants fruit to be earned
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Trigger {
+    At(f64), // Once, this many seconds into the run
+    Every(f64) // Repeatedly, at this interval in seconds
+}
+
+// A problem with a line of a level's script
+#[derive(Debug)]
+pub struct ScriptError {
+    pub line: usize,
+    pub message: String
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Clone)]
+struct Entry {
+    trigger: Trigger,
+    hazard: HazardSpec,
+    next_time: Option<f64> // When the entry fires next, or None once it is spent
+}
+
+// A timeline of hazards to spawn over the course of a run, parsed from script lines such as
+// "at 30s spawn laser row 5", "every 20s spawn bomb at random" and "every 25s spawn bomb near fruit"
+#[derive(Clone, Default)]
+pub struct Schedule {
+    entries: Vec<Entry>
+}
+
+impl Schedule {
+    // Parse a single script line, numbered for error reporting
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: String| ScriptError {
+            line: line_number,
+            message
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let seconds = |word: &str| word.strip_suffix('s')
+            .and_then(|number| number.parse::<f64>().ok())
+            .filter(|seconds| *seconds >= 0.0)
+            .ok_or_else(|| error(format!("invalid time '{}' (expected e.g. '30s')", word)));
+        let coordinate = |word: &str, limit: u32| word.parse::<i32>()
+            .ok()
+            .filter(|coordinate| *coordinate >= 0 && *coordinate < limit as i32)
+            .ok_or_else(|| error(format!("coordinate '{}' is outside the map", word)));
+
+        let (trigger, rest) = match words.as_slice() {
+            ["at", time, "spawn", rest @ ..] => (Trigger::At(seconds(time)?), rest),
+            ["every", time, "spawn", rest @ ..] => match seconds(time)? {
+                interval if interval > 0.0 => (Trigger::Every(interval), rest),
+                _ => return Err(error("'every' needs an interval above zero".to_string()))
+            },
+            _ => return Err(error("expected 'at <time> spawn ...' or 'every <time> spawn ...'".to_string()))
+        };
+
+        let (width, height) = Engine::MAP_DIMENSIONS;
+        let hazard = match rest {
+            ["laser", "row", y] => HazardSpec::LaserRow(coordinate(y, height)?),
+            ["laser", "column", x] => HazardSpec::LaserColumn(coordinate(x, width)?),
+            ["bomb", "at", "random"] => HazardSpec::Bomb(None),
+            ["bomb", "near", "fruit"] => HazardSpec::BombNearFruit,
+            ["bomb", "at", x, y] => HazardSpec::Bomb(Some(Position::new(coordinate(x, width)?, coordinate(y, height)?))),
+            _ => return Err(error(format!("unknown hazard '{}'", rest.join(" "))))
+        };
+
+        self.entries.push(Entry {
+            trigger,
+            hazard,
+            next_time: None
+        });
+        self.restart();
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Rewind the timeline to the start of a run
+    pub fn restart(&mut self) {
+        for entry in &mut self.entries {
+            entry.next_time = Some(match entry.trigger {
+                Trigger::At(time) | Trigger::Every(time) => time
+            });
+        }
+    }
+
+    // The hazards due to spawn by `run_time` (in seconds) which haven't been spawned yet
+    pub fn due(&mut self, run_time: f64) -> Vec<HazardSpec> {
+        let mut hazards = Vec::new();
+
+        for entry in &mut self.entries {
+            while let Some(time) = entry.next_time.filter(|time| *time <= run_time) {
+                hazards.push(entry.hazard);
+                entry.next_time = match entry.trigger {
+                    Trigger::At(_) => None,
+                    Trigger::Every(interval) => Some(time + interval)
+                };
+            }
+        }
+
+        hazards
+    }
+}
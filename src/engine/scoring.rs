@@ -0,0 +1,116 @@
+use super::snake::Snake;
+
+// How many points each fruit is worth, before the combo multiplier is applied
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ScoringPolicy {
+    #[default]
+    Flat, // Every fruit is worth what its kind is worth
+    Proximity // Fruit is worth more the quicker it is reached, and the longer the snake is
+}
+
+impl ScoringPolicy {
+    pub const ALL: [ScoringPolicy; 2] = [ScoringPolicy::Flat, ScoringPolicy::Proximity];
+
+    pub const QUICK_FRUIT_SECONDS: f64 = 10.0; // Fruit reached within this many seconds earns a speed bonus
+    pub const LENGTH_PER_BONUS_POINT: usize = 10; // Snake segments per extra point
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Flat => "Flat",
+            Self::Proximity => "Proximity"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|policy| *policy == self).unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    // Points for a fruit whose kind is worth `base` points, that took `seconds_to_reach` to get to and was
+    // eaten by a snake of `snake_length` segments
+    pub fn fruit_points(self, base: usize, seconds_to_reach: f64, snake_length: usize) -> usize {
+        match self {
+            Self::Flat => base,
+            Self::Proximity => {
+                let speed_bonus = (Self::QUICK_FRUIT_SECONDS - seconds_to_reach).max(0.0).ceil() as usize;
+                let length_bonus = snake_length.saturating_sub(Snake::STARTING_LENGTH) / Self::LENGTH_PER_BONUS_POINT;
+
+                base + speed_bonus + length_bonus
+            }
+        }
+    }
+}
+
+// Score of a run, with a multiplier that grows while fruit is eaten in quick succession
+pub struct Score {
+    points: usize,
+    fruits: usize, // Fruit eaten, of any kind
+    multiplier: usize,
+    last_fruit_time: Option<f64>, // Run time at which the previous fruit was eaten, for timing combos
+    assisted: bool // Whether the autopilot played any part of the run
+}
+
+impl Score {
+    pub const COMBO_TIMEOUT: f64 = 3.0; // Seconds allowed between fruits before the multiplier resets
+    pub const MAX_MULTIPLIER: usize = 5;
+
+    pub fn points(&self) -> usize {
+        self.points
+    }
+
+    pub fn fruits_eaten(&self) -> usize {
+        self.fruits
+    }
+
+    pub fn multiplier(&self) -> usize {
+        self.multiplier
+    }
+
+    pub fn is_assisted(&self) -> bool {
+        self.assisted
+    }
+
+    pub fn mark_assisted(&mut self) {
+        self.assisted = true;
+    }
+
+    // Award a fruit worth `points` eaten `time` seconds into the run
+    pub fn fruit_eaten(&mut self, points: usize, time: f64) {
+        match self.last_fruit_time.map(|previous| time - previous) {
+            Some(seconds) if seconds <= Self::COMBO_TIMEOUT => {
+                self.multiplier = (self.multiplier + 1).min(Self::MAX_MULTIPLIER);
+            },
+            _ => self.multiplier = 1
+        }
+
+        self.points += points * self.multiplier;
+        self.fruits += 1;
+        self.last_fruit_time = Some(time);
+    }
+
+    // Award a fruit whose points aren't affected by the combo multiplier
+    pub fn bonus_fruit_eaten(&mut self, points: usize) {
+        self.points += points;
+        self.fruits += 1;
+    }
+
+    // Drop the multiplier back down once the combo window has passed, `time` seconds into the run
+    pub fn expire_combo(&mut self, time: f64) {
+        if self.last_fruit_time.is_some_and(|previous| time - previous > Self::COMBO_TIMEOUT) {
+            self.multiplier = 1;
+        }
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self {
+            points: 0,
+            fruits: 0,
+            multiplier: 1,
+            last_fruit_time: None,
+            assisted: false
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::cmp::Ordering;
+
+use super::geometry::Direction;
+use super::fruit::FruitKind;
+use super::power_up::PowerUp;
+
+// The glyphs the game is drawn with, as listed in `data/skins.txt`. Everything drawn on the board
+// takes its glyph from here, so a skin made up of plain ASCII can be read with any font
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Skin {
+    pub name: &'static str,
+    pub horizontal: char,
+    pub vertical: char,
+    pub corners: (char, char, char, char), // Joining south and east, south and west, north and east, north and west
+    pub diagonals: (char, char), // Rising from the south-west to the north-east, then falling from the north-west to the south-east
+    pub fruits: [char; FruitKind::ALL.len()], // For each kind of fruit, in the order of `FruitKind::ALL`
+    pub bonus_fruit: char, // Frenzy fruit
+    pub power_ups: [char; PowerUp::ALL.len()], // For each power-up, in the order of `PowerUp::ALL`
+    pub laser: char,
+    pub bomb: char,
+    pub wall: char,
+    pub life: char,
+    pub arrows: [char; 4], // Pointing north, east, south and west
+    pub scrollbar: (char, char), // The thumb and the track
+    pub width: u8 // Columns each glyph takes up in a terminal, which is 2 for emoji
+}
+
+impl Skin {
+    const DATA: &'static str = include_str!("../../data/skins.txt");
+
+    // What a skin is drawn with until the data file says otherwise
+    const BLANK: Skin = Skin {
+        name: "",
+        horizontal: '?',
+        vertical: '?',
+        corners: ('?', '?', '?', '?'),
+        diagonals: ('?', '?'),
+        fruits: ['?'; FruitKind::ALL.len()],
+        bonus_fruit: '?',
+        power_ups: ['?'; PowerUp::ALL.len()],
+        laser: '?',
+        bomb: '?',
+        wall: '?',
+        life: '?',
+        arrows: ['?'; 4],
+        scrollbar: ('?', '?'),
+        width: 1
+    };
+
+    // Stands in for glyphs from elsewhere (such as level decorations) that an ASCII skin can't show
+    pub const ASCII_REPLACEMENT: char = '#';
+
+    // Every skin in the data file, in order. Lines that can't be made sense of are skipped
+    pub fn all() -> Vec<Skin> {
+        let mut skins: Vec<Skin> = Vec::new();
+
+        for line in Self::DATA.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                let base = skins.first().copied().unwrap_or(Self::BLANK);
+
+                skins.push(Skin {
+                    name: name.trim(),
+                    ..base
+                });
+            } else if let (Some(skin), Some((part, glyphs))) = (skins.last_mut(), line.split_once('=')) {
+                let glyphs: Vec<char> = glyphs.split_whitespace().filter_map(|glyph| glyph.chars().next()).collect();
+
+                skin.set_part(part.trim(), &glyphs);
+            }
+        }
+
+        skins
+    }
+
+    pub fn named(name: &str) -> Option<Skin> {
+        Self::all().into_iter().find(|skin| skin.name.eq_ignore_ascii_case(name))
+    }
+
+    fn set_part(&mut self, part: &str, glyphs: &[char]) {
+        match (part, glyphs) {
+            ("snake", &[horizontal, vertical, corner_0, corner_1, corner_2, corner_3]) => {
+                self.horizontal = horizontal;
+                self.vertical = vertical;
+                self.corners = (corner_0, corner_1, corner_2, corner_3);
+            },
+            ("diagonals", &[rising, falling]) => self.diagonals = (rising, falling),
+            ("fruit", &[apple, cherry, grape, mouse, bonus_fruit]) => {
+                self.fruits = [apple, cherry, grape, mouse];
+                self.bonus_fruit = bonus_fruit;
+            },
+            ("power_ups", &[shield, phase, magnet, confusion]) => self.power_ups = [shield, phase, magnet, confusion],
+            ("hazards", &[laser, bomb]) => {
+                self.laser = laser;
+                self.bomb = bomb;
+            },
+            ("wall", &[wall]) => self.wall = wall,
+            ("life", &[life]) => self.life = life,
+            ("arrows", &[north, east, south, west]) => self.arrows = [north, east, south, west],
+            ("scrollbar", &[thumb, track]) => self.scrollbar = (thumb, track),
+            ("width", &[width]) => if let Some(width) = width.to_digit(10) {
+                self.width = width as u8;
+            },
+            _ => {}
+        }
+    }
+
+    fn glyphs(self) -> Vec<char> {
+        let mut glyphs = vec![
+            self.horizontal, self.vertical, self.corners.0, self.corners.1, self.corners.2, self.corners.3, self.diagonals.0, self.diagonals.1,
+            self.bonus_fruit, self.laser, self.bomb, self.wall, self.life, self.scrollbar.0, self.scrollbar.1
+        ];
+
+        glyphs.extend_from_slice(&self.fruits);
+        glyphs.extend_from_slice(&self.power_ups);
+        glyphs.extend_from_slice(&self.arrows);
+
+        glyphs
+    }
+
+    // Whether `glyph` is one of this skin's own, rather than something from elsewhere like a decoration
+    pub fn is_own(self, glyph: char) -> bool {
+        self.glyphs().contains(&glyph)
+    }
+
+    // Whether every glyph in the skin is 7-bit ASCII
+    pub fn is_ascii(self) -> bool {
+        self.glyphs().iter().all(char::is_ascii)
+    }
+
+    // `glyph` if this skin can be expected to show it, or a stand-in if the skin is ASCII-only and it isn't
+    pub fn displayable(self, glyph: char) -> char {
+        if glyph.is_ascii() || !self.is_ascii() {
+            glyph
+        } else {
+            Self::ASCII_REPLACEMENT
+        }
+    }
+
+    pub fn fruit(self, kind: FruitKind) -> char {
+        self.fruits[kind.index()]
+    }
+
+    pub fn power_up(self, power_up: PowerUp) -> char {
+        self.power_ups[power_up.index()]
+    }
+
+    // There are no diagonal arrows, so diagonals point the way their body segments lie
+    pub fn arrow(self, direction: Direction) -> char {
+        match direction {
+            Direction::North => self.arrows[0],
+            Direction::East => self.arrows[1],
+            Direction::South => self.arrows[2],
+            Direction::West => self.arrows[3],
+            diagonal => self.straight(diagonal)
+        }
+    }
+
+    // The skin following this one, for cycling through skins in menus, passing over those the frontend
+    // can't draw. From a skin that can't be drawn, this is the first one that can
+    pub fn next(self, can_draw: impl Fn(Skin) -> bool) -> Self {
+        let skins: Vec<Skin> = Self::all().into_iter().filter(|&skin| can_draw(skin)).collect();
+        let index = skins.iter().position(|skin| *skin == self).map_or(0, |index| index + 1);
+
+        skins.get(index % skins.len().max(1)).copied().unwrap_or(self)
+    }
+
+    // The glyph for a segment heading straight along `direction`
+    pub fn straight(self, direction: Direction) -> char {
+        let (x, y) = direction.into();
+
+        self.along(x, y)
+    }
+
+    // The straight glyph lying nearest to a line running `x` across and `y` down
+    fn along(self, x: i32, y: i32) -> char {
+        match x.abs().cmp(&y.abs()) {
+            Ordering::Greater => self.horizontal,
+            Ordering::Less => self.vertical,
+            Ordering::Equal if x * y < 0 => self.diagonals.0,
+            Ordering::Equal => self.diagonals.1
+        }
+    }
+
+    pub fn is_straight(self, glyph: char) -> bool {
+        glyph == self.horizontal || glyph == self.vertical || glyph == self.diagonals.0 || glyph == self.diagonals.1
+    }
+
+    // The glyph for a segment joined up with its neighbours in `joins`. Where the segment joins up
+    // diagonally there are no corners to draw, so it lies along the line from one neighbour to the other
+    pub fn joining(self, joins: &[Direction]) -> char {
+        use Direction::*;
+
+        if joins.iter().any(|join| join.is_diagonal()) {
+            let step = |join: Option<&Direction>| join.map_or((0, 0), |&join| join.into());
+            let ((x, y), (back_x, back_y)) = (step(joins.first()), step(joins.get(1)));
+
+            return self.along(x - back_x, y - back_y);
+        }
+
+        let has = |direction| joins.contains(&direction);
+
+        match (has(North), has(East), has(South), has(West)) {
+            (false, true, true, false) => self.corners.0,
+            (false, false, true, true) => self.corners.1,
+            (true, true, false, false) => self.corners.2,
+            (true, false, false, true) => self.corners.3,
+            (true, false, _, false) | (_, false, true, false) => self.vertical,
+            _ => self.horizontal
+        }
+    }
+}
+
+impl Default for Skin {
+    fn default() -> Self {
+        Self::all().first().copied().expect("There are no skins in data/skins.txt")
+    }
+}
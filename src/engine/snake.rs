@@ -0,0 +1,328 @@
+use std::collections::VecDeque;
+use std::ops::Deref;
+
+use super::geometry::{Direction, Grid, Position};
+use super::event::DeathCause;
+use super::occupancy::Occupancy;
+use super::arena::ArenaMask;
+use super::skin::Skin;
+use super::power_up::{Effect, PowerUp};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Segment {
+    pub position: Position,
+    pub glyph: char
+}
+
+pub struct Snake {
+    body: VecDeque<Segment>,
+    occupancy: Occupancy, // The tiles under the body, kept in step with it so collisions are found without a search
+    direction: Direction,
+    grid: Grid, // Which ways the snake can move, and where a step leads
+    popped_tail: Option<Segment>, // The tail of the snake prior to a successful movement. Used for extending the snake after a fruit is obtained
+    requires_corner_update: bool, // For determining whether or not the glyphs of the corner segments of the snake need to be updated
+    skin: Skin,
+    effects: Vec<Effect>, // Power-ups in effect
+    stalled: bool, // Whether the last slither was stopped by a shield
+    pub alive: bool
+}
+
+impl Snake {
+    pub const STARTING_DIRECTIN: Direction = Direction::East;
+    pub const SAFE_RUNWAY: i32 = 5; // Free tiles required ahead of a respawning snake
+    pub const STARTING_LENGTH: usize = 5;
+
+    // A snake of starting length coiled up at `spawn_point`, with its head one tile along in `direction`
+    pub fn new(spawn_point: Position, direction: Direction, dimensions: (u32, u32), grid: Grid, skin: Skin) -> Self {
+        let glyph = skin.straight(direction);
+
+        let body_segment = Segment {
+            position: spawn_point,
+            glyph
+        };
+        let mut body = VecDeque::from(vec![body_segment; Self::STARTING_LENGTH - 1]);
+
+        let mut head = body_segment;
+        head.position = grid.step(spawn_point, direction);
+        body.push_front(head);
+
+        let mut occupancy = Occupancy::new(dimensions);
+
+        for segment in &body {
+            occupancy.add(segment.position);
+        }
+
+        Self {
+            body,
+            occupancy,
+            direction,
+            grid,
+            popped_tail: None,
+            requires_corner_update: false,
+            skin,
+            effects: Vec::new(),
+            stalled: false,
+            alive: true
+        }
+    }
+
+    // A snake laid out on `body`, head first, heading on the way its neck leads to its head. Used for
+    // puzzles, whose snakes start out in a shape of their own
+    pub fn from_body(body: &[Position], dimensions: (u32, u32), grid: Grid, skin: Skin) -> Self {
+        let direction = match body {
+            [head, neck, ..] => grid.direction_between(*neck, *head).unwrap_or(Self::STARTING_DIRECTIN),
+            _ => Self::STARTING_DIRECTIN
+        };
+        let mut occupancy = Occupancy::new(dimensions);
+
+        for position in body {
+            occupancy.add(*position);
+        }
+
+        let mut snake = Self {
+            body: body.iter().map(|&position| Segment {position, glyph: skin.straight(direction)}).collect(),
+            occupancy,
+            direction,
+            grid,
+            popped_tail: None,
+            requires_corner_update: false,
+            skin,
+            effects: Vec::new(),
+            stalled: false,
+            alive: true
+        };
+
+        snake.set_skin(skin); // Works out the corners
+
+        snake
+    }
+
+    // The segment left behind by the last slither, which the snake regains if it grows
+    pub fn popped_tail(&self) -> Option<Segment> {
+        self.popped_tail
+    }
+
+    // How many of the snake's segments lie on a tile
+    pub fn segments_at(&self, position: Position) -> u16 {
+        self.occupancy.count(position)
+    }
+
+    // The way the snake will move on its next slither
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: Direction) {
+        // Turning back on the neck is ignored
+        if self.grid.direction_between(self[0].position, self[1].position) != Some(direction) {
+            self.direction = direction;
+            self.requires_corner_update = true;
+        }
+    }
+
+    pub fn skin(&self) -> Skin {
+        self.skin
+    }
+
+    // Redraw the whole body in another skin, working out each segment's shape from its neighbours. Segments
+    // still coiled up under the one ahead of them take on its glyph
+    pub fn set_skin(&mut self, skin: Skin) {
+        let positions: Vec<Position> = self.body.iter().map(|segment| segment.position).collect();
+        let grid = self.grid;
+
+        for index in 0..positions.len() {
+            let neighbours = [index.checked_sub(1), Some(index + 1)];
+            let joins: Vec<Direction> = neighbours.iter()
+                .filter_map(|&neighbour| positions.get(neighbour?))
+                .filter_map(|&neighbour| grid.direction_between(positions[index], neighbour))
+                .collect();
+
+            self.body[index].glyph = match (joins.is_empty(), index) {
+                (false, _) => skin.joining(&joins),
+                (true, 0) => skin.straight(self.direction),
+                (true, _) => self.body[index - 1].glyph
+            };
+        }
+
+        if let Some(tail) = self.popped_tail.as_mut() {
+            tail.glyph = self.body.back().map_or(tail.glyph, |segment| segment.glyph);
+        }
+
+        self.skin = skin;
+    }
+
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    pub fn has_effect(&self, power_up: PowerUp) -> bool {
+        self.effects.iter().any(|effect| effect.power_up == power_up)
+    }
+
+    // Put an effect on the snake, starting afresh if it's already in effect
+    pub fn add_effect(&mut self, effect: Effect) {
+        self.remove_effect(effect.power_up);
+        self.effects.push(effect);
+    }
+
+    // Take an effect off the snake, returning whether it was in effect
+    pub fn remove_effect(&mut self, power_up: PowerUp) -> bool {
+        let count = self.effects.len();
+
+        self.effects.retain(|effect| effect.power_up != power_up);
+
+        self.effects.len() < count
+    }
+
+    // Count timed effects down by `seconds`, ending those that run out. A phase holds on past its time
+    // while the head is still inside the body, so it never wears off with the snake halfway through itself
+    pub fn age_effects(&mut self, seconds: f64) {
+        let inside_body = self.front().is_some_and(|head| self.occupancy.count(head.position) > 1);
+
+        for effect in &mut self.effects {
+            if let Some(seconds_left) = effect.seconds_left.as_mut() {
+                *seconds_left -= seconds;
+            }
+        }
+
+        self.effects.retain(|effect| {
+            effect.seconds_left.is_none_or(|seconds_left| seconds_left > 0.0) ||
+            (effect.power_up == PowerUp::Phase && inside_body)
+        });
+    }
+
+    // Whether the last slither was stopped short by a shield
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    // Whether moving the head onto `position` would run it into a wall or the body. The tail moves out of
+    // the way as the head moves, so its tile is safe unless the snake is coiled up on it
+    fn would_collide(&self, position: Position, arena: &ArenaMask) -> bool {
+        let tail_segments = if Some(position) == self.back().map(|segment| segment.position) { 1 } else { 0 };
+
+        !arena.is_open(position) || (!self.has_effect(PowerUp::Phase) && self.occupancy.count(position) > tail_segments)
+    }
+
+    // Whether the last slither ran the head into a wall or the body, which kills the snake on the next one
+    pub fn has_crashed(&self, arena: &ArenaMask) -> bool {
+        self.front().is_some_and(|head| {
+            !arena.is_open(head.position) || (self.occupancy.count(head.position) > 1 && !self.has_effect(PowerUp::Phase))
+        })
+    }
+
+    pub fn kill(&mut self) {
+        self.alive = false;
+    }
+
+    // Drop the whole body at once rather than letting it disappear a segment at a time, returning the
+    // tiles it lay on
+    pub fn shed(&mut self) -> Vec<Position> {
+        let tiles: Vec<Position> = self.body.drain(..).map(|segment| segment.position).collect();
+
+        for tile in &tiles {
+            self.occupancy.remove(*tile);
+        }
+
+        tiles
+    }
+
+    pub fn grow(&mut self) {
+        if let Some(tail) = self.popped_tail {
+            self.body.push_back(tail);
+            self.occupancy.add(tail.position);
+        }
+    }
+
+    pub fn update_corner_glyphs(&mut self) {
+        // Adjust glyphs of corners for when the snake turns
+        if self.len() > 2 {
+            let new_glyph = self[self.len() - 2].glyph;
+            let (head, grid) = (self[0], self.grid);
+            let tail = self.body.back_mut().unwrap();
+
+            // Straighten out tail if necessary
+            if self.skin.is_straight(new_glyph) && !self.skin.is_straight(tail.glyph) {
+                tail.glyph = new_glyph;
+            }
+
+            if self.requires_corner_update {
+                let neck_1 = self[2];
+                let neck_0 = self.body.get_mut(1).unwrap();
+
+                let joins: Vec<Direction> = [head.position, neck_1.position].iter()
+                    .filter_map(|&neighbour| grid.direction_between(neck_0.position, neighbour))
+                    .collect();
+
+                if !joins.is_empty() {
+                    neck_0.glyph = self.skin.joining(&joins);
+                }
+
+                self.requires_corner_update = false;
+            }
+        }
+    }
+
+    // Move one tile along, first checking whether the last move ran the snake into a wall or itself, and
+    // returning what killed it if so. A dead snake shrinks from the head instead, until it is gone
+    pub fn slither(&mut self, arena: &ArenaMask) -> Option<DeathCause> {
+        let mut death = None;
+
+        self.stalled = false;
+
+        if self.alive {
+            let head = self[0];
+
+            let out_of_bounds = !arena.is_open(head.position);
+            let self_collision = self.occupancy.count(head.position) > 1 && !self.has_effect(PowerUp::Phase); // The head accounts for one
+
+            if out_of_bounds {
+                death = Some(DeathCause::Wall);
+            } else if self_collision {
+                death = Some(DeathCause::Tail);
+            }
+
+            if death.is_some() {
+                self.kill();
+            }
+        }
+
+        if self.alive {
+            let mut head = self[0];
+
+            head.position = arena.wrap(self.grid.step(head.position, self.direction));
+            head.glyph = self.skin.straight(self.direction);
+
+            // A shield takes the blow of a fatal move, stopping the snake for a slither in its place
+            if self.would_collide(head.position, arena) && self.remove_effect(PowerUp::Shield) {
+                self.popped_tail = None;
+                self.stalled = true;
+
+                return None;
+            }
+
+            self.popped_tail = self.body.pop_back();
+            self.body.push_front(head);
+
+            if let Some(tail) = self.popped_tail {
+                self.occupancy.remove(tail.position);
+            }
+
+            self.occupancy.add(head.position);
+
+            self.update_corner_glyphs();
+        } else if let Some(head) = self.body.pop_front() {
+            self.occupancy.remove(head.position);
+        }
+
+        death
+    }
+}
+
+impl Deref for Snake {
+    type Target = VecDeque<Segment>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.body
+    }
+}
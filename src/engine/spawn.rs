@@ -0,0 +1,147 @@
+use super::{Direction, Position};
+
+// Where the snakes' heads are as a fruit spawns, for policies that weigh tiles by how far they are from them
+pub struct SpawnContext<'a> {
+    pub snake: usize, // The snake the fruit is for, where it comes in `Engine::snakes`
+    pub heads: &'a [Option<Position>] // Each snake's head, in the same order, or `None` once it's dead
+}
+
+impl SpawnContext<'_> {
+    // How far the head of the snake the fruit is for is from `tile`
+    pub fn own_distance(&self, tile: Position) -> Option<i32> {
+        self.heads.get(self.snake).copied().flatten().map(|head| head.manhattan_distance(tile))
+    }
+
+    // How far the nearest head of any other snake still alive is from `tile`
+    pub fn rival_distance(&self, tile: Position) -> Option<i32> {
+        self.heads.iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.snake)
+            .filter_map(|(_, head)| head.map(|head| head.manhattan_distance(tile)))
+            .min()
+    }
+}
+
+// How fruit is placed among the free tiles of the map, on top of any weighting the level or mode gives
+// them. Modes pick the policy they're played with through `GameMode::spawn_policy`
+pub trait SpawnPolicy {
+    fn name(&self) -> &str;
+
+    // Whether every free tile is as likely as any other, so the engine can skip weighing them up
+    fn is_uniform(&self) -> bool {
+        false
+    }
+
+    // Relative chance of the fruit spawning on `tile`
+    fn weight(&self, tile: Position, context: &SpawnContext) -> f64;
+
+    // The other tiles fruit spawns on along with a fruit at `anchor`, which the snake has to clear before
+    // the next fruit spawns. Tiles that aren't free are left out
+    fn cluster(&self, _anchor: Position) -> Vec<Position> {
+        Vec::new()
+    }
+}
+
+// The built-in policy of the given name, for mods to pick one by
+pub fn named(name: &str) -> Option<Box<dyn SpawnPolicy>> {
+    let policies: Vec<Box<dyn SpawnPolicy>> = vec![Box::new(Uniform), Box::new(Fair), Box::new(Clusters), Box::new(Lines)];
+
+    policies.into_iter().find(|policy| policy.name().eq_ignore_ascii_case(name))
+}
+
+// Every free tile alike, as fruit has always spawned
+pub struct Uniform;
+
+impl SpawnPolicy for Uniform {
+    fn name(&self) -> &str {
+        "Uniform"
+    }
+
+    fn is_uniform(&self) -> bool {
+        true
+    }
+
+    fn weight(&self, _tile: Position, _context: &SpawnContext) -> f64 {
+        1.0
+    }
+}
+
+// Keeps fruit from landing right under the snake's nose, and in matches against rivals from landing much
+// nearer a rival's head than the head of the snake it's for
+pub struct Fair;
+
+impl Fair {
+    pub const NEAR: i32 = 5; // Tiles closer to the head than this are less likely, the closer the less so
+    pub const FLOOR: f64 = 0.05; // The least likely a tile gets, so the fruit can always go somewhere
+}
+
+impl SpawnPolicy for Fair {
+    fn name(&self) -> &str {
+        "Fair"
+    }
+
+    fn weight(&self, tile: Position, context: &SpawnContext) -> f64 {
+        let own = match context.own_distance(tile) {
+            Some(distance) => distance,
+            None => return 1.0
+        };
+        let mut weight = (own as f64 / Self::NEAR as f64).min(1.0);
+
+        // Every few tiles of head start a rival has on the fruit halve its chances again
+        if let Some(rival) = context.rival_distance(tile).filter(|rival| *rival < own) {
+            weight /= 2f64.powi(((own - rival) as f64 / Self::NEAR as f64).ceil() as i32);
+        }
+
+        weight.max(Self::FLOOR)
+    }
+}
+
+// Fruit in small clumps, the fruit spawning along with the tiles around it
+pub struct Clusters;
+
+impl SpawnPolicy for Clusters {
+    fn name(&self) -> &str {
+        "Clusters"
+    }
+
+    fn is_uniform(&self) -> bool {
+        true
+    }
+
+    fn weight(&self, _tile: Position, _context: &SpawnContext) -> f64 {
+        1.0
+    }
+
+    fn cluster(&self, anchor: Position) -> Vec<Position> {
+        [Position::new(1, 0), Position::new(0, 1), Position::new(1, 1)].iter()
+            .map(|offset| anchor + *offset)
+            .collect()
+    }
+}
+
+// Fruit in short lines, across or down the map depending on where the first fruit lands
+pub struct Lines;
+
+impl Lines {
+    pub const LENGTH: i32 = 4;
+}
+
+impl SpawnPolicy for Lines {
+    fn name(&self) -> &str {
+        "Lines"
+    }
+
+    fn is_uniform(&self) -> bool {
+        true
+    }
+
+    fn weight(&self, _tile: Position, _context: &SpawnContext) -> f64 {
+        1.0
+    }
+
+    fn cluster(&self, anchor: Position) -> Vec<Position> {
+        let step = Position::from(if (anchor.x + anchor.y) % 2 == 0 { Direction::East } else { Direction::South });
+
+        (1..Self::LENGTH).map(|distance| anchor + step * distance).collect()
+    }
+}
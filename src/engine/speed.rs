@@ -0,0 +1,176 @@
+use super::snake::Snake;
+
+// How the pace picks up as the snake eats, as a multiple of the mode's slithers per second. It starts at
+// `base` and goes up by `increment` for every `every` fruit eaten, until it reaches `maximum`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SpeedCurve {
+    pub base: f64,
+    pub increment: f64,
+    pub every: usize, // Fruit eaten between one step up and the next
+    pub maximum: f64
+}
+
+impl SpeedCurve {
+    // The same pace from start to finish
+    pub const CONSTANT: SpeedCurve = SpeedCurve {
+        base: 1.0,
+        increment: 0.0,
+        every: 1,
+        maximum: 1.0
+    };
+
+    // A tenth faster every 5 fruit, up to twice the starting pace
+    pub const STEPWISE: SpeedCurve = SpeedCurve {
+        base: 1.0,
+        increment: 0.1,
+        every: 5,
+        maximum: 2.0
+    };
+
+    pub const PRESETS: [(&'static str, SpeedCurve); 2] = [("Constant", Self::CONSTANT), ("Stepwise", Self::STEPWISE)];
+
+    // The preset going by `name`, ignoring case
+    pub fn named(name: &str) -> Option<Self> {
+        Self::PRESETS.iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|&(_, curve)| curve)
+    }
+
+    // How many times the mode's pace the snake moves at, having eaten `fruits_eaten` fruit
+    pub fn factor(&self, fruits_eaten: usize) -> f64 {
+        let steps = fruits_eaten / self.every.max(1);
+
+        (self.base + self.increment * steps as f64).min(self.maximum)
+    }
+}
+
+impl Default for SpeedCurve {
+    fn default() -> Self {
+        Self::CONSTANT
+    }
+}
+
+// How the pace picks up as the snake grows, on top of its speed curve, so the longer the snake the less
+// time there is to steer it. The pace goes up by `per_segment` for every segment past `from`, until it
+// reaches `maximum` times the pace of a short snake
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LengthSpeed {
+    pub per_segment: f64,
+    pub from: usize, // The length the snake starts speeding up from
+    pub maximum: f64
+}
+
+impl LengthSpeed {
+    // The same pace whatever the snake's length
+    pub const OFF: LengthSpeed = LengthSpeed {
+        per_segment: 0.0,
+        from: Snake::STARTING_LENGTH,
+        maximum: 1.0
+    };
+
+    // Twice the pace by 55 segments
+    pub const GENTLE: LengthSpeed = LengthSpeed {
+        per_segment: 0.02,
+        from: Snake::STARTING_LENGTH,
+        maximum: 2.0
+    };
+
+    // Twice the pace by 25 segments, and three times by 45
+    pub const STEEP: LengthSpeed = LengthSpeed {
+        per_segment: 0.05,
+        from: Snake::STARTING_LENGTH,
+        maximum: 3.0
+    };
+
+    pub const PRESETS: [(&'static str, LengthSpeed); 3] = [("Off", Self::OFF), ("Gentle", Self::GENTLE), ("Steep", Self::STEEP)];
+
+    // The preset going by `name`, ignoring case
+    pub fn named(name: &str) -> Option<Self> {
+        Self::PRESETS.iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|&(_, speed)| speed)
+    }
+
+    // The name of the preset this is, or "Custom" for one of its own
+    pub fn name(self) -> &'static str {
+        Self::PRESETS.iter()
+            .find(|(_, speed)| *speed == self)
+            .map_or("Custom", |&(name, _)| name)
+    }
+
+    // The next preset along, for menus. Custom ones go back to the first
+    pub fn next(self) -> Self {
+        let index = Self::PRESETS.iter().position(|(_, speed)| *speed == self);
+
+        index.and_then(|index| Self::PRESETS.get(index + 1)).unwrap_or(&Self::PRESETS[0]).1
+    }
+
+    // The preset before, for menus. Custom ones go back to the last
+    pub fn previous(self) -> Self {
+        let index = Self::PRESETS.iter().position(|(_, speed)| *speed == self);
+
+        match index {
+            Some(index) if index > 0 => Self::PRESETS[index - 1].1,
+            _ => Self::PRESETS[Self::PRESETS.len() - 1].1
+        }
+    }
+
+    pub fn is_off(self) -> bool {
+        self.per_segment <= 0.0 || self.maximum <= 1.0
+    }
+
+    // How many times the pace of a short snake a snake of `length` moves at
+    pub fn factor(self, length: usize) -> f64 {
+        (1.0 + self.per_segment * length.saturating_sub(self.from) as f64).clamp(1.0, self.maximum.max(1.0))
+    }
+}
+
+impl Default for LengthSpeed {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+// Turns real time into slithers on a fixed timestep, so the snake keeps the same pace whatever the frame
+// rate: each frame banks the time it took, and the snake slithers once for every slither's worth banked.
+// Long frames are caught up on in full, up to a limit past which the game is taken to have stalled (such
+// as while the window is dragged) and the rest is let go rather than rushed through
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Timestep {
+    banked: f64 // Real seconds owed to the simulation that haven't been slithered yet
+}
+
+impl Timestep {
+    pub const MAX_CATCH_UP_SECONDS: f64 = 0.25; // The most owed past a single slither
+
+    // Bank a frame of `elapsed` seconds, at slithers `tick_length` seconds apart. Returns how many seconds
+    // were let go past the catch-up limit
+    pub fn bank(&mut self, elapsed: f64, tick_length: f64) -> f64 {
+        let limit = tick_length + Self::MAX_CATCH_UP_SECONDS;
+        let owed = self.banked + elapsed.max(0.0);
+
+        self.banked = owed.min(limit);
+
+        owed - self.banked
+    }
+
+    // Take a slither's worth of time, if that much is banked
+    pub fn take(&mut self, tick_length: f64) -> bool {
+        let ready = self.banked >= tick_length;
+
+        if ready {
+            self.banked -= tick_length;
+        }
+
+        ready
+    }
+
+    // Keep only a slither's worth banked, for a snake held still until it's steered to move the moment it is
+    pub fn hold(&mut self, tick_length: f64) {
+        self.banked = self.banked.min(tick_length);
+    }
+
+    pub fn reset(&mut self) {
+        self.banked = 0.0;
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use super::Engine;
+use super::geometry::Position;
+use super::schedule::ScriptError;
+
+// Free-form key-value tags attached to map cells, letting levels and mods give cells a meaning of their
+// own without any change to the level format. Tags without a value are stored with an empty one. The
+// tags the game itself understands are:
+//   no_fruit               fruit never spawns on the cell
+//   spawn_weight=<number>  how likely fruit is to spawn on the cell, relative to the default of 1
+//   decoration=<glyph>     drawn on the cell, beneath everything else
+//   spawn                  a snake starts out on the cell, in reading order, in place of the middle column
+#[derive(Clone, Default, Debug)]
+pub struct CellTags {
+    cells: HashMap<Position, HashMap<String, String>>
+}
+
+impl CellTags {
+    pub const NO_FRUIT: &'static str = "no_fruit";
+    pub const SPAWN_WEIGHT: &'static str = "spawn_weight";
+    pub const DECORATION: &'static str = "decoration";
+    pub const SPAWN: &'static str = "spawn";
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, position: Position, key: &str) -> Option<&str> {
+        self.cells.get(&position)?.get(key).map(String::as_str)
+    }
+
+    pub fn has(&self, position: Position, key: &str) -> bool {
+        self.get(position, key).is_some()
+    }
+
+    pub fn set(&mut self, position: Position, key: &str, value: &str) {
+        self.cells.entry(position).or_default().insert(key.to_string(), value.to_string());
+    }
+
+    pub fn remove(&mut self, position: Position, key: &str) -> Option<String> {
+        let tags = self.cells.get_mut(&position)?;
+        let value = tags.remove(key);
+
+        if tags.is_empty() {
+            self.cells.remove(&position);
+        }
+
+        value
+    }
+
+    // Every cell carrying `key`, alongside the tag's value
+    pub fn tagged<'a>(&'a self, key: &'a str) -> impl Iterator<Item = (Position, &'a str)> + 'a {
+        self.cells.iter().filter_map(move |(position, tags)| tags.get(key).map(|value| (*position, value.as_str())))
+    }
+
+    // How likely fruit is to spawn at `position`, where 0 means never
+    pub fn spawn_weight(&self, position: Position) -> f64 {
+        if self.has(position, Self::NO_FRUIT) {
+            0.0
+        } else {
+            self.get(position, Self::SPAWN_WEIGHT)
+                .and_then(|weight| weight.parse::<f64>().ok())
+                .map_or(1.0, |weight| weight.max(0.0))
+        }
+    }
+
+    // The cells snakes start out on, in reading order
+    pub fn spawn_points(&self) -> Vec<Position> {
+        let mut points: Vec<Position> = self.tagged(Self::SPAWN).map(|(position, _)| position).collect();
+
+        points.sort_by_key(|position| (position.y, position.x));
+        points
+    }
+
+    // Whether any cell has a say in where fruit spawns
+    pub fn affects_spawning(&self) -> bool {
+        self.tagged(Self::NO_FRUIT).next().is_some() || self.tagged(Self::SPAWN_WEIGHT).next().is_some()
+    }
+
+    // Parse a level script line of the form "tag <x> <y> <key>" or "tag <x> <y> <key>=<value>"
+    pub fn parse_line(&mut self, line_number: usize, line: &str) -> Result<(), ScriptError> {
+        let error = |message: String| ScriptError {
+            line: line_number,
+            message
+        };
+        let coordinate = |word: &str, limit: u32| word.parse::<i32>()
+            .ok()
+            .filter(|coordinate| *coordinate >= 0 && *coordinate < limit as i32)
+            .ok_or_else(|| error(format!("coordinate '{}' is outside the map", word)));
+
+        let (width, height) = Engine::MAP_DIMENSIONS;
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["tag", x, y, tag] => {
+                let position = Position::new(coordinate(x, width)?, coordinate(y, height)?);
+                let (key, value) = tag.split_once('=').unwrap_or((tag, ""));
+
+                if key.is_empty() {
+                    return Err(error(format!("tag '{}' has no name", tag)));
+                }
+
+                self.set(position, key, value);
+
+                Ok(())
+            },
+            _ => Err(error("expected 'tag <x> <y> <key>' or 'tag <x> <y> <key>=<value>'".to_string()))
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::Status;
+use super::mode::RunState;
+
+// What decides a match between rival snakes. Whichever way it's played, a player whose snake dies has
+// lost, while rivals that die are out of the match but keep their scores
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum WinCondition {
+    FirstTo(usize), // The first snake to eat this many fruit wins
+    LastAlive, // The last snake left alive wins
+    HighestAtTimeout(f64) // The snake with the most points after this many seconds wins, a tie losing
+}
+
+impl WinCondition {
+    pub const FRUIT_TARGET: usize = 10;
+    pub const TIME_LIMIT: f64 = 120.0;
+
+    pub const ALL: [WinCondition; 3] = [
+        WinCondition::FirstTo(Self::FRUIT_TARGET),
+        WinCondition::LastAlive,
+        WinCondition::HighestAtTimeout(Self::TIME_LIMIT)
+    ];
+
+    pub fn describe(self) -> String {
+        match self {
+            Self::FirstTo(fruits) => format!("First to {}", fruits),
+            Self::LastAlive => "Last alive".to_string(),
+            Self::HighestAtTimeout(seconds) => format!("Top at {}s", seconds)
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|condition| *condition == self).unwrap_or(0);
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    // How the match stands, the player's side first, for the HUD
+    pub fn progress(self, run: &RunState) -> String {
+        match self {
+            Self::FirstTo(fruits) => format!("First to {}: {} v {}", fruits, run.fruits_eaten, run.rival_fruits),
+            Self::LastAlive => format!("Last alive: {} v {}", run.player_alive as usize, run.rivals_alive),
+            Self::HighestAtTimeout(seconds) => format!("Top score: {} v {} {}s", run.points, run.rival_points, (seconds - run.time).max(0.0).ceil())
+        }
+    }
+
+    // Whether the player has won or lost the match, or it's still going
+    pub fn status(self, run: &RunState) -> Status {
+        if !run.player_alive {
+            return Status::Lost;
+        }
+
+        match self {
+            Self::FirstTo(fruits) if run.fruits_eaten >= fruits => Status::Won,
+            Self::FirstTo(fruits) if run.rival_fruits >= fruits => Status::Lost,
+            Self::FirstTo(_) | Self::LastAlive if run.rivals_alive == 0 => Status::Won,
+            Self::HighestAtTimeout(seconds) if run.time >= seconds => if run.points > run.rival_points {
+                Status::Won
+            } else {
+                Status::Lost
+            },
+            _ => Status::Playing
+        }
+    }
+}
+
+impl Default for WinCondition {
+    fn default() -> Self {
+        Self::FirstTo(Self::FRUIT_TARGET)
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::frame::{Font, Frame};
+use crate::recording::Recording;
+
+pub const SCREENSHOT_SCALE: u32 = 3; // Image pixels per font pixel
+
+// Save a captured frame as a PNG where the player can find it, returning its file name
+pub fn screenshot(frame: &Frame) -> Result<String, String> {
+    let png = frame.rasterise(&Font::load()?, SCREENSHOT_SCALE).to_png()?;
+
+    save("png", "image/png", &png)
+}
+
+// Save a recording as an animated GIF, returning its file name
+pub fn recording(recording: &Recording) -> Result<String, String> {
+    save("gif", "image/gif", &recording.to_gif()?)
+}
+
+pub fn replay(replay: &str) -> Result<String, String> {
+    save("replay", "text/plain", replay.as_bytes())
+}
+
+// Save a level made in the editor, which can be played with `--level`
+pub fn level(level: &str) -> Result<String, String> {
+    save("lvl", "text/plain", level.as_bytes())
+}
+
+// The name of the newest file saved with `extension` in the working directory, if there is one
+#[cfg(not(target_arch = "wasm32"))]
+pub fn latest(extension: &str) -> Option<String> {
+    let suffix = format!(".{}", extension);
+
+    fs::read_dir(".").ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("snake-") && name.ends_with(&suffix))
+        .max_by_key(|name| name["snake-".len()..name.len() - suffix.len()].parse::<u128>().unwrap_or(0))
+}
+
+// Files are named for when they were saved, so nothing is overwritten
+fn save(extension: &str, mime_type: &str, bytes: &[u8]) -> Result<String, String> {
+    let name = format!("snake-{}.{}", milliseconds_since_epoch(), extension);
+
+    write(&name, mime_type, bytes)?;
+
+    Ok(name)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn milliseconds_since_epoch() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_millis())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn milliseconds_since_epoch() -> u128 {
+    js_sys::Date::now() as u128
+}
+
+// Into the working directory
+#[cfg(not(target_arch = "wasm32"))]
+fn write(name: &str, _mime_type: &str, bytes: &[u8]) -> Result<(), String> {
+    fs::write(name, bytes).map_err(|error| error.to_string())
+}
+
+// Offered to the browser as a download
+#[cfg(target_arch = "wasm32")]
+fn write(name: &str, mime_type: &str, bytes: &[u8]) -> Result<(), String> {
+    crate::web::download(name, bytes, mime_type)
+}
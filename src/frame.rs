@@ -0,0 +1,223 @@
+use bracket_terminal::prelude::{BACKEND_INTERNAL, EMBED};
+
+// A picture of the screen as a grid of tiles, independent of the backend drawing it, for turning
+// into images
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Frame {
+    width: u32,
+    height: u32,
+    tiles: Vec<Tile>
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Tile {
+    pub glyph: u8, // Code page 437
+    pub foreground: [u8; 3],
+    pub background: [u8; 3]
+}
+
+// A block of tiles, as its top left corner and size
+pub type Area = ((u32, u32), (u32, u32));
+
+// The 8x8 code page 437 font the game is drawn in, as one bit per pixel
+pub struct Font {
+    pixels: Vec<bool> // The whole 16x16 glyph sheet, in row-major order
+}
+
+// An RGB image, three bytes per pixel in row-major order
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>
+}
+
+impl Frame {
+    // What's on screen right now, with the HUD layer laid over the map layer
+    pub fn capture() -> Self {
+        let internal = BACKEND_INTERNAL.lock();
+        let mut layers = internal.consoles.iter().map(|display| display.console.to_xp_layer());
+
+        let base = match layers.next() {
+            Some(layer) => layer,
+            None => return Self {width: 0, height: 0, tiles: Vec::new()}
+        };
+
+        let (width, height) = (base.width, base.height);
+
+        // XP layers are stored column by column, so they're read back in row-major order
+        let mut tiles: Vec<Tile> = (0..width * height)
+            .filter_map(|index| base.get(index % width, index / width))
+            .map(|cell| Tile {
+                glyph: cell.ch as u8,
+                foreground: [cell.fg.r, cell.fg.g, cell.fg.b],
+                background: [cell.bg.r, cell.bg.g, cell.bg.b]
+            })
+            .collect();
+
+        // Layers above the map have no background of their own, so only their glyphs are laid over it
+        for layer in layers {
+            for (index, tile) in tiles.iter_mut().enumerate() {
+                match layer.get(index % width, index / width) {
+                    Some(cell) if cell.ch != 0 && cell.ch != u32::from(b' ') => {
+                        tile.glyph = cell.ch as u8;
+                        tile.foreground = [cell.fg.r, cell.fg.g, cell.fg.b];
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Self {
+            width: width as u32,
+            height: height as u32,
+            tiles
+        }
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    // Every colour used in the frame, repeats and all
+    pub fn colours(&self) -> impl Iterator<Item = [u8; 3]> + '_ {
+        self.tiles.iter().flat_map(|tile| std::iter::once(tile.foreground).chain(std::iter::once(tile.background)))
+    }
+
+    // The smallest block of tiles covering every one that differs from `previous`, or `None` if nothing
+    // has changed
+    pub fn changes_since(&self, previous: &Frame) -> Option<Area> {
+        if self.dimensions() != previous.dimensions() {
+            return Some(((0, 0), self.dimensions()));
+        }
+
+        let changed = self.tiles.iter()
+            .zip(previous.tiles.iter())
+            .enumerate()
+            .filter(|(_, (tile, previous))| tile != previous)
+            .map(|(index, _)| (index as u32 % self.width, index as u32 / self.width));
+
+        let (mut left, mut top, mut right, mut bottom) = (u32::MAX, u32::MAX, 0, 0);
+
+        for (x, y) in changed {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
+        }
+
+        if left > right {
+            None
+        } else {
+            Some(((left, top), (right - left + 1, bottom - top + 1)))
+        }
+    }
+
+    // Draw the frame with `font`, blowing each font pixel up into a `scale` x `scale` square
+    pub fn rasterise(&self, font: &Font, scale: u32) -> Image {
+        let tile_size = Font::GLYPH_SIZE * scale;
+        let (width, height) = (self.width * tile_size, self.height * tile_size);
+        let mut pixels = vec![0; (width * height * 3) as usize];
+
+        for (index, tile) in self.tiles.iter().enumerate() {
+            let (column, row) = (index as u32 % self.width, index as u32 / self.width);
+
+            for y in 0..tile_size {
+                for x in 0..tile_size {
+                    let colour = if font.is_lit(tile.glyph, x / scale, y / scale) {
+                        tile.foreground
+                    } else {
+                        tile.background
+                    };
+                    let pixel = (((row * tile_size + y) * width + column * tile_size + x) * 3) as usize;
+
+                    pixels[pixel..pixel + 3].copy_from_slice(&colour);
+                }
+            }
+        }
+
+        Image {
+            width,
+            height,
+            pixels
+        }
+    }
+}
+
+impl Font {
+    pub const GLYPH_SIZE: u32 = 8;
+    const SHEET_SIZE: u32 = Self::GLYPH_SIZE * 16;
+
+    // Decode the font bracket-terminal renders with, from its embedded copy
+    pub fn load() -> Result<Self, String> {
+        let bytes = EMBED.lock()
+            .get_resource("resources/terminal8x8.png".to_string())
+            .ok_or_else(|| String::from("The font isn't embedded"))?;
+
+        let mut decoder = png::Decoder::new(bytes);
+        decoder.set_transformations(png::Transformations::EXPAND);
+
+        let (info, mut reader) = decoder.read_info().map_err(|error| error.to_string())?;
+        let mut buffer = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buffer).map_err(|error| error.to_string())?;
+
+        let channels = match info.color_type {
+            png::ColorType::RGBA => 4,
+            png::ColorType::RGB => 3,
+            png::ColorType::GrayscaleAlpha => 2,
+            _ => 1
+        };
+
+        if info.width != Self::SHEET_SIZE || info.height != Self::SHEET_SIZE {
+            return Err(format!("Expected a {0}x{0} font sheet", Self::SHEET_SIZE));
+        }
+
+        // A pixel is part of a glyph if it's bright and, where there's an alpha channel, opaque
+        let pixels = buffer.chunks(channels)
+            .map(|pixel| pixel[0] > 127 && (channels % 2 == 1 || pixel[channels - 1] > 127))
+            .collect();
+
+        Ok(Self {
+            pixels
+        })
+    }
+
+    fn is_lit(&self, glyph: u8, x: u32, y: u32) -> bool {
+        let (column, row) = (glyph as u32 % 16, glyph as u32 / 16);
+        let pixel = (row * Self::GLYPH_SIZE + y) * Self::SHEET_SIZE + column * Self::GLYPH_SIZE + x;
+
+        self.pixels.get(pixel as usize).copied().unwrap_or(false)
+    }
+}
+
+impl Image {
+    // The `size` block of the image with its top left corner at `origin`
+    pub fn crop(&self, origin: (u32, u32), size: (u32, u32)) -> Image {
+        let mut pixels = Vec::with_capacity((size.0 * size.1 * 3) as usize);
+
+        for y in origin.1..origin.1 + size.1 {
+            let start = ((y * self.width + origin.0) * 3) as usize;
+
+            pixels.extend_from_slice(&self.pixels[start..start + (size.0 * 3) as usize]);
+        }
+
+        Image {
+            width: size.0,
+            height: size.1,
+            pixels
+        }
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width, self.height);
+
+        encoder.set_color(png::ColorType::RGB);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder.write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(|error| error.to_string())?;
+
+        Ok(bytes)
+    }
+}
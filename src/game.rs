@@ -1,280 +1,1991 @@
-use std::convert::TryInto;
-
-use bracket_terminal::prelude::{
-    BTerm,
-    BTermBuilder,
-    BError,
-    VirtualKeyCode,
-    GameState,
-    Point,
-    RGB
-};
-
-use rand;
-use rand::seq::SliceRandom;
-use rand::rngs::ThreadRng;
-
-use web_sys::Performance;
-
-use crate::object::{Object, Obj};
-use crate::snake::Snake;
-use crate::direction::Direction;
-
-#[cfg(not(target_arch = "wasm32"))]
-use bracket_terminal::prelude::{INPUT, BEvent};
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::Instant;
-
-#[cfg(not(target_arch = "wasm32"))]
-pub type Timestamp = Instant;
-#[cfg(target_arch = "wasm32")]
-pub type Timestamp = f64;
-
-
-pub struct Game {
-    #[cfg(target_arch = "wasm32")]
-    time: web_sys::Performance,
-    rng: ThreadRng,
-    snake: Snake,
-    fruit: Object,
-    score: usize,
-    game_over: bool,
-    paused: bool,
-    previous_snake_update_time: Timestamp,
-}
-
-impl Game {
-    pub const TITLE: &'static str = "Snake";
-
-    pub const FRUIT_GLYPH: char = '*';
-    pub const FRUIT_COLOUR: RGB = RGB {r: 1.0, g: 0.5, b: 0.5};
-    pub const BACKGROUND_COLOUR: RGB = RGB {r: 0.175, g: 0.2, b: 0.225};
-
-    pub const TILE_DIMENSIONS: (u32, u32) = (25, 25);
-    pub const MAP_DIMENSIONS: (u32, u32) = (25, 25);
-
-    pub const MAP_CENTRE: (u32, u32) = (
-        Self::MAP_DIMENSIONS.0 / 2,
-        Self::MAP_DIMENSIONS.1 / 2
-    );
-
-    pub const FRAMES_PER_SECOND: f32 = 60.0;
-    pub const SLITHERS_PER_SECOND: u32 = 15;
-
-    pub fn new() -> Self {
-        // Attributes for the WASM version of the game
-        let time: Performance;
-        let previous_snake_update_time: f64;
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            time = web_sys::window().unwrap().performance().unwrap();
-            previous_snake_update_time = time.now();
-        }
-
-        let mut game = Self {
-            rng: rand::thread_rng(),
-            snake: Snake::default(),
-            fruit: Object::new((-1, -1).into(), Self::FRUIT_GLYPH, Self::FRUIT_COLOUR), // Initally positioned outside of map
-            #[cfg(not(target_arch = "wasm32"))]
-            previous_snake_update_time: Instant::now(),
-            #[cfg(target_arch = "wasm32")]
-            time,
-            #[cfg(target_arch = "wasm32")]
-            previous_snake_update_time,
-            score: 0,
-            game_over: false,
-            paused: false
-        };
-
-        game.spawn_fruit();
-
-        game
-    }
-
-    pub fn run(self) -> BError {
-        // Build application
-        let mut ctx = BTermBuilder::simple(Self::MAP_DIMENSIONS.0, Self::MAP_DIMENSIONS.1).expect("Failed to construct applciation builder")
-            .with_title(Self::TITLE)
-            .with_tile_dimensions(Self::TILE_DIMENSIONS.0, Self::TILE_DIMENSIONS.1)
-            .with_fps_cap(Self::FRAMES_PER_SECOND)
-            .with_advanced_input(true)
-            .build().expect("Failed to build application context");
-
-        ctx.with_post_scanlines(true);
-
-        // Run game loop
-        bracket_terminal::prelude::main_loop(ctx, self)
-    }
-
-    pub fn reset(&mut self) {
-        self.snake = Snake::default();
-        self.spawn_fruit();
-        #[cfg(target_arch = "wasm32")]
-        {
-            self.previous_snake_update_time = self.time.now();
-        }
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            self.previous_snake_update_time = Instant::now();
-        }
-        self.score = 0;
-        self.game_over = false;
-    }
-    
-    fn spawn_fruit(&mut self) {
-        let spawn_locations = self.get_empty_points();
-
-        self.fruit.position = *spawn_locations.choose(&mut self.rng).expect("Failed to spawn fruit");
-    }
-
-    fn update_snake(&mut self) {
-        let update_delta;
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            update_delta = (self.time.now() - self.previous_snake_update_time) / 1000.0;
-        }
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            update_delta = self.previous_snake_update_time.elapsed().as_secs_f64();
-        }
-
-        if (!self.snake.alive || !self.game_over) && update_delta > 1.0 / Self::SLITHERS_PER_SECOND as f64 {
-            self.snake.update();
-
-            #[cfg(target_arch = "wasm32")]
-            {
-                self.previous_snake_update_time = self.time.now();
-            }
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                self.previous_snake_update_time = Instant::now();
-            }
-        }
-    }
-
-    fn get_empty_points(&self) -> Vec<Point> {
-        let mut empty_points = Vec::<Point>::default();
-        let mut snake_segment_points = self.snake.iter().map(|cell| cell.position);
-        
-        for y in 0..Self::MAP_DIMENSIONS.1 {
-            for x in 0..Self::MAP_DIMENSIONS.0 {
-                let point = Into::<Point>::into((x as f32, y as f32));
-
-                if !snake_segment_points.any(|p| p == point) && self.fruit.position != point {
-                    empty_points.push(point)
-                }
-            }
-        }
-
-        empty_points
-    }
-
-    fn execute_input(&mut self, key_code: VirtualKeyCode) {
-        if !self.game_over {
-            match key_code {
-                VirtualKeyCode::W | VirtualKeyCode::A |
-                VirtualKeyCode::S | VirtualKeyCode::D |
-                VirtualKeyCode::Up | VirtualKeyCode::Down |
-                VirtualKeyCode::Left | VirtualKeyCode::Right => if self.snake.alive && !self.paused {
-                    self.snake.set_direction(
-                        TryInto::<Direction>::try_into(key_code).unwrap() // Change snake direction
-                    )
-                },
-                VirtualKeyCode::Escape | VirtualKeyCode::P => {
-                    self.paused = !self.paused
-                }
-                _ => {}
-            }
-        } else if key_code == VirtualKeyCode::R {
-            self.reset();
-        }
-    }
-
-    fn handle_input(&mut self, ctx: &mut BTerm) {
-        #[cfg(target_arch = "wasm32")]
-        {
-            match ctx.key {
-                Some(key_code) => self.execute_input(key_code),
-                None => {}
-            }
-        }
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            INPUT.lock().for_each_message(|event| {
-                match event {
-                    BEvent::KeyboardInput {key, pressed: true, ..} => self.execute_input(key),
-                    BEvent::CloseRequested => ctx.quit(),
-                    _ => { }
-                }
-            });
-        }
-    }
-
-    fn handle_logic(&mut self) {
-        // Check and store the status of the game
-        if !self.game_over {
-            let won = self.snake.len() as u32 == Self::MAP_DIMENSIONS.0 * Self::MAP_DIMENSIONS.1;
-            let lost = !self.snake.alive;
-
-            self.game_over = won || lost;
-        }
-
-        // If the game is not over, check if the snake collides with the fruit
-        if !self.game_over {
-            let snake_head = self.snake[0];
-
-            // If the snake collides with the fruit, grow the snake and respawn the fruit
-            if snake_head.position == self.fruit.position {
-                self.score += 1;
-                self.snake.grow();
-                self.spawn_fruit(); // Must respawn the fruit after the snake grows
-            }
-        }
-
-        // Update the snake (Slither and update its corner tiles)
-        self.update_snake();
-    }
-
-    fn handle_rendering(&mut self, ctx: &mut BTerm) {
-        ctx.cls_bg(Self::BACKGROUND_COLOUR);
-
-        if self.paused {
-            ctx.print_color_centered_at(Self::MAP_CENTRE.0, Self::MAP_CENTRE.1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "PAUSED".to_string());
-        } else {
-            self.snake.render(ctx);
-
-            // If the game is over, print end-game information
-            if self.game_over {
-                ctx.print_color_centered_at(Self::MAP_CENTRE.0, Self::MAP_CENTRE.1 - 3, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "GAME OVER".to_string());
-                ctx.print_color_centered_at(Self::MAP_CENTRE.0, Self::MAP_CENTRE.1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, if self.snake.alive {
-                    "You won!".to_string()
-                } else {
-                    format!("Score: {}", self.score)
-                });
-                ctx.print_color_centered_at(Self::MAP_CENTRE.0, Self::MAP_CENTRE.1 + 3, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "[R] Restart");
-            } else { // If the game is not over, continue rendering the fruit
-                self.fruit.render(ctx);
-            }
-        }
-    }
-}
-
-impl GameState for Game {
-    fn tick(&mut self, ctx: &mut BTerm) {
-        self.handle_input(ctx);
-
-        if !self.paused {
-            self.handle_logic();
-        }
-
-        self.handle_rendering(ctx);
-    }
-}
-
-impl Default for Game {
-    fn default() -> Self {
-        Self::new()
-    }
+use bracket_terminal::prelude::{
+    BTerm,
+    BTermBuilder,
+    BError,
+    VirtualKeyCode,
+    GameState,
+    RGB
+};
+
+use rand;
+use rand::Rng;
+
+use crate::object::{Animation, Animator, Layer, Object, Render, Scene, Update};
+use crate::engine::{Engine, Config, Grid, Position, Status};
+use crate::engine::board::Board;
+use crate::engine::hazard::Hazard;
+use crate::engine::frenzy::Frenzy;
+use crate::engine::skin::Skin;
+use crate::engine::power_up::PowerUp;
+use crate::engine::snake::Snake;
+use crate::engine::speed::Timestep;
+use crate::engine::controller::{Controller, KeyboardController};
+use crate::engine::event::{GameEvent, Observer};
+use crate::engine::mode::{GameMode, Mode};
+use crate::direction::Direction;
+use crate::highscores::{Leaderboard, HighScores, Metric, NameEntry, ScoreRecord};
+use crate::save::SaveData;
+use crate::storage;
+use crate::profiles::ProfileMenu;
+use crate::dialog::{QuitDialog, QuitChoice, LoadErrorDialog};
+use crate::tasks::{TaskQueue, Task, TaskEvent};
+use crate::changelog::{self, ChangelogView};
+use crate::input::{InputSource, InputEvent, Action};
+use crate::clock::{self, Clock, RunTimer, SpeedCalibration};
+use crate::debug::{DebugOverlay, DebugInfo};
+use crate::frame::Frame;
+use crate::level::{Level, LevelError};
+use crate::campaign::{Campaign, LevelSelect, LevelChoice};
+use crate::puzzle::{Puzzles, PuzzleSelect};
+use crate::replay::Replay;
+use crate::challenge::{Challenge, ChallengePrompt};
+use crate::tournament::Tournament;
+use crate::session::Session;
+use crate::tutorial::Tutorial;
+use crate::minimap::Minimap;
+use crate::camera::Camera;
+use crate::editor::{Editor, EditorAction};
+use crate::custom::{CustomGameMenu, CustomChoice};
+use crate::display::{CustomFont, DisplayChoice, DisplayMenu, DisplaySettings, FontChoice, Scale};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::export;
+use crate::recording::Recorder;
+use crate::params::UrlParams;
+use crate::touch::{self, TouchButton};
+
+pub use crate::clock::Timestamp;
+
+/// How [`Game::run_with`] sets up bracket-terminal's backend. Options that only mean something to some
+/// backends are ignored by the rest
+#[derive(Copy, Clone, Debug)]
+pub struct RunOptions {
+    /// The most frames drawn per second. By default, the cap picked on the display menu, which may be none.
+    /// The snake keeps the same pace whatever the frame rate
+    pub fps_cap: Option<f32>,
+    /// Wait for the screen's refresh between frames. By default, as picked on the display menu. OpenGL only,
+    /// outside the browser, which always does
+    pub vsync: Option<bool>,
+    /// The size of a tile in pixels. Left at [`Game::TILE_DIMENSIONS`], the size picked on the display
+    /// menu is used instead. Terminal backends always use a character per tile
+    pub tile_dimensions: (u32, u32),
+    /// Draw the CRT scanline effect. OpenGL only, and on by default there
+    pub scanlines: bool,
+    /// Fill the screen. OpenGL only, outside the browser
+    pub fullscreen: bool,
+    /// How many tiles the window shows each way. Maps bigger than that scroll to follow the snake. By
+    /// default the window fits the map, up to [`Game::MAX_VIEW_DIMENSIONS`]
+    pub view_dimensions: Option<(u32, u32)>,
+    /// How much bigger than its tile size everything is drawn, for high-DPI screens. By default, the scale
+    /// picked on the display menu, which works it out from the screen unless set otherwise. OpenGL only
+    pub scale: Option<Scale>
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            fps_cap: None,
+            vsync: None,
+            tile_dimensions: Game::TILE_DIMENSIONS,
+            scanlines: cfg!(feature = "opengl"),
+            fullscreen: false,
+            view_dimensions: None,
+            scale: None
+        }
+    }
+}
+
+/// Plays an [`Engine`] run in a terminal window: slithers it along in real time, feeds it the player's
+/// input, and draws it alongside the menus and screens around it
+pub struct Game {
+    clock: Clock,
+    run_timer: RunTimer,
+    splits: Vec<(usize, f64)>, // Run time at which each fruit milestone was reached
+    debug_overlay: DebugOverlay,
+    animator: Animator, // Times the animations of everything on the board
+    input: InputSource,
+    config: Config, // Settings for the next run, as chosen on the game over screen
+    url_params: UrlParams, // Settings fixed by the page's URL, which win out over the saved ones
+    ascii_only: bool, // Whether skins drawn with more than 7-bit ASCII are kept off the screen
+    view: (u32, u32), // How many tiles the window shows each way, which the camera scrolls bigger maps within
+    display: DisplaySettings, // The tile size and font, as saved for the next launch
+    window: DisplaySettings, // The settings the window was built with, which hold until it's next launched
+    custom_font: Option<CustomFont>, // A font sheet loaded from a file, to be picked on the display menu
+    engine: Engine,
+    custom_rules: Option<Box<dyn Fn() -> Box<dyn GameMode>>>, // Builds the rules of every run in place of `config.mode`
+    level_name: Option<String>,
+    high_score_tab: Metric, // The table shown on the game over screen
+    high_score_leaderboard: Option<Leaderboard>, // The leaderboard shown, if not that of the chosen settings
+    save_data: SaveData,
+    tasks: TaskQueue,
+    changelog: Option<ChangelogView>, // The "What's new" screen, while it is open
+    profile_menu: Option<ProfileMenu>, // The profile screen, while it is open
+    name_entry: Option<NameEntry>, // Naming a run that made the high-score tables, before it's recorded
+    challenge_prompt: Option<ChallengePrompt>, // The "Play challenge" screen, while it is open
+    challenge: Option<Challenge>, // The challenge being raced, which fixes the seed while its settings are kept
+    session: Session, // The runs played since launch, summed up on the way out
+    quit_dialog: Option<QuitDialog>, // Asking whether to quit, while it is open
+    load_error: Option<LoadErrorDialog>, // Why the last level couldn't be loaded, until a key is pressed
+    closing: bool, // Whether the session summary is up, the game quitting on the next key
+    heatmap: bool, // Whether the death heatmap is shown in place of the game over screen
+    tournament: Option<Tournament>, // The hot-seat tournament being played, which takes over the game over screen
+    tutorial: Option<Tutorial>, // The tutorial being played, which scripts the run and prompts the player
+    campaign: Option<Campaign>, // The campaign being played, whose levels and goals set up every run
+    level_select: Option<LevelSelect>, // Picking a level of the campaign, while the screen is open
+    puzzles: Option<Puzzles>, // The puzzles being played, each of which lays out a run in advance
+    puzzle_select: Option<PuzzleSelect>, // Picking a puzzle, while the screen is open
+    custom_menu: Option<CustomGameMenu>, // Setting up a custom game, while the menu is open
+    display_menu: Option<DisplayMenu>, // Picking the tile size and font, while the menu is open
+    editor: Option<Editor>, // The level being made in the editor, kept while it's playtested
+    editing: bool, // Whether the editor is open
+    recorder: Option<Recorder>, // Only kept when runs are being recorded
+    toast: Option<(String, Timestamp)>, // A brief message along the bottom of the screen, and when it was shown
+    title: String, // The window's title as it was last set, so it's only set again when it changes
+    assist: bool, // Whether the autopilot is steering the snake in place of the player
+    playback: Option<Replay>, // The replay being watched, which steers every snake in place of their controllers
+    keyboard: KeyboardController,
+    partner: KeyboardController, // The second player's keys, in modes for two
+    touch_controls: bool, // Whether to draw buttons for touch screens
+    autopilot: Box<dyn Controller>,
+    bots: Vec<Box<dyn Controller>>, // Steering the snakes no player does, such as rivals in versus
+    observers: Vec<Box<dyn Observer>>,
+    paused: bool,
+    resumed_at: Option<Timestamp>, // When the run was last unpaused, the snake holding still for a countdown
+    restart_held_since: Option<Timestamp>, // When [R] was pressed mid-run, which restarts once it's held long enough
+    undos_left: usize, // Times the run can still be rewound after a death, in modes that allow it
+    died_at: Option<Timestamp>, // When the run ended with an undo to hand, which is offered for a few seconds
+    practice_speed: f64, // Multiplies the pace of practice runs, as set with [ and ]
+    checkpoint: Option<usize>, // The slither a practice run goes back to after a death, if one was set
+    previous_frame_time: Timestamp,
+    timestep: Timestep,
+    calibration: SpeedCalibration,
+}
+
+impl Game {
+    pub const TITLE: &'static str = "Snake";
+
+    pub const FRUIT_COLOUR: RGB = RGB {r: 1.0, g: 0.5, b: 0.5};
+    pub const ASSIST_COLOUR: RGB = RGB {r: 0.5, g: 0.75, b: 1.0};
+    pub const BACKGROUND_COLOUR: RGB = RGB {r: 0.175, g: 0.2, b: 0.225};
+
+    pub const TILE_DIMENSIONS: (u32, u32) = (25, 25);
+    pub const MAP_DIMENSIONS: (u32, u32) = Engine::MAP_DIMENSIONS;
+    pub const MAX_VIEW_DIMENSIONS: (u32, u32) = (60, 60); // The most tiles the window fits a map to by default
+
+    pub const MAP_CENTRE: (u32, u32) = (
+        Self::MAP_DIMENSIONS.0 / 2,
+        Self::MAP_DIMENSIONS.1 / 2
+    );
+
+    pub const MAP_LAYER: usize = 0;
+    pub const HUD_LAYER: usize = 1;
+
+    pub const SPLIT_MILESTONES: [usize; 3] = [10, 25, 50]; // Fruit eaten at which run splits are taken
+
+    pub const FRAMES_PER_SECOND: f32 = 60.0;
+
+    pub const TOAST_SECONDS: f64 = 3.0;
+    pub const RESUME_COUNTDOWN_SECONDS: f64 = 3.0;
+    pub const RESTART_HOLD_SECONDS: f64 = 0.75; // How long [R] has to be held to restart a run in progress
+    pub const RESTART_BAR_WIDTH: usize = 10;
+    pub const UNDO_TICKS: usize = 15; // Slithers taken back by an undo
+    pub const UNDO_SECONDS: f64 = 3.0; // How long an undo is offered for after the snake dies
+    pub const PRACTICE_SPEEDS: (f64, f64) = (0.25, 4.0); // The slowest and fastest practice runs can be set to
+
+    pub fn new() -> Self {
+        let clock = Clock::default();
+        let config = Config {
+            seed: rand::thread_rng().gen(),
+            ..Config::default()
+        };
+        let engine = Engine::new(config.clone());
+
+        let mut game = Self {
+            previous_frame_time: clock.now(),
+            timestep: Timestep::default(),
+            calibration: SpeedCalibration::new(&clock),
+            debug_overlay: DebugOverlay::new(&clock),
+            animator: Animator::new(&clock),
+            clock,
+            run_timer: RunTimer::default(),
+            splits: Vec::with_capacity(Self::SPLIT_MILESTONES.len()),
+            input: InputSource::default(),
+            bots: config.difficulty.bots_for(&engine),
+            engine,
+            autopilot: config.difficulty.controller(),
+            view: config.dimensions,
+            display: DisplaySettings::default(),
+            window: DisplaySettings::default(),
+            custom_font: None,
+            config,
+            url_params: UrlParams::default(),
+            ascii_only: false,
+            custom_rules: None,
+            level_name: None,
+            high_score_tab: Metric::default(),
+            high_score_leaderboard: None,
+            save_data: SaveData::default(), // Replaced once the load task finishes
+            tasks: TaskQueue::default(),
+            changelog: None,
+            profile_menu: None,
+            name_entry: None,
+            challenge_prompt: None,
+            challenge: None,
+            session: Session::default(),
+            quit_dialog: None,
+            load_error: None,
+            closing: false,
+            heatmap: false,
+            tournament: None,
+            tutorial: None,
+            campaign: None,
+            level_select: None,
+            puzzles: None,
+            puzzle_select: None,
+            custom_menu: None,
+            display_menu: None,
+            editor: None,
+            editing: false,
+            recorder: None,
+            toast: None,
+            title: Self::TITLE.to_string(),
+            assist: false,
+            playback: None,
+            keyboard: KeyboardController::default(),
+            partner: KeyboardController::default(),
+            touch_controls: touch::is_available(),
+            observers: Vec::new(),
+            paused: false,
+            resumed_at: None,
+            restart_held_since: None,
+            undos_left: 0,
+            died_at: None,
+            practice_speed: 1.0,
+            checkpoint: None
+        };
+
+        game.tasks.submit(Task::Load);
+
+        game
+    }
+
+    /// Open the game window and play until it is closed. This hands the thread over to bracket-terminal's
+    /// main loop, so embedders that want to drive the game themselves should use [`Engine`] instead
+    pub fn run(self) -> BError {
+        self.run_with(RunOptions::default())
+    }
+
+    /// [`Game::run`], with the backend set up by `options`
+    pub fn run_with(mut self, options: RunOptions) -> BError {
+        let map = self.config.dimensions;
+
+        self.view = options.view_dimensions.unwrap_or((
+            map.0.min(Self::MAX_VIEW_DIMENSIONS.0),
+            map.1.min(Self::MAX_VIEW_DIMENSIONS.1)
+        ));
+
+        let (width, height) = self.view;
+
+        // The window is sized by the display settings, so they're read ahead of the rest of the save data
+        self.load_display_settings();
+
+        let tile_dimensions = match options.tile_dimensions {
+            dimensions if dimensions == Self::TILE_DIMENSIONS => (self.display.tile_size, self.display.tile_size),
+            dimensions => dimensions
+        };
+
+        self.window = DisplaySettings {
+            tile_size: tile_dimensions.0,
+            scale: options.scale.unwrap_or(self.display.scale),
+            vsync: options.vsync.unwrap_or(self.display.vsync),
+            fps_cap: options.fps_cap.map_or(self.display.fps_cap, |cap| Some(cap.round() as u32)),
+            ..self.display.clone()
+        };
+
+        // The browser stretches the canvas to fit the page, so on high-DPI screens it's drawn at the
+        // screen's own resolution to stay sharp
+        #[cfg(target_arch = "wasm32")]
+        let tile_dimensions = {
+            let factor = self.window.scale.factor(crate::web::device_pixel_ratio(), None);
+
+            ((tile_dimensions.0 as f64 * factor).round() as u32, (tile_dimensions.1 as f64 * factor).round() as u32)
+        };
+
+        // Build application
+        let mut builder = BTermBuilder::simple(width, height).expect("Failed to construct applciation builder")
+            .with_title(Self::TITLE)
+            .with_tile_dimensions(tile_dimensions.0, tile_dimensions.1)
+            .with_vsync(self.window.vsync)
+            .with_fullscreen(options.fullscreen)
+            .with_simple_console_no_bg(width, height, "terminal8x8.png") // HUD layer
+            .with_font("vga8x16.png", 8, 16)
+            .with_advanced_input(true);
+
+        if let Some(cap) = options.fps_cap.or_else(|| self.window.fps_cap.map(|cap| cap as f32)) {
+            builder = builder.with_fps_cap(cap);
+        }
+
+        if let Some(font) = &self.custom_font {
+            builder = builder.with_font(CustomFont::RESOURCE, font.glyph_dimensions.0, font.glyph_dimensions.1);
+        }
+
+        let mut ctx = builder.build().expect("Failed to build application context");
+
+        if options.scanlines {
+            ctx.with_post_scanlines(true);
+        }
+
+        #[cfg(all(feature = "opengl", not(target_arch = "wasm32")))]
+        Self::scale_window(self.window.scale);
+
+        self.apply_display(&mut ctx);
+
+        // The canvas only exists once the context has been built
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::web::fit_canvas_to_window();
+            crate::web::listen_for_taps();
+            crate::web::listen_for_visibility();
+        }
+
+        // Run game loop
+        bracket_terminal::prelude::main_loop(ctx, self)
+    }
+
+    // Play by the settings in a page's URL from now on, starting a fresh run. This has to be done before the
+    // game is run, as the window is sized to the map
+    pub fn apply_url_params(&mut self, params: UrlParams) {
+        params.apply(&mut self.config);
+        self.url_params = params;
+        self.reset();
+    }
+
+    // Play a designed level from now on, starting a fresh run. A level that can't be played on the map,
+    // such as one with part of its arena walled off, is turned away with an error screen instead
+    pub fn load_level(&mut self, level: Level) {
+        if let Err(error) = level.validate(self.config.dimensions) {
+            self.show_load_error(error);
+            return;
+        }
+
+        self.level_name = Some(level.name);
+        self.config.schedule = level.schedule;
+        self.config.tags = level.tags;
+        self.config.fruits = level.fruits;
+        self.config.arena = level.arena;
+        self.config.edges = level.edges;
+        self.reset();
+    }
+
+    // Explain why a level couldn't be loaded, until a key is pressed
+    pub fn show_load_error(&mut self, error: LevelError) {
+        self.load_error = Some(LoadErrorDialog::new(&error));
+    }
+
+    /// Play every run from now on by custom rules, such as those of a mod, in place of the built-in modes.
+    /// `rules` is called for a fresh copy at the start of each run. Runs played this way are left out of
+    /// the high-score tables
+    pub fn play_by(&mut self, rules: impl Fn() -> Box<dyn GameMode> + 'static) {
+        self.custom_rules = Some(Box::new(rules));
+        self.reset();
+    }
+
+    /// Keep a recording of every run from now on, which can be saved as an animated GIF with [G]: the whole
+    /// run once it's over, or the last thirty seconds while it's still going
+    pub fn record_runs(&mut self) {
+        self.recorder.get_or_insert_with(Recorder::default);
+    }
+
+    /// Draw everything in 7-bit ASCII, for fonts without the box-drawing characters. The skin switches
+    /// to an ASCII one, and [K] only cycles through those
+    pub fn restrict_to_ascii(&mut self) {
+        self.ascii_only = true;
+        self.set_skin(self.config.skin);
+    }
+
+    /// Draw the window with the font sheet in the PNG file at `path`: 16 rows of 16 glyphs in code page 437
+    /// order, as bracket-terminal's own fonts are laid out. This has to be done before the game is run, as
+    /// the window's fonts are loaded as it's built, and the font is remembered for the next launch
+    pub fn use_font(&mut self, path: &str) -> Result<(), String> {
+        self.custom_font = Some(CustomFont::load(path)?);
+
+        Ok(())
+    }
+
+    // Pick up the saved tile size and font, along with the custom font they were last used with unless
+    // another has been loaded in its place
+    fn load_display_settings(&mut self) {
+        self.display = SaveData::load(&storage::platform()).display;
+
+        if let Some(font) = &self.custom_font {
+            self.display.font = FontChoice::Custom;
+            self.display.font_file = Some(font.path.clone());
+        } else if let Some(path) = self.display.font_file.clone() {
+            match CustomFont::load(&path) {
+                Ok(font) => self.custom_font = Some(font),
+                Err(error) => {
+                    eprintln!("Failed to load the font: {}", error);
+                    self.display.font_file = None;
+                }
+            }
+        }
+    }
+
+    // Grow the window to suit a high-DPI screen. It's built in logical pixels, which the backend already
+    // multiplies by the screen's DPI factor, so this makes up the difference where the factor falls short,
+    // as it tends to on X11. The window can only be reached until the main loop takes it over
+    #[cfg(all(feature = "opengl", not(target_arch = "wasm32")))]
+    fn scale_window(scale: Scale) {
+        let backend = bracket_terminal::prelude::BACKEND.lock();
+        let window = match &backend.context_wrapper {
+            Some(wrapper) => wrapper.wc.window(),
+            None => return
+        };
+        let mut size = window.inner_size();
+        let screen = window.current_monitor().map(|monitor| (monitor.size().width, monitor.size().height));
+        let factor = scale.window_factor(window.scale_factor(), screen, (size.width, size.height));
+
+        if (factor - 1.0).abs() > f64::EPSILON {
+            size.width = (size.width as f64 * factor).round() as u32;
+            size.height = (size.height as f64 * factor).round() as u32;
+            window.set_inner_size(size);
+        }
+    }
+
+    // Draw with the font being looked at: the display menu's while it's open, so changes show as they're
+    // made. The window can't be resized once it's built, so the menu previews other tile sizes by zooming
+    // the map
+    #[cfg(feature = "opengl")]
+    fn apply_display(&self, ctx: &mut BTerm) {
+        let settings = self.display_menu.as_ref().map_or(&self.display, DisplayMenu::settings);
+        let font = settings.font.index(self.custom_font.is_some());
+
+        for layer in [Self::MAP_LAYER, Self::HUD_LAYER].iter().copied() {
+            ctx.set_active_console(layer);
+            ctx.set_active_font(font, false);
+        }
+
+        let scale = self.display_menu.as_ref().map_or(1.0, DisplayMenu::preview_scale);
+
+        ctx.set_active_console(Self::MAP_LAYER);
+        ctx.set_scale(scale, self.view.0 as i32 / 2, self.view.1 as i32 / 2);
+    }
+
+    // Terminal backends draw with the terminal's own font, a character per tile
+    #[cfg(not(feature = "opengl"))]
+    fn apply_display(&self, _ctx: &mut BTerm) {}
+
+    fn execute_display_choice(&mut self, choice: DisplayChoice) {
+        if let DisplayChoice::Apply(settings) = choice {
+            if settings.needs_restart_from(&self.window) {
+                self.show_toast("Applies on restart".to_string());
+            }
+
+            self.display = settings;
+            self.persist();
+        }
+    }
+
+    /// The window's title while a run of `engine` is being played, naming its mode and the score so far,
+    /// such as "Snake — Classic — 23"
+    pub fn run_title(engine: &Engine) -> String {
+        format!("{} \u{2014} {} \u{2014} {}", Self::TITLE, engine.rules().name(), engine.score().points())
+    }
+
+    // Retitle the window with the mode and score while a run is being played, and plainly on the menus and
+    // game over screen. bracket-terminal hands the native window over to its event loop once the game is
+    // running, leaving no way to retitle it, so only the browser tab and terminal backends follow along
+    fn update_title(&mut self) {
+        let title = match self.engine.is_over() || self.is_in_menu() {
+            true => Self::TITLE.to_string(),
+            false => Self::run_title(&self.engine)
+        };
+
+        if title == self.title {
+            return;
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        crate::web::set_title(&title);
+
+        // Set with the xterm escape sequence, which terminals that don't understand it ignore
+        #[cfg(all(not(feature = "opengl"), not(target_arch = "wasm32")))]
+        {
+            use std::io::Write;
+
+            print!("\x1b]0;{}\x07", title);
+            std::io::stdout().flush().ok();
+        }
+
+        self.title = title;
+    }
+
+    /// Register an observer to be told about every event of every run from now on
+    pub fn subscribe(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    fn notify(&mut self, event: GameEvent) {
+        for observer in &mut self.observers {
+            observer.notify(&event);
+        }
+    }
+
+    // Start a fresh run with the chosen settings and a new seed
+    pub fn reset(&mut self) {
+        // Changing any of a challenge's settings leaves it behind
+        self.challenge = self.challenge.filter(|challenge| challenge.is_played_by(&self.config));
+        self.config.seed = match (&self.tournament, self.challenge) {
+            (Some(tournament), _) => tournament.seed(),
+            (None, Some(challenge)) => challenge.seed,
+            (None, None) => self.url_params.seed.unwrap_or_else(|| rand::thread_rng().gen())
+        };
+        self.engine = match (&mut self.tutorial, &self.campaign, &self.puzzles) {
+            (Some(tutorial), _, _) => {
+                tutorial.retry();
+                Engine::new(Tutorial::config(&self.config))
+            },
+            (None, Some(campaign), _) => Engine::with_rules(campaign.config(&self.config), campaign.rules()),
+            (None, None, Some(puzzles)) => Engine::with_rules(puzzles.config(&self.config), puzzles.rules()),
+            (None, None, None) => self.start_engine(self.config.clone())
+        };
+        self.undos_left = self.engine.rules().undos();
+        self.died_at = None;
+        self.checkpoint = None;
+        self.previous_frame_time = self.now();
+        self.timestep.reset();
+        self.calibration.reset(&self.clock);
+        self.run_timer = RunTimer::default();
+        self.splits.clear();
+        self.assist = false;
+        self.playback = None;
+        self.autopilot = self.config.difficulty.controller();
+        self.bots = match &self.campaign {
+            Some(campaign) => campaign.bots(),
+            None => self.config.difficulty.bots_for(&self.engine)
+        };
+        self.keyboard.clear();
+        self.partner.clear();
+        self.paused = false;
+        self.resumed_at = None;
+        self.restart_held_since = None;
+        self.heatmap = false;
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.clear();
+        }
+    }
+    
+    // A fresh run set up by `config`, by the custom rules if there are any
+    fn start_engine(&self, config: Config) -> Engine {
+        match &self.custom_rules {
+            Some(rules) => Engine::with_rules(config, rules()),
+            None => Engine::new(config)
+        }
+    }
+
+    // Show what's new if the game has been updated since it was last launched, or start the tutorial on the
+    // first ever launch, unless the game was launched to play something in particular
+    fn check_for_update(&mut self) {
+        let updated = match &self.save_data.last_seen_version {
+            Some(version) => version != changelog::CURRENT_VERSION,
+            None => false // Nothing to compare against on the first ever launch
+        };
+        let launched_for = self.level_name.is_some() || self.custom_rules.is_some() || self.playback.is_some()
+            || self.challenge.is_some() || self.tournament.is_some();
+
+        if updated {
+            self.changelog = Some(ChangelogView::new(changelog::CURRENT_VERSION));
+        } else if self.save_data.last_seen_version.is_none() && !launched_for {
+            self.start_tutorial();
+        }
+
+        self.save_data.last_seen_version = Some(changelog::CURRENT_VERSION.to_string());
+        self.tasks.submit(Task::Save(Box::new(self.save_data.clone())));
+    }
+
+    // Go back to the settings the profile last played with, restarting the run if it hasn't got going yet
+    fn restore_settings(&mut self) {
+        let profile = self.save_data.profile().clone();
+
+        self.config.mode = profile.mode;
+        self.config.scoring_policy = profile.scoring_policy;
+        self.set_skin(profile.skin);
+        self.config.difficulty = profile.difficulty;
+        self.config.win_condition = profile.win_condition;
+        self.config.obstacles = profile.obstacles;
+        self.config.symmetry = profile.symmetry;
+        self.config.length_speed = profile.length_speed;
+        self.config.grid = profile.grid;
+        self.url_params.apply(&mut self.config);
+
+        if let Some(challenge) = self.challenge {
+            challenge.apply(&mut self.config);
+        }
+
+        if self.engine.run_state().time == 0.0 {
+            self.reset();
+        }
+    }
+
+    // Skins two columns wide are for terminals with emoji, which the window's code page 437 font lacks
+    fn can_draw(&self, skin: Skin) -> bool {
+        skin.width == 1 && (!self.ascii_only || skin.is_ascii())
+    }
+
+    // Switch to `skin`, or the first skin that can be drawn in its place
+    fn set_skin(&mut self, skin: Skin) {
+        let skin = if self.can_draw(skin) {
+            skin
+        } else {
+            skin.next(|skin| self.can_draw(skin))
+        };
+
+        self.config.skin = skin;
+        self.engine.set_skin(skin);
+    }
+
+    // Save the high scores and settings as they stand
+    fn persist(&mut self) {
+        let profile = self.save_data.profile_mut();
+
+        profile.mode = self.config.mode;
+        profile.scoring_policy = self.config.scoring_policy;
+        profile.skin = self.config.skin;
+        profile.difficulty = self.config.difficulty;
+        profile.win_condition = self.config.win_condition;
+        profile.obstacles = self.config.obstacles;
+        profile.symmetry = self.config.symmetry;
+        profile.length_speed = self.config.length_speed;
+        profile.grid = self.config.grid;
+        self.save_data.display = self.display.clone();
+        self.tasks.submit(Task::Save(Box::new(self.save_data.clone())));
+    }
+
+    // Pick up the results of any storage tasks that have finished
+    fn handle_tasks(&mut self) {
+        for event in self.tasks.poll() {
+            match event {
+                TaskEvent::Loaded(save_data) => {
+                    self.save_data = *save_data;
+                    self.restore_settings();
+                    self.check_for_update();
+
+                    // Ask who's playing when there's more than one player to choose from
+                    if self.save_data.profiles().len() > 1 && self.changelog.is_none() {
+                        self.profile_menu = Some(ProfileMenu::new(&self.save_data));
+                    }
+                },
+                // Failing to save shouldn't stop the game from running
+                TaskEvent::Saved(Err(error)) => eprintln!("Failed to save: {}", error),
+                TaskEvent::Saved(Ok(())) => {},
+                TaskEvent::Exported(result) => self.show_toast(match result {
+                    Ok(name) => format!("Saved {}", name),
+                    Err(error) => format!("Failed to save: {}", error)
+                })
+            }
+        }
+    }
+
+    fn save_recording(&mut self) {
+        let clip = match &self.recorder {
+            Some(recorder) if !recorder.is_empty() => if self.engine.is_over() {
+                recorder.clip(None)
+            } else {
+                recorder.clip(Some(Recorder::CLIP_SECONDS))
+            },
+            _ => return
+        };
+
+        self.show_toast("Saving recording...".to_string());
+        self.tasks.submit(Task::SaveRecording(clip));
+    }
+
+    // Whether the run can be saved as a replay. Replays only hold the settings of a plain run, so runs laid
+    // out by a level or driven by rules of their own would play back as something else
+    fn can_save_replay(&self) -> bool {
+        self.level_name.is_none() && self.custom_rules.is_none() && self.campaign.is_none() && self.puzzles.is_none() && self.tutorial.is_none()
+    }
+
+    // Save the run just played as a replay, if it's one a replay can hold
+    fn save_replay(&mut self) {
+        if !self.can_save_replay() {
+            self.show_toast("This run can't be replayed".to_string());
+            return;
+        }
+
+        self.tasks.submit(Task::SaveReplay(Replay::of(&self.engine).serialise()));
+    }
+
+    // Watch the newest replay saved in the working directory
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_replay(&mut self) {
+        let loaded = export::latest("replay")
+            .ok_or_else(|| "No replays saved".to_string())
+            .and_then(|path| Replay::load(&path).map_err(|error| error.to_string()));
+
+        match loaded {
+            Ok(replay) => self.watch(replay),
+            Err(error) => self.show_toast(error)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_replay(&mut self) {
+        self.show_toast("Needs the desktop game".to_string());
+    }
+
+    /// Play back a replay in place of the next run. Replays recorded on maps bigger than the window
+    /// scroll to follow the player's snake
+    pub fn watch(&mut self, replay: Replay) {
+        self.reset();
+        self.engine = replay.start();
+        self.engine.set_skin(self.config.skin);
+        self.playback = Some(replay);
+    }
+
+    /// Play the tutorial, which teaches the basics over a few lessons on a run of its own. It can be
+    /// skipped with [F1], and is started on the first ever launch
+    pub fn start_tutorial(&mut self) {
+        self.tournament = None;
+        self.tutorial = Some(Tutorial::new());
+        self.reset();
+    }
+
+    // Leave the tutorial for a run of the player's own
+    fn leave_tutorial(&mut self) {
+        self.tutorial = None;
+        self.reset();
+    }
+
+    /// Play the campaign from the level at `index` from now on, moving on to the next level each time one
+    /// is passed. The campaign is left from its level select screen, opened with [Q]
+    pub fn play_campaign(&mut self, index: usize) {
+        if self.level_name.is_some() || self.custom_rules.is_some() {
+            self.show_toast("Can't play the campaign here".to_string());
+            return;
+        }
+
+        self.tournament = None;
+        self.tutorial = None;
+        self.puzzles = None;
+        self.campaign = Some(Campaign::new(index));
+        self.reset();
+    }
+
+    /// Play the puzzles from the one at `index` from now on, moving on to the next puzzle each time one is
+    /// solved. The snake only moves when it's steered. The puzzles are left from their select screen,
+    /// opened with [F4]
+    pub fn play_puzzle(&mut self, index: usize) {
+        if self.level_name.is_some() || self.custom_rules.is_some() {
+            self.show_toast("Can't play puzzles here".to_string());
+            return;
+        }
+
+        self.tournament = None;
+        self.tutorial = None;
+        self.campaign = None;
+        self.puzzles = Some(Puzzles::new(index));
+        self.reset();
+    }
+
+    fn open_puzzle_select(&mut self) {
+        self.puzzle_select = Some(PuzzleSelect::new(&self.save_data.profile().puzzles));
+    }
+
+    fn execute_puzzle_choice(&mut self, choice: LevelChoice) {
+        match choice {
+            LevelChoice::Play(index) => self.play_puzzle(index),
+            LevelChoice::Close => if self.puzzles.take().is_some() {
+                self.reset();
+            }
+        }
+    }
+
+    fn open_level_select(&mut self) {
+        self.level_select = Some(LevelSelect::new(&self.save_data.profile().campaign));
+    }
+
+    fn execute_level_choice(&mut self, choice: LevelChoice) {
+        match choice {
+            LevelChoice::Play(index) => self.play_campaign(index),
+            // Backing out of the level select screen leaves the campaign for a run of the player's own
+            LevelChoice::Close => if self.campaign.take().is_some() {
+                self.reset();
+            }
+        }
+    }
+
+    /// Race the board of a challenge code from now on, starting a fresh run. Restarting keeps to the same
+    /// board until one of the challenge's settings is changed
+    pub fn play_challenge(&mut self, challenge: Challenge) {
+        if self.level_name.is_some() || self.custom_rules.is_some() {
+            self.show_toast("Can't race challenges here".to_string());
+            return;
+        }
+
+        challenge.apply(&mut self.config);
+        self.challenge = Some(challenge);
+        self.reset();
+    }
+
+    /// Start a hot-seat tournament between `players`, who take turns at runs on the same board, best of
+    /// `best_of` rounds. The game over screen shows the standings until the tournament is left
+    pub fn start_tournament(&mut self, players: usize, best_of: usize) {
+        if self.level_name.is_some() || self.custom_rules.is_some() {
+            self.show_toast("Can't hold tournaments here".to_string());
+            return;
+        }
+
+        self.tournament = Some(Tournament::new(players, best_of, rand::thread_rng().gen()));
+        self.reset();
+    }
+
+    // Record a run waiting to be named under the default name, for when there's no way left to type one
+    fn skip_name_entry(&mut self) {
+        if let Some((leaderboard, record)) = self.name_entry.take().and_then(|mut entry| entry.handle_input(VirtualKeyCode::Escape)) {
+            self.save_data.profile_mut().high_scores.record(leaderboard, record);
+            self.persist();
+        }
+    }
+
+    // Whether a screen is open over the game, which holds the run still
+    fn is_in_menu(&self) -> bool {
+        self.changelog.is_some() || self.profile_menu.is_some() || self.name_entry.is_some() || self.quit_dialog.is_some() || self.load_error.is_some() || self.level_select.is_some() || self.puzzle_select.is_some() || self.custom_menu.is_some() || self.display_menu.is_some() || self.editing || self.closing
+    }
+
+    // Play as whichever profile is active from now on, with its own settings
+    fn switch_profile(&mut self) {
+        self.restore_settings();
+        self.tasks.submit(Task::Save(Box::new(self.save_data.clone())));
+    }
+
+    // Pause a run in progress when the player switches away from the window, so it can't be lost to an
+    // alt-tab. This goes for modes that can't otherwise be paused too
+    fn pause_on_focus_loss(&mut self) {
+        if self.engine.is_over() || self.paused || self.is_in_menu() {
+            return;
+        }
+
+        self.paused = true;
+        self.resumed_at = None;
+        self.restart_held_since = None; // [R] may never be seen let go
+        self.notify(GameEvent::Paused);
+    }
+
+    // The seconds left before the snake moves again after being unpaused, if it's still waiting
+    fn countdown_left(&self) -> Option<f64> {
+        let resumed_at = self.resumed_at?;
+
+        Some(Self::RESUME_COUNTDOWN_SECONDS - self.seconds_since(resumed_at)).filter(|left| *left > 0.0)
+    }
+
+    // Ask before quitting, offering to save a run that's still going. Asking to close the window again
+    // while the dialog is up doesn't wait for an answer
+    fn confirm_quit(&mut self) {
+        if self.quit_dialog.is_some() {
+            self.quit_dialog = None;
+            self.end_session();
+            return;
+        }
+
+        let can_save = self.can_save_replay() && self.playback.is_none();
+
+        self.quit_dialog = Some(QuitDialog::new(!self.engine.is_over() && self.playback.is_none(), can_save));
+    }
+
+    // How far [R] has been held towards restarting the run, from 0 to 1, while it's held
+    fn restart_progress(&self) -> Option<f64> {
+        self.restart_held_since.map(|held_since| (self.seconds_since(held_since) / Self::RESTART_HOLD_SECONDS).min(1.0))
+    }
+
+    // Restart the run in progress once [R] has been held long enough, or forget it if let go early or the
+    // run ends first. The run given up still counts towards the session
+    fn update_restart(&mut self) {
+        match self.restart_progress() {
+            Some(_) if self.engine.is_over() || !self.input.is_held(VirtualKeyCode::R) => self.restart_held_since = None,
+            Some(progress) if progress >= 1.0 => {
+                if self.playback.is_none() {
+                    self.session.record(self.engine.score().points(), self.engine.longest_length(), self.run_timer.seconds(&self.clock));
+                }
+
+                self.reset();
+            },
+            _ => {}
+        }
+    }
+
+    // How much faster than its own pace the run is played. Only practice runs can be sped up or slowed down
+    fn speed_multiplier(&self) -> f64 {
+        if self.engine.rules().is_practice() {
+            self.practice_speed
+        } else {
+            1.0
+        }
+    }
+
+    fn toggle_fruit_hint(&mut self) {
+        let profile = self.save_data.profile_mut();
+
+        profile.fruit_hint = !profile.fruit_hint;
+        self.persist();
+    }
+
+    // Halve or double the pace of practice runs, which carries over to the next run
+    fn set_practice_speed(&mut self, speed: f64) {
+        self.practice_speed = speed.clamp(Self::PRACTICE_SPEEDS.0, Self::PRACTICE_SPEEDS.1);
+        self.calibration.restart(&self.clock); // The rate measured so far was for the old pace
+        self.show_toast(format!("Speed x{}", self.practice_speed));
+    }
+
+    // Whether the run just lost can still be taken back, whether by an undo or to a checkpoint
+    fn can_undo(&self) -> bool {
+        (self.undos_left > 0 || self.checkpoint.is_some()) && self.engine.status() == Status::Lost && self.playback.is_none() && self.tournament.is_none()
+    }
+
+    // Rewind the run to its checkpoint, or else a moment before the snake died, and carry on from there
+    // after the countdown. Going back to a checkpoint doesn't use up an undo
+    fn undo(&mut self) {
+        let tick = match self.checkpoint {
+            Some(tick) => tick,
+            None => {
+                self.undos_left -= 1;
+                self.engine.ticks().saturating_sub(Self::UNDO_TICKS)
+            }
+        };
+
+        self.rewind_to(tick);
+    }
+
+    // Go back to just before slither `tick` of the run. The engine can't be copied, so the run is played out
+    // again from its steering up to that point
+    fn rewind_to(&mut self, tick: usize) {
+        let replay = Replay::of(&self.engine);
+        let mut engine = self.start_engine(replay.config.clone());
+
+        replay.play_to(&mut engine, tick);
+
+        if self.engine.score().is_assisted() {
+            engine.mark_assisted();
+        }
+
+        self.engine = engine;
+        self.died_at = None;
+        self.bots = self.config.difficulty.bots_for(&self.engine);
+        self.keyboard.clear();
+        self.partner.clear();
+        self.previous_frame_time = self.now();
+        self.timestep.reset();
+        self.resumed_at = Some(self.now());
+    }
+
+    // Settle the run once the undo offer has run out
+    fn update_undo(&mut self) {
+        if self.died_at.is_some_and(|died_at| self.seconds_since(died_at) >= Self::UNDO_SECONDS) {
+            self.died_at = None;
+            self.finish_run();
+        }
+    }
+
+    // Count a run that's over towards the session and any tournament, and offer it a place in the
+    // high-score tables
+    fn finish_run(&mut self) {
+        if self.playback.is_some() {
+            return;
+        }
+
+        self.session.record(self.engine.score().points(), self.engine.longest_length(), self.run_timer.seconds(&self.clock));
+
+        if let Some(tournament) = &mut self.tournament {
+            tournament.record(self.engine.score().points());
+        }
+
+        if let (Some(campaign), Status::Won) = (&self.campaign, self.engine.status()) {
+            let stars = campaign.stars(self.engine.run_state().time);
+            let unlocked: Vec<Mode> = Mode::ALL.iter().copied().filter(|mode| !self.save_data.profile().is_unlocked(*mode)).collect();
+
+            self.save_data.profile_mut().pass_level(campaign.current(), stars);
+            self.persist();
+
+            if let Some(mode) = unlocked.into_iter().find(|mode| self.save_data.profile().is_unlocked(*mode)) {
+                self.show_toast(format!("{} mode unlocked!", mode.name()));
+            }
+        }
+
+        if let (Some(puzzles), Status::Won) = (&self.puzzles, self.engine.status()) {
+            self.save_data.profile_mut().solve_puzzle(puzzles.current(), puzzles.stars(self.engine.ticks()));
+            self.persist();
+        }
+
+        if self.level_name.is_none() && self.custom_rules.is_none() && self.campaign.is_none() && self.puzzles.is_none() && !self.engine.rules().is_practice() && Leaderboard::ranks(self.engine.config()) {
+            let leaderboard = Leaderboard::of(self.engine.config());
+            let profile = self.save_data.profile();
+            let record = ScoreRecord {
+                name: profile.name.clone(),
+                score: self.engine.score().points(),
+                length: self.engine.longest_length(),
+                assisted: self.engine.score().is_assisted()
+            };
+
+            // Runs that make the tables are named first, and recorded once the name is confirmed
+            if profile.high_scores.qualifies(leaderboard, &record) {
+                self.name_entry = Some(NameEntry::new(leaderboard, record, &profile.name));
+            }
+        }
+    }
+
+    // Sum up the session before quitting, which the next key press or close does
+    fn end_session(&mut self) {
+        if self.died_at.take().is_some() {
+            self.finish_run();
+        }
+
+        self.closing = true;
+        self.skip_name_entry();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        println!("{}", self.session.lines().join("\n"));
+    }
+
+    // Write the board out as text, for pasting into bug reports
+    fn copy_board(&mut self) {
+        let board = Board::capture(&self.engine).to_string();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            println!("{}", board);
+            self.show_toast("Board printed".to_string());
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            web_sys::console::log_1(&board.into());
+            self.show_toast("Board logged to the console".to_string());
+        }
+    }
+
+    // Keep a picture of every slither, once it's been drawn
+    fn record_frame(&mut self) {
+        let time = self.engine.run_state().time;
+
+        if let Some(recorder) = &mut self.recorder {
+            if recorder.last_time() != Some(time) {
+                recorder.record(Frame::capture(), time);
+            }
+        }
+    }
+
+    fn show_toast(&mut self, message: String) {
+        self.toast = Some((message, self.now()));
+    }
+
+    // The current time, as measured by the platform's clock
+    fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    fn seconds_since(&self, timestamp: Timestamp) -> f64 {
+        self.clock.seconds_since(timestamp)
+    }
+
+    // Keep the run timer going only while the game is actually being played
+    fn update_run_timer(&mut self) {
+        if self.engine.is_over() || self.paused || self.is_in_menu() || self.countdown_left().is_some() {
+            self.run_timer.stop(&self.clock);
+        } else {
+            self.run_timer.start(&self.clock);
+        }
+    }
+
+    // Take a split for every fruit milestone that has just been passed
+    fn record_splits(&mut self) {
+        let fruits_eaten = self.engine.score().fruits_eaten();
+
+        for milestone in Self::SPLIT_MILESTONES.iter().copied() {
+            if fruits_eaten >= milestone && !self.splits.iter().any(|(reached, _)| *reached == milestone) {
+                self.splits.push((milestone, self.run_timer.seconds(&self.clock)));
+            }
+        }
+    }
+
+    // Run as many fixed-length slithers as the real time since the last frame calls for, so the snake
+    // keeps the same pace at any frame rate and however unevenly frames arrive. Time lost to a long stall
+    // is only partly made up, rather than sending the snake flying across the map, and what's let go is
+    // logged to the console
+    fn update_snake(&mut self) {
+        let tick_length = self.engine.seconds_per_tick() / self.speed_multiplier();
+        let elapsed = self.seconds_since(self.previous_frame_time) * self.calibration.correction();
+
+        self.previous_frame_time = self.now();
+
+        let dropped = self.timestep.bank(elapsed, tick_length);
+
+        if dropped > 0.0 {
+            bracket_terminal::console::log(format!("Frame took too long, letting {:.2}s go rather than catching up", dropped));
+            self.calibration.record_dropped(dropped / self.calibration.correction()); // Back to real seconds
+        }
+
+        loop {
+            // Turn-based modes hold the snake still until it's steered, ready to move the moment it is. The
+            // wait is kept out of the speed calibration, as it isn't the snake running slow
+            if self.is_waiting_for_move() {
+                self.timestep.hold(tick_length);
+                self.calibration.restart(&self.clock);
+                break;
+            }
+
+            if !self.timestep.take(tick_length) {
+                break;
+            }
+
+            match &self.playback {
+                Some(replay) => replay.steer(&mut self.engine),
+                None => self.steer_snakes()
+            }
+
+            self.engine.tick();
+
+            for event in self.engine.events() {
+                for observer in &mut self.observers {
+                    observer.notify(event);
+                }
+
+                if let (GameEvent::Died {snake: 0, position, ..}, None, None, None) = (event, &self.playback, &self.custom_rules, &self.tutorial) {
+                    self.save_data.profile_mut().deaths.record(*position);
+                }
+            }
+
+            if let Some(tutorial) = &mut self.tutorial {
+                if tutorial.update(&mut self.engine) {
+                    self.show_toast("Well done!".to_string());
+                }
+            }
+
+            self.debug_overlay.record_slither();
+            self.calibration.record_slither();
+        }
+
+        self.calibration.update(&self.clock, self.engine.slithers_per_second() * self.speed_multiplier());
+    }
+
+    // Whether the snake is waiting on the player to pick its next move. A snake that has crashed carries on
+    // without one, so it goes down in its own time
+    fn is_waiting_for_move(&self) -> bool {
+        let snake = self.engine.snake();
+
+        self.engine.rules().is_turn_based() && self.playback.is_none() && !self.assist && !self.engine.is_over() &&
+            snake.alive && !snake.has_crashed(self.engine.arena()) && !self.keyboard.has_presses()
+    }
+
+    // Ask each snake's controller which way to go next
+    fn steer_snakes(&mut self) {
+        let controller: &mut dyn Controller = if self.assist {
+            self.engine.mark_assisted();
+            self.autopilot.as_mut()
+        } else {
+            &mut self.keyboard
+        };
+
+        if let Some(direction) = controller.decide(&self.engine.view()) {
+            self.engine.steer(direction);
+        }
+
+        if self.engine.rules().players() > 1 {
+            if let Some(direction) = self.partner.decide(&self.engine.view_of(1)) {
+                self.engine.steer_snake(1, direction);
+            }
+        }
+
+        for (index, bot) in (self.engine.rules().players()..).zip(&mut self.bots) {
+            if let Some(direction) = bot.decide(&self.engine.view_of(index)) {
+                self.engine.steer_snake(index, direction);
+            }
+        }
+    }
+
+    fn execute_input(&mut self, key_code: VirtualKeyCode) {
+        let action = Action::from_key_for(key_code, self.engine.rules().players(), self.engine.config().grid != Grid::Square && !self.engine.is_over());
+
+        // Whatever dialog is open takes the key before the hotkeys do, so keys typed into a challenge code or a
+        // name are only text
+        if let Some(dialog) = &self.quit_dialog {
+            if let Some(choice) = dialog.handle_input(key_code) {
+                self.quit_dialog = None;
+
+                if choice == QuitChoice::SaveAndQuit {
+                    self.save_replay();
+                }
+
+                if choice != QuitChoice::Stay {
+                    self.end_session();
+                } else if !self.engine.is_over() && !self.paused {
+                    self.resumed_at = Some(self.now()); // Back to the run, with the same countdown as unpausing
+                }
+            }
+        } else if self.load_error.is_some() {
+            self.load_error = None;
+        } else if let Some(select) = &mut self.level_select {
+            if let Some(choice) = select.handle_input(key_code) {
+                self.level_select = None;
+                self.execute_level_choice(choice);
+            }
+        } else if let Some(select) = &mut self.puzzle_select {
+            if let Some(choice) = select.handle_input(key_code) {
+                self.puzzle_select = None;
+                self.execute_puzzle_choice(choice);
+            }
+        } else if let Some(menu) = &mut self.custom_menu {
+            if let Some(choice) = menu.handle_input(key_code) {
+                self.custom_menu = None;
+
+                if let CustomChoice::Play {obstacles, symmetry, length_speed, grid} = choice {
+                    self.config.obstacles = obstacles;
+                    self.config.symmetry = symmetry;
+                    self.config.length_speed = length_speed;
+                    self.config.grid = grid;
+                    self.persist();
+                    self.reset();
+                }
+            }
+        } else if let Some(menu) = &mut self.display_menu {
+            if let Some(choice) = menu.handle_input(key_code) {
+                self.display_menu = None;
+                self.execute_display_choice(choice);
+            }
+        } else if let Some(entry) = &mut self.name_entry {
+            if let Some((leaderboard, record)) = entry.handle_input(key_code) {
+                self.name_entry = None;
+                self.save_data.profile_mut().high_scores.record(leaderboard, record);
+                self.persist();
+            }
+        } else if let Some(menu) = &mut self.profile_menu {
+            let active = self.save_data.active();
+            let profiles = self.save_data.profiles().len();
+
+            if !menu.handle_input(key_code, &mut self.save_data) {
+                self.profile_menu = None;
+            }
+
+            if self.save_data.active() != active || self.save_data.profiles().len() != profiles {
+                self.switch_profile();
+            }
+        } else if let Some(prompt) = &mut self.challenge_prompt {
+            if let Some(entered) = prompt.handle_input(key_code) {
+                self.challenge_prompt = None;
+
+                if let Some(challenge) = entered {
+                    self.play_challenge(challenge);
+                }
+            }
+        } else if action == Some(Action::ToggleDebug) {
+            self.debug_overlay.toggle();
+        } else if action == Some(Action::Screenshot) {
+            // The consoles still hold the last frame drawn, as they aren't cleared until rendering
+            self.tasks.submit(Task::Screenshot(Frame::capture()));
+        } else if action == Some(Action::SaveRecording) {
+            self.save_recording();
+        } else if action == Some(Action::CopyBoard) {
+            self.copy_board();
+        } else if self.editing {
+            if let Some(action) = self.editor.as_mut().and_then(|editor| editor.handle_input(key_code)) {
+                self.execute_editor_action(action);
+            }
+        } else if self.died_at.is_some() {
+            // The game over screen waits until the undo offer is over
+            if action == Some(Action::Undo) {
+                self.undo();
+            } else if key_code == VirtualKeyCode::Escape {
+                self.confirm_quit();
+            }
+        } else if self.heatmap {
+            self.heatmap = false;
+        } else if let Some(changelog) = &mut self.changelog {
+            if !changelog.handle_input(key_code) {
+                self.changelog = None;
+            }
+        } else if key_code == VirtualKeyCode::Return && self.tutorial.as_ref().is_some_and(Tutorial::is_finished) {
+            self.leave_tutorial();
+        } else if key_code == VirtualKeyCode::Return && self.engine.status() == Status::Won && self.campaign.as_ref().is_some_and(|campaign| !campaign.is_last()) {
+            if let Some(campaign) = &mut self.campaign {
+                campaign.advance();
+            }
+
+            self.reset();
+        } else if key_code == VirtualKeyCode::Return && self.engine.status() == Status::Won && self.puzzles.as_ref().is_some_and(|puzzles| !puzzles.is_last()) {
+            if let Some(puzzles) = &mut self.puzzles {
+                puzzles.advance();
+            }
+
+            self.reset();
+        } else if key_code == VirtualKeyCode::Escape && self.engine.is_over() && self.tournament.is_none() {
+            // Backing out of the game over screen ends the session, once the player has confirmed it
+            self.confirm_quit();
+        } else if let Some(action) = action {
+            self.execute_action(action);
+        }
+    }
+
+    fn execute_editor_action(&mut self, action: EditorAction) {
+        let level = match &self.editor {
+            Some(editor) => editor.level(),
+            None => return
+        };
+
+        match action {
+            // A level that fails to load leaves the editor open behind the error, to be fixed
+            EditorAction::Playtest => {
+                self.load_level(level);
+
+                if self.load_error.is_none() {
+                    self.editing = false;
+                    self.show_toast("[F2] Back to the editor".to_string());
+                }
+            },
+            EditorAction::Save => {
+                self.show_toast("Saving level...".to_string());
+                self.tasks.submit(Task::SaveLevel(self.editor.as_ref().map(Editor::level_text).unwrap_or_default()));
+            },
+            EditorAction::Close => self.editing = false
+        }
+    }
+
+    // Press whichever touch button was tapped. Any tap closes the "What's new" screen
+    #[cfg(target_arch = "wasm32")]
+    fn execute_tap(&mut self, tap: (f64, f64)) {
+        if self.quit_dialog.is_some() {
+            self.quit_dialog = None;
+        } else if self.load_error.is_some() {
+            self.load_error = None;
+        } else if self.level_select.is_some() {
+            self.level_select = None;
+        } else if self.puzzle_select.is_some() {
+            self.puzzle_select = None;
+        } else if self.custom_menu.is_some() {
+            self.custom_menu = None;
+        } else if self.display_menu.is_some() {
+            self.display_menu = None;
+        } else if self.died_at.is_some() {
+            self.undo();
+        } else if self.name_entry.is_some() {
+            self.skip_name_entry();
+        } else if self.changelog.is_some() || self.challenge_prompt.is_some() || self.heatmap || self.profile_menu.is_some() || self.editing {
+            self.editing = false;
+            self.changelog = None;
+            self.profile_menu = None;
+            self.heatmap = false;
+            self.challenge_prompt = None;
+        } else if let Some(button) = TouchButton::at(tap, self.engine.is_over(), self.view) {
+            self.execute_action(button.action());
+        }
+    }
+
+    fn execute_action(&mut self, action: Action) {
+        if !self.engine.is_over() {
+            match action {
+                Action::Steer(direction) if !self.paused && self.playback.is_none() => {
+                    self.assist = false; // Steering hands control back to the player
+                    self.keyboard.press(direction)
+                },
+                Action::SteerPartner(direction) if !self.paused && self.playback.is_none() => self.partner.press(direction),
+                Action::ToggleAssist if !self.paused && self.playback.is_none() && self.tournament.is_none() && self.tutorial.is_none() => {
+                    self.assist = !self.assist;
+                },
+                Action::SlowDown if self.engine.rules().is_practice() => self.set_practice_speed(self.practice_speed / 2.0),
+                Action::SpeedUp if self.engine.rules().is_practice() => self.set_practice_speed(self.practice_speed * 2.0),
+                Action::ToggleHint => self.toggle_fruit_hint(),
+                Action::Tutorial if self.tutorial.is_some() => self.leave_tutorial(),
+                Action::Tutorial if self.tournament.is_none() => self.start_tutorial(),
+                // A playtest stays where it is behind the editor, and carries on if it's closed
+                Action::Editor if self.level_name.as_deref() == Some(Editor::NAME) => self.editing = true,
+                Action::Checkpoint if self.engine.rules().is_practice() && self.playback.is_none() => {
+                    self.checkpoint = Some(self.engine.ticks());
+                    self.show_toast("Checkpoint set".to_string());
+                },
+                // Held rather than pressed, so a stray key doesn't throw the run away. Tournament turns
+                // can't be retaken
+                Action::Restart if self.tournament.is_none() && self.restart_held_since.is_none() => {
+                    self.restart_held_since = Some(self.now());
+                },
+                // A run paused when the window lost focus can always be resumed, whatever the mode
+                Action::TogglePause if self.paused || self.engine.rules().can_pause() => {
+                    self.paused = !self.paused;
+                    self.resumed_at = if self.paused { None } else { Some(self.now()) };
+                    self.notify(if self.paused {
+                        GameEvent::Paused
+                    } else {
+                        GameEvent::Resumed
+                    });
+                }
+                _ => {}
+            }
+        } else if let Some(tournament) = &self.tournament {
+            // Settings stay as they are until the tournament is left
+            match action {
+                Action::Restart => {
+                    if tournament.winner().is_some() {
+                        self.tournament = None;
+                    }
+
+                    self.reset();
+                },
+                Action::PlayTournament => if let Some(tournament) = self.tournament.take() {
+                    if tournament.winner().is_some() {
+                        self.start_tournament(tournament.players(), tournament.best_of());
+                    }
+                },
+                _ => {}
+            }
+        } else if self.campaign.is_some() {
+            // The campaign keeps to its levels until it's left from the level select screen
+            match action {
+                Action::Restart => self.reset(),
+                Action::Campaign => self.open_level_select(),
+                _ => {}
+            }
+        } else if self.puzzles.is_some() {
+            // As does a run through the puzzles
+            match action {
+                Action::Restart => self.reset(),
+                Action::Puzzles => self.open_puzzle_select(),
+                _ => {}
+            }
+        } else {
+            match action {
+                Action::Restart => self.reset(),
+                Action::Campaign if self.custom_rules.is_none() && self.level_name.is_none() => self.open_level_select(),
+                Action::Puzzles if self.custom_rules.is_none() && self.level_name.is_none() => self.open_puzzle_select(),
+                Action::NextMode if self.custom_rules.is_none() => {
+                    // Modes still locked behind the campaign are skipped, classic always being unlocked
+                    self.config.mode = self.config.mode.next();
+
+                    while !self.save_data.profile().is_unlocked(self.config.mode) {
+                        self.config.mode = self.config.mode.next();
+                    }
+
+                    self.persist();
+                },
+                Action::NextScoring => {
+                    self.config.scoring_policy = self.config.scoring_policy.next();
+                    self.persist();
+                },
+                Action::NextSkin => {
+                    self.set_skin(self.config.skin.next(|skin| self.can_draw(skin)));
+                    self.persist();
+                },
+                Action::NextDifficulty => {
+                    self.config.difficulty = self.config.difficulty.next();
+                    self.persist();
+                },
+                Action::NextWinCondition => {
+                    self.config.win_condition = self.config.win_condition.next();
+                    self.persist();
+                },
+                Action::NextTable => self.high_score_tab = self.high_score_tab.next(),
+                // Left and right flick through the other leaderboards
+                Action::Steer(direction) | Action::SteerPartner(direction) if direction == Direction::West || direction == Direction::East => {
+                    self.high_score_leaderboard = Some(self.shown_leaderboard().step(direction == Direction::East));
+                },
+                Action::WhatsNew => self.changelog = Some(ChangelogView::new(changelog::CURRENT_VERSION)),
+                Action::SaveReplay => self.save_replay(),
+                Action::LoadReplay => self.load_replay(),
+                Action::ShowDeaths => self.heatmap = true,
+                Action::ToggleHint => self.toggle_fruit_hint(),
+                Action::Tutorial => self.start_tutorial(),
+                Action::Editor if self.custom_rules.is_none() => {
+                    self.editor.get_or_insert_with(|| Editor::new(Self::MAP_DIMENSIONS));
+                    self.editing = true;
+                },
+                Action::Profiles => self.profile_menu = Some(ProfileMenu::new(&self.save_data)),
+                Action::CustomGame if self.custom_rules.is_none() => self.custom_menu = Some(CustomGameMenu::new(&self.config)),
+                Action::Display => self.display_menu = Some(DisplayMenu::new(&self.display, &self.window, self.custom_font.is_some())),
+                Action::PlayTournament if self.custom_rules.is_none() && self.level_name.is_none() => {
+                    self.start_tournament(Tournament::PLAYERS, Tournament::BEST_OF);
+                },
+                Action::PlayChallenge if self.custom_rules.is_none() && self.level_name.is_none() => {
+                    self.challenge_prompt = Some(ChallengePrompt::new(Challenge::of(self.engine.config())));
+                },
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_input(&mut self, ctx: &mut BTerm) {
+        // Text typed along with the key that opened the prompt isn't part of the code
+        let typing = self.challenge_prompt.is_some() || self.name_entry.is_some() || self.profile_menu.as_ref().is_some_and(ProfileMenu::is_naming);
+
+        for event in self.input.poll(ctx) {
+            match event {
+                InputEvent::Key(_) | InputEvent::Quit if self.closing => ctx.quit(),
+                InputEvent::Key(key_code) => self.execute_input(key_code),
+                InputEvent::Character(character) if typing => if let Some(entry) = &mut self.name_entry {
+                    entry.type_character(character);
+                } else if let Some(prompt) = &mut self.challenge_prompt {
+                    prompt.type_character(character);
+                } else if let Some(menu) = &mut self.profile_menu {
+                    menu.type_character(character);
+                },
+                InputEvent::Character(_) => {},
+                #[cfg(target_arch = "wasm32")]
+                InputEvent::Tap(x, y) => self.execute_tap((x, y)),
+                InputEvent::FocusLost => self.pause_on_focus_loss(),
+                InputEvent::Quit => self.confirm_quit()
+            }
+        }
+
+        // The editor paints wherever the mouse is held down over the map
+        if let (true, Some(editor)) = (self.editing, &mut self.editor) {
+            let (screen, left, right) = self.input.mouse(Self::MAP_LAYER);
+            let tile = editor.camera(self.view).to_map(screen);
+
+            editor.handle_mouse(tile, left, right);
+        }
+    }
+
+    fn handle_logic(&mut self) {
+        let was_over = self.engine.is_over();
+
+        self.update_snake();
+        self.record_splits();
+
+        // Stop the clock and record the run as soon as it's over, unless it might yet be taken back
+        if !was_over && self.engine.is_over() {
+            self.run_timer.stop(&self.clock);
+
+            // The tutorial starts the lesson over rather than ending the run
+            if self.tutorial.is_some() {
+                self.reset();
+                self.show_toast("Try again".to_string());
+            } else if self.can_undo() {
+                self.died_at = Some(self.now());
+            } else {
+                self.finish_run();
+            }
+        }
+    }
+
+    fn handle_rendering(&mut self, ctx: &mut BTerm) {
+        ctx.set_active_console(Self::HUD_LAYER);
+        ctx.cls();
+
+        if !self.engine.is_over() && !self.paused && !self.is_in_menu() {
+            self.render_hud(ctx);
+
+            if let Some(tutorial) = &self.tutorial {
+                tutorial.render(ctx, self.view);
+            }
+
+            if let Some(campaign) = &self.campaign {
+                campaign.render(ctx, self.view, &self.engine.run_state());
+            }
+
+            if let Some(puzzles) = &self.puzzles {
+                puzzles.render(ctx, self.view, self.engine.ticks());
+            }
+
+            if let Some(left) = self.countdown_left() {
+                ctx.print_color_centered_at(self.centre().0, self.centre().1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, left.ceil().to_string());
+            }
+        }
+
+        if let Some(progress) = self.restart_progress() {
+            let filled = (progress * Self::RESTART_BAR_WIDTH as f64) as usize;
+            let bar = format!("Restart [{}{}]", "#".repeat(filled), " ".repeat(Self::RESTART_BAR_WIDTH - filled));
+
+            ctx.print_color_centered_at(self.centre().0, 2, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, bar);
+        }
+
+        if let Some((message, shown_at)) = &self.toast {
+            if self.seconds_since(*shown_at) < Self::TOAST_SECONDS {
+                ctx.print_color_centered_at(self.centre().0, self.view.1 - 2, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, message);
+            }
+        }
+
+        if self.debug_overlay.is_visible() {
+            let info = DebugInfo {
+                fps: ctx.fps,
+                snake_length: self.engine.snake().len(),
+                head_position: self.engine.snake().front().map(|head| (head.position.x, head.position.y)),
+                seed: self.engine.config().seed,
+                target_rate: self.engine.slithers_per_second() * self.speed_multiplier(),
+                calibrated_rate: self.calibration.measured_rate(),
+                speed_correction: self.calibration.correction(),
+                free_cells: self.engine.empty_points().len()
+            };
+
+            self.debug_overlay.render(ctx, &info);
+        }
+
+        if let (true, Some(editor)) = (self.editing, &self.editor) {
+            editor.render_help(ctx, self.view);
+        }
+
+        // Over the board, which is drawn in the font and at the size being picked
+        if let Some(menu) = &self.display_menu {
+            menu.render(ctx, self.centre());
+        }
+
+        ctx.set_active_console(Self::MAP_LAYER);
+        ctx.cls_bg(Self::BACKGROUND_COLOUR);
+
+        let centre = self.centre();
+        let camera = self.camera();
+
+        if self.closing {
+            self.session.render(ctx, centre);
+        } else if let Some(dialog) = &self.quit_dialog {
+            dialog.render(ctx, centre);
+        } else if let Some(dialog) = &self.load_error {
+            dialog.render(ctx, centre, self.config.skin);
+        } else if let Some(select) = &self.level_select {
+            select.render(ctx, centre);
+        } else if let Some(select) = &self.puzzle_select {
+            select.render(ctx, centre);
+        } else if let Some(menu) = &self.custom_menu {
+            menu.render(ctx, centre, self.ascii_only);
+        } else if let Some(changelog) = &self.changelog {
+            changelog.render(ctx, self.config.skin);
+        } else if let Some(entry) = &self.name_entry {
+            entry.render(ctx, centre);
+        } else if let Some(menu) = &self.profile_menu {
+            menu.render(ctx, centre, &self.save_data);
+        } else if let Some(prompt) = &self.challenge_prompt {
+            prompt.render(ctx, centre);
+        } else if let (true, Some(editor)) = (self.editing, &self.editor) {
+            editor.render(ctx, self.config.skin, &editor.camera(self.view));
+        } else if self.heatmap {
+            self.save_data.profile().deaths.render(ctx, &camera);
+        } else if self.paused {
+            ctx.print_color_centered_at(centre.0, centre.1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "PAUSED".to_string());
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "[P] Resume");
+            ctx.print_color_centered_at(centre.0, centre.1 + 4, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[J] Fruit hint: {}", if self.save_data.profile().fruit_hint { "On" } else { "Off" }));
+        } else {
+            self.engine.arena().render_edges(ctx, &camera);
+            self.scene().render(ctx, &camera);
+
+            // If the game is over, print end-game information
+            if let Some(died_at) = self.died_at {
+                let seconds_left = (Self::UNDO_SECONDS - self.seconds_since(died_at)).max(0.0);
+
+                ctx.print_color_centered_at(centre.0, centre.1 - 1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, match self.checkpoint {
+                    Some(_) => "[U] Back to checkpoint".to_string(),
+                    None => format!("[U] Undo ({} left)", self.undos_left)
+                });
+                ctx.print_color_centered_at(centre.0, centre.1 + 1, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, format!("{}", seconds_left.ceil()));
+            } else if self.display_menu.is_some() {
+                // Just the board, for the display menu to preview
+            } else if let (true, Some(tournament)) = (self.engine.is_over(), &self.tournament) {
+                tournament.render(ctx, centre);
+            } else if let (true, Some(campaign)) = (self.engine.is_over(), &self.campaign) {
+                let stars = Some(campaign.stars(self.engine.run_state().time)).filter(|_| self.engine.status() == Status::Won);
+
+                campaign.render_result(ctx, centre, stars);
+            } else if let (true, Some(puzzles)) = (self.engine.is_over(), &self.puzzles) {
+                let stars = Some(puzzles.stars(self.engine.ticks())).filter(|_| self.engine.status() == Status::Won);
+
+                puzzles.render_result(ctx, centre, self.engine.ticks(), stars);
+            } else if self.engine.is_over() {
+                if let Some(level_name) = &self.level_name {
+                    ctx.print_color_centered_at(centre.0, centre.1 - 11, Hazard::COLOUR, Self::BACKGROUND_COLOUR, level_name);
+                } else {
+                    ctx.print_color_centered_at(centre.0, centre.1 - 11, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, format!("[O] {}", self.save_data.profile().name));
+                }
+
+                ctx.print_color_centered_at(centre.0, centre.1 - 10, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "GAME OVER".to_string());
+                ctx.print_color_centered_at(centre.0, centre.1 - 8, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, if self.engine.status() == Status::Won {
+                    "You won!".to_string()
+                } else {
+                    format!("Score: {}", self.engine.score().points())
+                });
+
+                if self.engine.rules().is_practice() {
+                    ctx.print_color_centered_at(centre.0, centre.1 - 9, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, "(practice, not recorded)");
+                } else if self.engine.score().is_assisted() {
+                    ctx.print_color_centered_at(centre.0, centre.1 - 9, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, "(assisted)");
+                }
+
+                self.render_splits(ctx, centre.1 - 7);
+                ctx.print_color_centered_at(centre.0, centre.1 + 4, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "[R] Restart [H] Challenge");
+
+                if self.custom_rules.is_none() {
+                    self.render_high_scores(ctx, centre.1 - 2);
+                    ctx.print_color_centered_at(centre.0, centre.1 + 5, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[M] Mode: {}", self.config.mode.name()));
+                } else {
+                    ctx.print_color_centered_at(centre.0, centre.1 + 5, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("Mode: {}", self.engine.rules().name()));
+                }
+                ctx.print_color_centered_at(centre.0, centre.1 + 6, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[F] Scoring: {}", self.config.scoring_policy.name()));
+                ctx.print_color_centered_at(centre.0, centre.1 + 7, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[K] Skin: {}", self.config.skin.name));
+                ctx.print_color_centered_at(centre.0, centre.1 + 8, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[B] Bot: {} [Y] Walls", self.config.difficulty.name()));
+                ctx.print_color_centered_at(centre.0, centre.1 + 9, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "[N] What's new [X] Deaths");
+                ctx.print_color_centered_at(centre.0, centre.1 + 10, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, "[Tab] Table [T] Tourney");
+                ctx.print_color_centered_at(centre.0, centre.1 + 11, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("[V] Versus: {}", self.config.win_condition.describe()));
+                ctx.print_color_centered_at(centre.0, centre.1 + 12, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, match (self.can_save_replay(), self.recorder.is_some()) {
+                    (true, true) => "[E]/[L] Replay [G] GIF",
+                    (true, false) => "[E] Save [L] Load replay",
+                    (false, true) => "[L] Replay [G] GIF",
+                    (false, false) => "[L] Load replay"
+                });
+            }
+        }
+
+        if self.touch_controls && !self.is_in_menu() {
+            for button in TouchButton::shown(self.engine.is_over()) {
+                button.render(ctx, self.view, self.config.skin);
+            }
+        }
+    }
+
+    // The middle of the window, which the menus are laid out around
+    fn centre(&self) -> (u32, u32) {
+        let (width, height) = self.view;
+
+        (width / 2, height / 2)
+    }
+
+    // Everything on the board, on the layers it's drawn in. Fruit, power-ups and hazards are left off
+    // while anything is shown over the board
+    fn scene(&self) -> Scene {
+        let skin = self.config.skin;
+        let mut scene = Scene::default();
+
+        scene.extend(self.engine.arena().objects(skin));
+        scene.extend(self.engine.cell_tags().objects(skin));
+
+        for (index, snake) in self.engine.snakes().iter().enumerate() {
+            scene.extend(snake.objects(index));
+        }
+
+        if self.died_at.is_some() || self.display_menu.is_some() || self.engine.is_over() {
+            return scene;
+        }
+
+        for fruit in self.engine.fruits().iter().chain(self.engine.cluster_fruits()) {
+            let rot = self.engine.rot(fruit);
+            let object = Object::new(fruit.position.into(), skin.fruit(fruit.kind), fruit.kind.rotten_colour(rot));
+
+            scene.add(self.animator.animate(match rot {
+                Some(_) => object,
+                None => object.animated(fruit.kind.shimmer())
+            }));
+        }
+
+        if let Some(pickup) = self.engine.pickup() {
+            let glyph = skin.power_up(pickup.power_up);
+            let object = Object::new(pickup.position.into(), glyph, pickup.power_up.colour());
+            let left = PowerUp::PICKUP_LIFETIME - (self.engine.run_state().time - pickup.spawned_at);
+
+            scene.add(self.animator.animate(match left < PowerUp::BLINK_SECONDS {
+                true => object.animated(Animation::blink(glyph, PowerUp::BLINK_FRAME_SECONDS)),
+                false => object
+            }));
+        }
+
+        scene.extend(self.engine.frenzy().objects(skin));
+
+        for pellet in self.engine.pellets() {
+            scene.add(Object::new(pellet.position.into(), skin.bonus_fruit, Snake::player_colour(pellet.snake)));
+        }
+
+        for hazard in self.engine.hazards() {
+            scene.extend(hazard.objects(skin, self.engine.config().dimensions));
+        }
+
+        scene
+    }
+
+    // The part of the map on screen, following the player's head
+    fn camera(&self) -> Camera {
+        let map = self.engine.config().dimensions;
+        let focus = self.engine.snake().front()
+            .map(|head| head.position)
+            .unwrap_or(Position::new(map.0 as i32 / 2, map.1 as i32 / 2));
+        let mut camera = Camera::new(map, self.view).with_grid(self.engine.config().grid);
+
+        camera.follow(focus);
+        camera
+    }
+
+    // Print the score and combo multiplier along the top of the HUD layer
+    fn render_hud(&self, ctx: &mut BTerm) {
+        let dimensions = self.view;
+        let centre = self.centre();
+
+        // Confusion turns the score's colours inside out, to go with the controls
+        let (foreground, background) = if self.engine.snake().has_effect(PowerUp::Confusion) {
+            (Self::BACKGROUND_COLOUR, PowerUp::Confusion.colour())
+        } else {
+            (RGB::named(bracket_terminal::prelude::WHITE), Self::BACKGROUND_COLOUR)
+        };
+
+        ctx.print_color(0, 0, foreground, background, format!("Score: {}", self.engine.score().points()));
+
+        if self.engine.score().multiplier() > 1 {
+            ctx.print_color_right(dimensions.0, 0, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, format!("x{}", self.engine.score().multiplier()));
+        }
+
+        // The pace creeps up as the snake grows, so the player can see it coming
+        if !self.engine.config().length_speed.is_off() {
+            ctx.print_color_right(dimensions.0, 1, Hazard::COLOUR, Self::BACKGROUND_COLOUR, format!("Pace x{:.1}", self.engine.length_factor()));
+        }
+
+        if let Some(seconds_left) = self.engine.frenzy_seconds_left() {
+            ctx.print_color_right(dimensions.0, dimensions.1 - 1, Frenzy::COLOUR, Self::BACKGROUND_COLOUR, format!("FRENZY! {}s", seconds_left.ceil()));
+        }
+
+        ctx.print_color(0, dimensions.1 - 1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, clock::format_duration(self.run_timer.seconds(&self.clock)));
+
+        if self.engine.rules().lives() > 1 {
+            ctx.print_color_centered_at(centre.0, 0, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, self.config.skin.life.to_string().repeat(self.engine.lives()));
+        }
+
+        if self.assist {
+            ctx.print_color_centered_at(centre.0, 1, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, "AUTOPILOT");
+        } else if self.playback.is_some() {
+            ctx.print_color_centered_at(centre.0, 1, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, "REPLAY");
+        } else if let Some(tournament) = &self.tournament {
+            ctx.print_color_centered_at(centre.0, 1, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, tournament.describe_turn());
+        } else if self.engine.rules().is_practice() {
+            ctx.print_color_centered_at(centre.0, 1, Self::ASSIST_COLOUR, Self::BACKGROUND_COLOUR, format!("PRACTICE x{}", self.practice_speed));
+        }
+
+        if self.engine.rules().rivals() && self.campaign.is_none() {
+            let run = self.engine.run_state();
+
+            ctx.print_color_centered_at(centre.0, 2, Snake::player_colour(1), Self::BACKGROUND_COLOUR, run.win_condition.progress(&run));
+        }
+
+        if self.save_data.profile().fruit_hint {
+            self.render_fruit_hint(ctx);
+        }
+
+        // Maps bigger than the window get a minimap in the bottom-right corner
+        if self.camera().is_scrolling() {
+            let minimap = Minimap::of(&self.engine);
+            let size = minimap.dimensions();
+
+            minimap.render(ctx, dimensions.0.saturating_sub(size.0), dimensions.1.saturating_sub(size.1 + 1), self.ascii_only);
+        }
+
+        if let Some(effect) = self.engine.snake().effects().last() {
+            let effects: Vec<String> = self.engine.snake().effects().iter()
+                .map(|effect| format!("{} {}", self.config.skin.power_up(effect.power_up), effect.describe()))
+                .collect();
+
+            ctx.print_color_centered_at(centre.0, dimensions.1 - 1, effect.power_up.colour(), Self::BACKGROUND_COLOUR, effects.join(" "));
+        }
+    }
+
+    // Draw an arrow on the tile beside the snake's head, pointing the way to the nearest fruit
+    fn render_fruit_hint(&self, ctx: &mut BTerm) {
+        let head = match self.engine.snake().front() {
+            Some(head) => head.position,
+            None => return
+        };
+        let nearest = self.engine.fruits().iter()
+            .chain(self.engine.cluster_fruits())
+            .map(|fruit| fruit.position)
+            .filter(|position| position.in_bounds(self.engine.config().dimensions))
+            .min_by_key(|position| position.manhattan_distance(head));
+        let fruit = match nearest {
+            Some(fruit) if fruit.manhattan_distance(head) > 1 => fruit,
+            _ => return // Right in front of the snake, or nowhere to be seen
+        };
+
+        let (dx, dy) = (fruit.x - head.x, fruit.y - head.y);
+        let direction = if dx.abs() >= dy.abs() {
+            if dx > 0 { Direction::East } else { Direction::West }
+        } else if dy > 0 {
+            Direction::South
+        } else {
+            Direction::North
+        };
+        let arrow = match (direction, self.ascii_only) {
+            (Direction::North, false) => '↑',
+            (Direction::South, false) => '↓',
+            (Direction::East, false) => '→',
+            (Direction::West, false) => '←',
+            (Direction::North, true) => '^',
+            (Direction::South, true) => 'v',
+            (Direction::East, true) => '>',
+            (Direction::West, true) => '<',
+            (diagonal, _) => self.config.skin.straight(diagonal) // The hint only ever points along the grid
+        };
+        let tile = self.engine.config().grid.step(head, direction);
+
+        Object::new(tile.into(), arrow, Self::ASSIST_COLOUR).on(Layer::Overlay).render(ctx, &self.camera());
+    }
+
+    // Print the run time and its splits, starting at the given row
+    fn render_splits(&self, ctx: &mut BTerm, y: u32) {
+        let centre = self.centre();
+
+        ctx.print_color_centered_at(centre.0, y, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("Time: {}", clock::format_duration(self.run_timer.seconds(&self.clock))));
+
+        for (row, (milestone, seconds)) in self.splits.iter().enumerate() {
+            ctx.print_color_centered_at(centre.0, y + 1 + row as u32, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, format!("{:>2} fruit {:>9}", milestone, clock::format_duration(*seconds)));
+        }
+    }
+
+    // The leaderboard on the game over screen
+    fn shown_leaderboard(&self) -> Leaderboard {
+        // Practice runs have no tables of their own, so the classic ones are shown in their place
+        self.high_score_leaderboard.unwrap_or_else(|| if self.config.mode.rules().is_practice() {
+            Leaderboard::new(Mode::Classic, self.config.difficulty)
+        } else {
+            Leaderboard::of(&self.config)
+        })
+    }
+
+    // Print the high-score table of the leaderboard being shown, starting at the given row,
+    // with a tab for each metric the runs are ranked by
+    fn render_high_scores(&self, ctx: &mut BTerm, y: u32) {
+        let centre = self.centre();
+        let tabs = Metric::ALL.iter()
+            .map(|metric| if *metric == self.high_score_tab {
+                format!("[{}]", metric.name())
+            } else {
+                format!(" {} ", metric.name())
+            })
+            .collect::<String>();
+
+        ctx.print_color_centered_at(centre.0, y - 1, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, format!("< {} scores >", self.shown_leaderboard().name()));
+        ctx.print_color_centered_at(centre.0, y, Self::FRUIT_COLOUR, Self::BACKGROUND_COLOUR, tabs);
+
+        for rank in 0..HighScores::CAPACITY {
+            let entry = match self.save_data.profile().high_scores.table(self.shown_leaderboard(), self.high_score_tab).get(rank) {
+                Some(record) => format!("{}. {:<8} {:>4}{}", rank + 1, record.name, self.high_score_tab.value(record), if record.assisted { "*" } else { " " }),
+                None => format!("{}. {:<8} {:>4} ", rank + 1, "", "-")
+            };
+
+            ctx.print_color_centered_at(centre.0, y + 1 + rank as u32, bracket_terminal::prelude::WHITE, Self::BACKGROUND_COLOUR, entry);
+        }
+    }
+}
+
+impl GameState for Game {
+    fn tick(&mut self, ctx: &mut BTerm) {
+        self.handle_tasks();
+        self.handle_input(ctx);
+        self.update_restart();
+        self.update_undo();
+        self.update_run_timer();
+        self.debug_overlay.update(&self.clock);
+        self.animator.update(&self.clock);
+
+        if !self.paused && !self.is_in_menu() && self.countdown_left().is_none() {
+            self.handle_logic();
+        } else {
+            self.previous_frame_time = self.now(); // Time spent paused isn't owed to the simulation
+            self.calibration.restart(&self.clock);
+        }
+
+        self.apply_display(ctx);
+        self.update_title();
+        self.handle_rendering(ctx);
+        self.record_frame();
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
 }
\ No newline at end of file
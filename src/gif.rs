@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+// Writes an animated GIF89a that loops forever, with one global palette of up to 256 colours shared by
+// every frame
+pub struct GifEncoder {
+    bytes: Vec<u8>,
+    palette_bits: u8 // log2 of the palette's size, as written to the file
+}
+
+impl GifEncoder {
+    pub const MAX_COLOURS: usize = 256;
+    const MAX_CODE_BITS: u8 = 12;
+
+    pub fn new(width: u16, height: u16, palette: &[[u8; 3]]) -> Self {
+        let palette_bits = (1..=8).find(|bits| 1 << bits >= palette.len()).unwrap_or(8);
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(b"GIF89a");
+        bytes.extend_from_slice(&width.to_le_bytes());
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.push(0x80 | 0x70 | (palette_bits - 1)); // A global colour table, at 8 bits per channel
+        bytes.extend_from_slice(&[0, 0]); // Background colour and pixel aspect ratio
+
+        for index in 0..1 << palette_bits {
+            bytes.extend_from_slice(&palette.get(index).copied().unwrap_or([0, 0, 0]));
+        }
+
+        // Loop forever
+        bytes.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        bytes.extend_from_slice(b"NETSCAPE2.0");
+        bytes.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        Self {
+            bytes,
+            palette_bits
+        }
+    }
+
+    // Draw a `width` x `height` block of palette indices at (`left`, `top`), over what earlier frames
+    // left behind, and hold it for `delay` hundredths of a second
+    pub fn add_frame(&mut self, (left, top): (u16, u16), (width, height): (u16, u16), indices: &[u8], delay: u16) {
+        self.bytes.extend_from_slice(&[0x21, 0xF9, 0x04, 0x04]); // Leave the frame in place for the next
+        self.bytes.extend_from_slice(&delay.to_le_bytes());
+        self.bytes.extend_from_slice(&[0x00, 0x00]);
+
+        self.bytes.push(0x2C);
+
+        for value in [left, top, width, height].iter() {
+            self.bytes.extend_from_slice(&value.to_le_bytes());
+        }
+
+        self.bytes.push(0x00); // No local colour table or interlacing
+
+        let minimum_code_size = self.palette_bits.max(2);
+        let data = lzw_compress(indices, minimum_code_size);
+
+        self.bytes.push(minimum_code_size);
+
+        for block in data.chunks(255) {
+            self.bytes.push(block.len() as u8);
+            self.bytes.extend_from_slice(block);
+        }
+
+        self.bytes.push(0x00);
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(0x3B);
+        self.bytes
+    }
+}
+
+// Packs variable-width codes into bytes, least significant bit first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+// GIF's flavour of LZW, starting over with a clear code whenever the code table fills up
+fn lzw_compress(indices: &[u8], minimum_code_size: u8) -> Vec<u8> {
+    let clear_code = 1u16 << minimum_code_size;
+    let end_code = clear_code + 1;
+
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end_code + 1;
+    let mut width = minimum_code_size + 1;
+
+    writer.write(clear_code, width);
+
+    let mut pixels = indices.iter().copied();
+    let mut current = match pixels.next() {
+        Some(index) => index as u16,
+        None => {
+            writer.write(end_code, width);
+            return writer.finish();
+        }
+    };
+
+    for index in pixels {
+        if let Some(&code) = table.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+
+        writer.write(current, width);
+
+        // The decoder widens its codes as soon as the table holds one that needs the extra bit
+        if next_code >= 1 << width && width < GifEncoder::MAX_CODE_BITS {
+            width += 1;
+        }
+
+        if next_code < 1 << GifEncoder::MAX_CODE_BITS {
+            table.insert((current, index), next_code);
+            next_code += 1;
+        } else {
+            writer.write(clear_code, width);
+            table.clear();
+            next_code = end_code + 1;
+            width = minimum_code_size + 1;
+        }
+
+        current = index as u16;
+    }
+
+    writer.write(current, width);
+
+    if next_code >= 1 << width && width < GifEncoder::MAX_CODE_BITS {
+        width += 1;
+    }
+
+    writer.write(end_code, width);
+
+    writer.finish()
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB
+};
+
+use crate::game::Game;
+use crate::camera::Camera;
+use crate::engine::Position;
+
+// Where the player has died, over every session, for the heatmap screen. Deaths are counted per tile,
+// whatever the size of the map they happened on
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct DeathMap {
+    deaths: HashMap<Position, usize>
+}
+
+impl DeathMap {
+    pub const HOTTEST_COLOUR: RGB = RGB {r: 1.0, g: 0.2, b: 0.1};
+
+    pub fn record(&mut self, position: Position) {
+        self.add(position, 1);
+    }
+
+    pub fn add(&mut self, position: Position, count: usize) {
+        *self.deaths.entry(position).or_insert(0) += count;
+    }
+
+    pub fn count(&self, position: Position) -> usize {
+        self.deaths.get(&position).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.deaths.values().sum()
+    }
+
+    // Every tile died on, with how often, in reading order
+    pub fn tiles(&self) -> Vec<(Position, usize)> {
+        let mut tiles: Vec<(Position, usize)> = self.deaths.iter().map(|(position, count)| (*position, *count)).collect();
+
+        tiles.sort_by_key(|(position, _)| (position.y, position.x));
+        tiles
+    }
+
+    // Shade every tile of the map in view by how often the player has died there, relative to the deadliest
+    // on the whole map
+    pub fn render(&self, ctx: &mut BTerm, camera: &Camera) {
+        let on_map: Vec<(Position, usize)> = self.tiles().into_iter().filter(|(position, _)| position.in_bounds(camera.map())).collect();
+        let hottest = on_map.iter().map(|(_, count)| *count).max().unwrap_or(0);
+
+        for (position, count) in &on_map {
+            let heat = *count as f32 / hottest as f32;
+
+            if let Some(screen) = camera.to_screen(*position) {
+                ctx.set_bg(screen.x, screen.y, Game::BACKGROUND_COLOUR.lerp(Self::HOTTEST_COLOUR, heat));
+            }
+        }
+
+        let white = bracket_terminal::prelude::WHITE;
+        let dimensions = camera.view();
+        let centre = (dimensions.0 / 2, dimensions.1 / 2);
+
+        ctx.print_color_centered_at(centre.0, 0, white, Game::BACKGROUND_COLOUR, format!("DEATHS: {}", self.total()));
+
+        if hottest == 0 {
+            ctx.print_color_centered_at(centre.0, centre.1, white, Game::BACKGROUND_COLOUR, "None yet");
+        }
+
+        ctx.print_color_centered_at(centre.0, dimensions.1 - 1, white, Game::BACKGROUND_COLOUR, "Press any key");
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::engine::Config;
+use crate::engine::mode::Mode;
+use crate::engine::difficulty::Difficulty;
+use crate::save::Profile;
+use crate::ui::TextField;
+
+// What a high-score table ranks runs by
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Metric {
+    #[default]
+    Score,
+    Length // The longest the snake grew during the run
+}
+
+// Which high-score table a run goes in: one for each mode, split by how well the bots play in modes
+// against them, where it makes all the difference
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Leaderboard {
+    pub mode: Mode,
+    pub difficulty: Option<Difficulty> // Only for modes with rival bots
+}
+
+impl Leaderboard {
+    pub fn new(mode: Mode, difficulty: Difficulty) -> Self {
+        Self {
+            mode,
+            difficulty: Some(difficulty).filter(|_| mode.rules().rivals())
+        }
+    }
+
+    // The leaderboard that runs set up by `config` are ranked on
+    pub fn of(config: &Config) -> Self {
+        Self::new(config.mode, config.difficulty)
+    }
+
+    // Whether runs set up by `config` are ranked at all. The tables only keep modes and bots apart, so runs
+    // played on anything but the default map, pace and scoring, or laid out by a level, are left off
+    // rather than ranked against runs that played out differently
+    pub fn ranks(config: &Config) -> bool {
+        let default = Config::default();
+
+        config.dimensions == default.dimensions &&
+        config.scoring_policy == default.scoring_policy &&
+        config.speed == default.speed &&
+        config.speed_curve == default.speed_curve &&
+        config.length_speed == default.length_speed &&
+        config.power_ups == default.power_ups &&
+        config.win_condition == default.win_condition &&
+        config.obstacles == default.obstacles &&
+        config.symmetry == default.symmetry &&
+        config.grid == default.grid &&
+        config.arena == default.arena &&
+        config.edges == default.edges &&
+        config.fruits == default.fruits &&
+        config.schedule.is_empty() &&
+        config.tags.is_empty()
+    }
+
+    // Every leaderboard, in the order they're flicked through
+    pub fn all() -> Vec<Self> {
+        let mut leaderboards = Vec::new();
+
+        for mode in Mode::ALL.iter().copied().filter(|mode| !mode.rules().is_practice()) {
+            if mode.rules().rivals() {
+                leaderboards.extend(Difficulty::ALL.iter().map(|difficulty| Self::new(mode, *difficulty)));
+            } else {
+                leaderboards.push(Self::new(mode, Difficulty::default()));
+            }
+        }
+
+        leaderboards
+    }
+
+    pub fn step(self, forward: bool) -> Self {
+        let leaderboards = Self::all();
+        let index = leaderboards.iter().position(|leaderboard| *leaderboard == self).unwrap_or(0);
+
+        leaderboards[if forward { index + 1 } else { index + leaderboards.len() - 1 } % leaderboards.len()]
+    }
+
+    pub fn name(self) -> String {
+        match self.difficulty {
+            Some(difficulty) => format!("{} {}", self.mode.name(), difficulty.name()),
+            None => self.mode.name().to_string()
+        }
+    }
+
+    // How the leaderboard is written in the save file: the mode, then the difficulty after a colon if there is one
+    pub fn key(self) -> String {
+        match self.difficulty {
+            Some(difficulty) => format!("{}:{}", self.mode.name(), difficulty.name()),
+            None => self.mode.name().to_string()
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        let (mode, difficulty) = match key.split_once(':') {
+            Some((mode, difficulty)) => (mode, Some(difficulty)),
+            None => (key, None)
+        };
+        let mode = *Mode::ALL.iter().find(|candidate| candidate.name() == mode)?;
+        let difficulty = match difficulty {
+            Some(name) => *Difficulty::ALL.iter().find(|difficulty| difficulty.name() == name)?,
+            None => Difficulty::default() // Saved before the bots' tables were kept apart
+        };
+
+        Some(Self::new(mode, difficulty))
+    }
+}
+
+// The results of a single run
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ScoreRecord {
+    pub name: String, // Whoever set it, as entered when it made the table
+    pub score: usize,
+    pub length: usize,
+    pub assisted: bool // Played at least partly by the autopilot
+}
+
+impl Metric {
+    pub const ALL: [Metric; 2] = [Metric::Score, Metric::Length];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Score => "Score",
+            Self::Length => "Length"
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|metric| *metric == self).unwrap();
+
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn value(self, record: &ScoreRecord) -> usize {
+        match self {
+            Self::Score => record.score,
+            Self::Length => record.length
+        }
+    }
+}
+
+// Best runs, kept separately for every leaderboard and ranked by each metric
+#[derive(Clone, Default)]
+pub struct HighScores {
+    tables: HashMap<(Leaderboard, Metric), Vec<ScoreRecord>>
+}
+
+impl HighScores {
+    pub const CAPACITY: usize = 5;
+
+    // Where a run would rank in a table, if it makes it at all
+    fn rank(&self, leaderboard: Leaderboard, metric: Metric, record: &ScoreRecord) -> Option<usize> {
+        let table = self.table(leaderboard, metric);
+        let rank = table.iter()
+            .position(|entry| metric.value(record) > metric.value(entry))
+            .unwrap_or(table.len());
+
+        Some(rank).filter(|rank| *rank < Self::CAPACITY)
+    }
+
+    // Whether a run would make any of a leaderboard's tables
+    pub fn qualifies(&self, leaderboard: Leaderboard, record: &ScoreRecord) -> bool {
+        Metric::ALL.iter().any(|metric| self.rank(leaderboard, *metric, record).is_some())
+    }
+
+    // Record a run on a leaderboard in the table of every metric
+    pub fn record(&mut self, leaderboard: Leaderboard, record: ScoreRecord) {
+        for metric in Metric::ALL.iter().copied() {
+            if let Some(rank) = self.rank(leaderboard, metric, &record) {
+                let table = self.tables.entry((leaderboard, metric)).or_default();
+
+                table.insert(rank, record.clone());
+                table.truncate(Self::CAPACITY);
+            }
+        }
+    }
+
+    // Add a record to the bottom of a table, for restoring tables that were already ranked
+    pub fn push(&mut self, leaderboard: Leaderboard, metric: Metric, record: ScoreRecord) {
+        let table = self.tables.entry((leaderboard, metric)).or_default();
+
+        if table.len() < Self::CAPACITY {
+            table.push(record);
+        }
+    }
+
+    pub fn table(&self, leaderboard: Leaderboard, metric: Metric) -> &[ScoreRecord] {
+        self.tables.get(&(leaderboard, metric)).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+// The screen for naming a run that has made the high-score tables. Names can be typed, or picked a
+// letter at a time with the arrow keys as on an arcade cabinet: up and down change the last letter,
+// right adds another and left takes one away
+pub struct NameEntry {
+    name: TextField,
+    default_name: String, // Used if the name is left blank
+    leaderboard: Leaderboard,
+    record: ScoreRecord // The run, named once the entry is confirmed
+}
+
+impl NameEntry {
+    pub const MAX_LENGTH: usize = 8; // As much of a name as the tables have room for
+
+    pub fn new(leaderboard: Leaderboard, record: ScoreRecord, default_name: &str) -> Self {
+        let default_name: String = default_name.chars().take(Self::MAX_LENGTH).collect();
+        let mut name = TextField::new(Self::MAX_LENGTH, Profile::allows);
+
+        for character in default_name.chars() {
+            name.type_character(character);
+        }
+
+        Self {
+            name,
+            default_name,
+            leaderboard,
+            record
+        }
+    }
+
+    pub fn type_character(&mut self, character: char) {
+        self.name.type_character(character);
+    }
+
+    // Respond to a key press, giving back the leaderboard and its named run once the name has been confirmed
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<(Leaderboard, ScoreRecord)> {
+        match key_code {
+            VirtualKeyCode::Up => self.name.cycle(true),
+            VirtualKeyCode::Down => self.name.cycle(false),
+            VirtualKeyCode::Right => {
+                self.name.type_character('A');
+            },
+            VirtualKeyCode::Left | VirtualKeyCode::Back => self.name.backspace(),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Escape => {
+                let name = self.name.text().trim();
+
+                self.record.name = if name.is_empty() || key_code == VirtualKeyCode::Escape {
+                    self.default_name.clone()
+                } else {
+                    name.to_string()
+                };
+
+                return Some((self.leaderboard, self.record.clone()));
+            },
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 4, white, Game::BACKGROUND_COLOUR, "NEW HIGH SCORE");
+        ctx.print_color_centered_at(centre.0, centre.1 - 2, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("Score {} Length {}", self.record.score, self.record.length));
+        ctx.print_color_centered_at(centre.0, centre.1, white, Game::BACKGROUND_COLOUR, format!("Name: {}", self.name.display()));
+        ctx.print_color_centered_at(centre.0, centre.1 + 3, white, Game::BACKGROUND_COLOUR, "Type, or pick letters");
+        ctx.print_color_centered_at(centre.0, centre.1 + 4, white, Game::BACKGROUND_COLOUR, "with the arrow keys");
+        ctx.print_color_centered_at(centre.0, centre.1 + 6, white, Game::BACKGROUND_COLOUR, "[Enter] Confirm");
+    }
+}
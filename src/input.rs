@@ -0,0 +1,215 @@
+use std::convert::TryFrom;
+
+use bracket_terminal::prelude::{
+    BTerm,
+    BEvent,
+    Point,
+    VirtualKeyCode,
+    INPUT
+};
+
+use crate::direction::Direction;
+
+// Where key presses are read from
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputPath {
+    Advanced, // bracket's INPUT event queue, which doesn't drop presses made between frames
+    Simple // The single key of the frame held in `BTerm::key`, which every backend provides
+}
+
+// What a key press asks of the game, whichever frontend it came through
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Action {
+    Steer(Direction),
+    SteerPartner(Direction), // Steers the second snake, in modes for two players
+    ToggleAssist,
+    TogglePause,
+    Restart,
+    NextMode,
+    NextScoring,
+    NextSkin,
+    NextDifficulty,
+    NextWinCondition,
+    NextTable,
+    WhatsNew,
+    ToggleDebug,
+    Screenshot,
+    SaveRecording,
+    SaveReplay,
+    LoadReplay,
+    PlayChallenge,
+    PlayTournament,
+    ShowDeaths,
+    Profiles,
+    CopyBoard,
+    Undo,
+    SlowDown,
+    SpeedUp,
+    Checkpoint,
+    Tutorial,
+    ToggleHint,
+    Editor,
+    Campaign,
+    CustomGame,
+    Puzzles,
+    Display
+}
+
+impl Action {
+    pub fn from_key(key: VirtualKeyCode) -> Option<Self> {
+        if let Ok(direction) = Direction::try_from(key) {
+            return Some(Self::Steer(direction));
+        }
+
+        match key {
+            VirtualKeyCode::I => Some(Self::ToggleAssist),
+            VirtualKeyCode::Escape | VirtualKeyCode::P => Some(Self::TogglePause),
+            VirtualKeyCode::R => Some(Self::Restart),
+            VirtualKeyCode::M => Some(Self::NextMode),
+            VirtualKeyCode::F => Some(Self::NextScoring),
+            VirtualKeyCode::K => Some(Self::NextSkin),
+            VirtualKeyCode::B => Some(Self::NextDifficulty),
+            VirtualKeyCode::V => Some(Self::NextWinCondition),
+            VirtualKeyCode::Tab => Some(Self::NextTable),
+            VirtualKeyCode::N => Some(Self::WhatsNew),
+            VirtualKeyCode::F3 => Some(Self::ToggleDebug),
+            VirtualKeyCode::F12 => Some(Self::Screenshot),
+            VirtualKeyCode::G => Some(Self::SaveRecording),
+            VirtualKeyCode::E => Some(Self::SaveReplay),
+            VirtualKeyCode::L => Some(Self::LoadReplay),
+            VirtualKeyCode::H => Some(Self::PlayChallenge),
+            VirtualKeyCode::T => Some(Self::PlayTournament),
+            VirtualKeyCode::X => Some(Self::ShowDeaths),
+            VirtualKeyCode::O => Some(Self::Profiles),
+            VirtualKeyCode::C => Some(Self::CopyBoard),
+            VirtualKeyCode::U => Some(Self::Undo),
+            VirtualKeyCode::LBracket => Some(Self::SlowDown),
+            VirtualKeyCode::RBracket => Some(Self::SpeedUp),
+            VirtualKeyCode::Z => Some(Self::Checkpoint),
+            VirtualKeyCode::F1 => Some(Self::Tutorial),
+            VirtualKeyCode::J => Some(Self::ToggleHint),
+            VirtualKeyCode::F2 => Some(Self::Editor),
+            VirtualKeyCode::Q => Some(Self::Campaign),
+            VirtualKeyCode::Y => Some(Self::CustomGame),
+            VirtualKeyCode::F4 => Some(Self::Puzzles),
+            VirtualKeyCode::F5 => Some(Self::Display),
+            _ => None
+        }
+    }
+
+    // The diagonal a key steers in while diagonal moves are allowed, when Q, E, Z and C sit around WASD
+    // in place of their usual actions
+    fn diagonal(key: VirtualKeyCode) -> Option<Direction> {
+        match key {
+            VirtualKeyCode::Q => Some(Direction::NorthWest),
+            VirtualKeyCode::E => Some(Direction::NorthEast),
+            VirtualKeyCode::Z => Some(Direction::SouthWest),
+            VirtualKeyCode::C => Some(Direction::SouthEast),
+            _ => None
+        }
+    }
+
+    // As with `from_key`, but with two players at the keyboard the arrow keys (and the numpad) steer the
+    // first snake and WASD the second. With `diagonals`, QEZC steer diagonally too
+    pub fn from_key_for(key: VirtualKeyCode, players: usize, diagonals: bool) -> Option<Self> {
+        let arrow = !matches!(key, VirtualKeyCode::W | VirtualKeyCode::A | VirtualKeyCode::S | VirtualKeyCode::D) && Direction::try_from(key).is_ok();
+        let action = match Self::diagonal(key).filter(|_| diagonals) {
+            Some(direction) => Some(Self::Steer(direction)),
+            None => Self::from_key(key)
+        };
+
+        match action {
+            Some(Self::Steer(direction)) if players > 1 && !arrow => Some(Self::SteerPartner(direction)),
+            action => action
+        }
+    }
+}
+
+pub enum InputEvent {
+    Key(VirtualKeyCode),
+    Character(char), // Text typed, for screens that take it. Only the advanced path delivers these
+    #[cfg(target_arch = "wasm32")]
+    Tap(f64, f64), // Where the screen was touched, as fractions of the canvas's width and height
+    FocusLost, // The window was switched away from, or the page hidden
+    Quit
+}
+
+// Gathers input from the advanced event queue, falling back to the simple path at runtime on
+// backends whose queue never receives key presses (such as some browsers)
+pub struct InputSource {
+    path: InputPath,
+    probing: bool // Whether the advanced path has yet to prove that it delivers key presses
+}
+
+impl InputSource {
+    // Collect this frame's input events
+    pub fn poll(&mut self, ctx: &BTerm) -> Vec<InputEvent> {
+        let mut events = Vec::new();
+        let mut queue_has_keys = false;
+
+        INPUT.lock().for_each_message(|event| {
+            match event {
+                BEvent::KeyboardInput {key, pressed: true, ..} => {
+                    queue_has_keys = true;
+                    events.push(InputEvent::Key(key));
+                },
+                BEvent::Character {c} => events.push(InputEvent::Character(c)),
+                BEvent::CloseRequested => events.push(InputEvent::Quit),
+                BEvent::Focused {focused: false} => events.push(InputEvent::FocusLost),
+                _ => {}
+            }
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            events.extend(crate::web::take_taps().into_iter().map(|(x, y)| InputEvent::Tap(x, y)));
+
+            if crate::web::take_hidden() {
+                events.push(InputEvent::FocusLost);
+            }
+        }
+
+        match self.path {
+            InputPath::Advanced => if self.probing {
+                if queue_has_keys {
+                    self.probing = false;
+                } else if let Some(key) = ctx.key {
+                    // The backend saw a key press that never reached the queue, so stop relying on it
+                    self.path = InputPath::Simple;
+                    self.probing = false;
+                    events.push(InputEvent::Key(key));
+                }
+            },
+            InputPath::Simple => {
+                events.retain(|event| !matches!(event, InputEvent::Key(_)));
+
+                if let Some(key) = ctx.key {
+                    events.push(InputEvent::Key(key));
+                }
+            }
+        }
+
+        events
+    }
+
+    // Whether `key` is being held down right now, as bracket tracks on every backend
+    pub fn is_held(&self, key: VirtualKeyCode) -> bool {
+        INPUT.lock().is_key_pressed(key)
+    }
+
+    // The tile of `layer` under the mouse, and which of its buttons are held: the left, then the right
+    pub fn mouse(&self, layer: usize) -> (Point, bool, bool) {
+        let input = INPUT.lock();
+
+        (input.mouse_tile(layer), input.is_mouse_button_pressed(0), input.is_mouse_button_pressed(1))
+    }
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self {
+            path: InputPath::Advanced,
+            probing: true
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use std::io;
+
+use crate::engine::fruit::FruitTable;
+use crate::engine::schedule::{Schedule, ScriptError};
+use crate::engine::tags::CellTags;
+use crate::engine::Position;
+use crate::engine::arena::{Arena, Edges};
+use crate::engine::goal::Goal;
+use crate::engine::boss::BossKind;
+use crate::engine::puzzle::Scenario;
+
+// A designed level, loaded from a text file. Blank lines and lines starting with '#' are ignored,
+// `name = ...` names the level, `tag ...` lines attach metadata to cells (see `CellTags`), `fruit ...`
+// lines set what each kind of fruit is worth and how often it spawns (see `FruitTable`), `arena ...` and
+// `mask ...` lines shape the arena (see `Arena`), a `goal ...` line sets what the level asks of the player
+// in the campaign (see `Goal`), `par = <seconds>` how quickly it should be passed for the best rating,
+// `boss <kind>` pits the player against a boss (see `BossKind`), `edge <side> <wall|wrap>` sets whether an
+// edge of the map wraps around (see `Edges`), `puzzle snake ...` and `puzzle fruit ...` lay the level out as
+// a puzzle (see `Scenario`), and every other line is a hazard script entry (see
+// `Schedule`)
+#[derive(Clone)]
+pub struct Level {
+    pub name: String,
+    pub schedule: Schedule,
+    pub tags: CellTags,
+    pub fruits: FruitTable,
+    pub arena: Arena,
+    pub edges: Edges,
+    pub goal: Option<Goal>,
+    pub par: Option<f64>, // Seconds to pass the level in for three stars, or twice that for two
+    pub boss: Option<BossKind>,
+    pub puzzle: Option<Scenario>
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(io::Error),
+    Script(ScriptError),
+    TooBig {
+        mask: (usize, usize), // The widest row of the mask, and how many rows it has
+        map: (u32, u32)
+    },
+    NoFloor, // The whole map is walled off
+    SpawnBlocked(Position), // A spawn point in a wall
+    Unreachable(Position), // A tile walled off from the rest of the arena
+    PuzzleBlocked(Position), // A puzzle's snake or fruit in a wall, or on top of another
+    Unsolvable // A puzzle whose fruit can't all be eaten
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read level: {}", error),
+            Self::Script(error) => write!(f, "Invalid level script, {}", error),
+            Self::TooBig {mask, map} => write!(f, "Invalid level, the mask is {}x{} but the map is only {}x{}", mask.0, mask.1, map.0, map.1),
+            Self::NoFloor => write!(f, "Invalid level, the whole map is walled off"),
+            Self::SpawnBlocked(position) => write!(f, "Invalid level, the spawn point at ({}, {}) is in a wall", position.x, position.y),
+            Self::Unreachable(position) => write!(f, "Invalid level, ({}, {}) is walled off from the rest of the arena", position.x, position.y),
+            Self::PuzzleBlocked(position) => write!(f, "Invalid puzzle, ({}, {}) is taken twice or in a wall", position.x, position.y),
+            Self::Unsolvable => write!(f, "Invalid puzzle, there's no way to eat all of the fruit")
+        }
+    }
+}
+
+impl From<io::Error> for LevelError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ScriptError> for LevelError {
+    fn from(error: ScriptError) -> Self {
+        Self::Script(error)
+    }
+}
+
+impl Level {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &str) -> Result<Self, LevelError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, LevelError> {
+        let mut name = String::from("Custom");
+        let mut schedule = Schedule::default();
+        let mut tags = CellTags::default();
+        let mut fruits = FruitTable::default();
+        let mut arena = Arena::default();
+        let mut edges = Edges::default();
+        let mut goal = None;
+        let mut par = None;
+        let mut boss = None;
+        let mut puzzle = Scenario::default();
+
+        for (index, line) in text.lines().enumerate().map(|(index, line)| (index, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_once('=') {
+                Some((key, value)) if key.trim() == "name" => name = value.trim().to_string(),
+                Some((key, value)) if key.trim() == "par" => par = match value.trim().trim_end_matches('s').parse::<f64>() {
+                    Ok(seconds) if seconds > 0.0 => Some(seconds),
+                    _ => return Err(LevelError::Script(ScriptError {
+                        line: index + 1,
+                        message: "expected 'par = <seconds>s'".to_string()
+                    }))
+                },
+                _ if line.starts_with("tag ") => tags.parse_line(index + 1, line)?,
+                _ if line.starts_with("fruit ") => fruits.parse_line(index + 1, line)?,
+                _ if line.starts_with("arena ") || line.starts_with("mask ") => arena.parse_line(index + 1, line)?,
+                _ if line.starts_with("edge ") => edges.parse_line(index + 1, line)?,
+                _ if line.starts_with("puzzle ") => puzzle.parse_line(index + 1, line)?,
+                _ if line.starts_with("goal ") => goal = Some(Goal::parse_line(index + 1, line)?),
+                _ if line.starts_with("boss ") => boss = match BossKind::parse(line["boss ".len()..].trim()) {
+                    Some(kind) => Some(kind),
+                    None => return Err(LevelError::Script(ScriptError {
+                        line: index + 1,
+                        message: "unknown boss (expected cutter or guard)".to_string()
+                    }))
+                },
+                _ => schedule.parse_line(index + 1, line)?
+            }
+        }
+
+        Ok(Self {
+            name,
+            schedule,
+            tags,
+            fruits,
+            arena,
+            edges,
+            goal,
+            par,
+            boss,
+            puzzle: Some(puzzle).filter(|puzzle| !puzzle.is_empty())
+        })
+    }
+
+    // Check the level can be played on a map of the given size: that its mask fits, and that every spawn
+    // point and every tile of the arena can be reached, so no fruit spawns where the snake can't get to it.
+    // Puzzles must be laid out clear of the walls, and be possible to solve
+    pub fn validate(&self, dimensions: (u32, u32)) -> Result<(), LevelError> {
+        if let Arena::Mask(rows) = &self.arena {
+            let mask = (rows.iter().map(|row| row.chars().count()).max().unwrap_or(0), rows.len());
+
+            if mask.0 > dimensions.0 as usize || mask.1 > dimensions.1 as usize {
+                return Err(LevelError::TooBig {mask, map: dimensions});
+            }
+        }
+
+        let arena = self.arena.mask(dimensions).with_edges(self.edges);
+
+        if arena.open_tiles() == 0 {
+            return Err(LevelError::NoFloor);
+        }
+
+        if let Some(spawn) = self.tags.spawn_points().into_iter().find(|spawn| !arena.is_open(*spawn)) {
+            return Err(LevelError::SpawnBlocked(spawn));
+        }
+
+        if let Some(tile) = arena.unreachable() {
+            return Err(LevelError::Unreachable(tile));
+        }
+
+        match &self.puzzle {
+            Some(puzzle) if puzzle.snake.is_empty() || puzzle.fruit.is_empty() => Err(LevelError::Unsolvable),
+            Some(puzzle) => {
+                puzzle.fits(&arena).map_err(LevelError::PuzzleBlocked)?;
+                puzzle.solve(&arena).map(|_| ()).ok_or(LevelError::Unsolvable)
+            },
+            None => Ok(())
+        }
+    }
+}
@@ -1,16 +1,83 @@
+//! A clone of the classic Snake game, playable in a window through [`Game`], or embedded in another
+//! program through [`Engine`], which runs the same simulation with no window, main loop or clock.
+//!
+//! ```
+//! use snake_lib::{Config, Direction, Engine, Outcome};
+//!
+//! let mut engine = Engine::new(Config {
+//!     seed: 42,
+//!     ..Config::default()
+//! });
+//!
+//! // Head north until the snake hits the wall
+//! while engine.advance(Some(Direction::North)) != Outcome::Lost {}
+//!
+//! println!("Scored {} points", engine.score().points());
+//! ```
 pub mod game;
-mod snake;
+pub mod engine;
 mod object;
+mod render;
 mod direction;
+pub mod highscores;
+pub mod save;
+pub mod storage;
+mod tasks;
+mod ui;
+mod changelog;
+mod profiles;
+mod dialog;
+mod editor;
+mod custom;
+mod minimap;
+mod input;
+mod clock;
+mod debug;
+mod frame;
+mod gif;
+mod recording;
+mod export;
+pub mod level;
+pub mod replay;
+pub mod challenge;
+pub mod tournament;
+pub mod heatmap;
+pub mod session;
+pub mod tutorial;
+pub mod campaign;
+pub mod puzzle;
+pub mod display;
+pub mod camera;
+pub mod params;
+pub mod touch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod scripting;
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(all(feature = "terminal", not(target_arch = "wasm32")))]
+pub mod terminal;
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(hidden)]
+pub mod soak;
+
+pub use game::{Game, RunOptions};
+pub use engine::{Engine, Config, Direction, Position, Status, Outcome};
+pub use engine::snake::Snake;
+pub use engine::event::{GameEvent, DeathCause, Observer};
 
 bracket_terminal::add_wasm_support!();
 
 #[cfg(target_arch = "wasm32")]
 use bracket_terminal::prelude::BError;
-#[cfg(target_arch = "wasm32")]
-use game::Game;
 
 #[cfg(target_arch = "wasm32")]
 fn main() -> BError {
-    Game::new().run()
+    let mut game = Game::new();
+    let query = web_sys::window().and_then(|window| window.location().search().ok());
+
+    if let Some(query) = query {
+        game.apply_url_params(params::UrlParams::parse(&query));
+    }
+
+    game.run()
 }
\ No newline at end of file
@@ -0,0 +1,118 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB
+};
+
+use crate::game::Game;
+use crate::engine::{Engine, Position};
+use crate::engine::hazard::Hazard;
+use crate::engine::arena::ArenaMask;
+use crate::engine::snake::Snake;
+
+// What a tile of the minimap shows of the block of the map it stands for
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Cell {
+    Empty,
+    Wall,
+    Hazard,
+    Fruit,
+    Snake(usize) // Where the snake comes in `Engine::snakes`
+}
+
+impl Cell {
+    // Which of two things in the same block is shown: snakes over fruit over hazards over walls, and the
+    // player's snake over any other
+    fn priority(self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Wall => 1,
+            Self::Hazard => 2,
+            Self::Fruit => 3,
+            Self::Snake(0) => 5,
+            Self::Snake(_) => 4
+        }
+    }
+}
+
+// The whole map shrunk down into a corner of the screen, for maps too big to be seen all at once
+pub struct Minimap {
+    dimensions: (u32, u32), // In minimap tiles
+    cells: Vec<Cell>
+}
+
+impl Minimap {
+    pub const SIZE: u32 = 8; // The most tiles along the minimap's longer side
+    pub const EMPTY_COLOUR: RGB = RGB {r: 0.35, g: 0.4, b: 0.45};
+
+    pub fn of(engine: &Engine) -> Self {
+        Self::of_size(engine, Self::SIZE)
+    }
+
+    // The map shrunk down to at most `size` tiles along its longer side
+    pub fn of_size(engine: &Engine, size: u32) -> Self {
+        let map = engine.config().dimensions;
+        let scale = map.0.max(map.1).div_ceil(size).max(1); // Map tiles to a minimap tile, each way
+        let dimensions = (map.0.div_ceil(scale), map.1.div_ceil(scale));
+        let mut minimap = Self {
+            dimensions,
+            cells: vec![Cell::Empty; (dimensions.0 * dimensions.1) as usize]
+        };
+        let mut mark = |position: Position, cell: Cell| {
+            if position.in_bounds(map) {
+                let index = (position.y as u32 / scale * dimensions.0 + position.x as u32 / scale) as usize;
+
+                if cell.priority() > minimap.cells[index].priority() {
+                    minimap.cells[index] = cell;
+                }
+            }
+        };
+
+        for wall in engine.arena().walls() {
+            mark(wall, Cell::Wall);
+        }
+
+        for hazard in engine.hazards().iter().filter(|hazard| hazard.is_armed()) {
+            for tile in hazard.tiles(map) {
+                mark(tile, Cell::Hazard);
+            }
+        }
+
+        for fruit in engine.fruits().iter().chain(engine.cluster_fruits()) {
+            mark(fruit.position, Cell::Fruit);
+        }
+
+        for pellet in engine.pellets() {
+            mark(pellet.position, Cell::Fruit);
+        }
+
+        for (index, snake) in engine.snakes().iter().enumerate().filter(|(_, snake)| snake.alive) {
+            for segment in snake.iter() {
+                mark(segment.position, Cell::Snake(index));
+            }
+        }
+
+        minimap
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    // Draw the minimap with its top-left corner at (x, y)
+    pub fn render(&self, ctx: &mut BTerm, x: u32, y: u32, ascii_only: bool) {
+        let block = if ascii_only { '#' } else { '█' };
+
+        for (index, cell) in self.cells.iter().enumerate() {
+            let (glyph, colour) = match cell {
+                Cell::Empty => ('.', Self::EMPTY_COLOUR),
+                Cell::Wall => (block, ArenaMask::COLOUR),
+                Cell::Hazard => (block, Hazard::COLOUR),
+                Cell::Fruit => (block, Game::FRUIT_COLOUR),
+                Cell::Snake(snake) => (block, Snake::player_colour(*snake))
+            };
+            let (column, row) = (index as u32 % self.dimensions.0, index as u32 / self.dimensions.0);
+
+            ctx.set(x + column, y + row, colour, Game::BACKGROUND_COLOUR, bracket_terminal::prelude::to_cp437(glyph));
+        }
+    }
+}
@@ -1,40 +1,210 @@
-use bracket_terminal::prelude::{
-    BTerm,
-    Point,
-    RGB,
-};
-
-use crate::game::Game;
-
-pub trait Obj {
-    fn render(&self, ctx: &mut BTerm);
-    fn update(&mut self) {}
-}
-
-#[derive(Copy, Clone)]
-pub struct Object {
-    pub position: Point,
-    pub glyph: char,
-    pub colour: RGB,
-}
-
-impl Object {
-    pub fn new(position: Point, glyph: char, colour: RGB) -> Self {
-        Self {
-            position,
-            glyph,
-            colour
-        }
-    }
-}
-
-impl Obj for Object {
-    fn render(&self, ctx: &mut BTerm) {
-        ctx.set(
-            self.position.x, self.position.y,
-            self.colour,
-            Game::BACKGROUND_COLOUR,
-            bracket_terminal::prelude::to_cp437(self.glyph)
-        )
-    }
-}
\ No newline at end of file
+use bracket_terminal::prelude::{
+    BTerm,
+    Point,
+    RGB,
+};
+
+use crate::game::Game;
+use crate::camera::Camera;
+use crate::clock::{Clock, Timestamp};
+use crate::engine::Position;
+
+// Where something is drawn in the stack of things on a tile, bottom first. Things on the same layer are
+// drawn in the order they were added to the scene
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Layer {
+    Floor, // Walls and decorations
+    Body, // Snakes
+    Item, // Fruit, power-ups and pellets
+    Hazard,
+    Overlay // Hints and effects drawn over everything else
+}
+
+// Something drawn on the map, on a layer that decides what it's drawn over
+pub trait Render {
+    fn layer(&self) -> Layer;
+    fn render(&self, ctx: &mut BTerm, camera: &Camera);
+}
+
+// Something that changes from frame to frame of its own accord, by the game's clock. Things that only
+// change when the engine does are drawn from it as they are instead
+pub trait Update {
+    fn update(&mut self, clock: &Clock);
+}
+
+// How an object changes on a timer: cycling through glyphs, pulsing towards another colour and back, or
+// both. Fruit shimmers, and pickups blink as they're about to go
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Animation {
+    glyphs: [char; Self::MAX_FRAMES],
+    frames: usize, // How many of the glyphs are cycled through, none keeping the object's own
+    frame_seconds: f64,
+    pulse: Option<(RGB, f64)>, // The colour pulsed towards, and the seconds a pulse takes
+    seconds: f64 // How far into the animation the object is, set by the animator as it's drawn
+}
+
+impl Animation {
+    pub const MAX_FRAMES: usize = 4;
+
+    // Cycle through `glyphs`, up to `MAX_FRAMES` of them, showing each for `frame_seconds`
+    pub fn cycle(glyphs: &[char], frame_seconds: f64) -> Self {
+        let frames = glyphs.len().min(Self::MAX_FRAMES);
+        let mut cycle = [' '; Self::MAX_FRAMES];
+
+        cycle[..frames].copy_from_slice(&glyphs[..frames]);
+
+        Self {
+            glyphs: cycle,
+            frames,
+            frame_seconds,
+            pulse: None,
+            seconds: 0.0
+        }
+    }
+
+    // Turn `glyph` on and off, each for `frame_seconds`
+    pub fn blink(glyph: char, frame_seconds: f64) -> Self {
+        Self::cycle(&[glyph, ' '], frame_seconds)
+    }
+
+    // Fade towards `colour` and back every `seconds`, keeping the object's own glyph
+    pub fn pulse(colour: RGB, seconds: f64) -> Self {
+        Self::cycle(&[], 0.0).with_pulse(colour, seconds)
+    }
+
+    pub fn with_pulse(mut self, colour: RGB, seconds: f64) -> Self {
+        self.pulse = Some((colour, seconds));
+        self
+    }
+
+    pub fn glyph(&self, glyph: char) -> char {
+        match self.frames {
+            0 => glyph,
+            frames => self.glyphs[(self.seconds / self.frame_seconds) as usize % frames]
+        }
+    }
+
+    pub fn colour(&self, colour: RGB) -> RGB {
+        match self.pulse {
+            Some((pulse, seconds)) => {
+                let phase = (self.seconds / seconds).fract() * std::f64::consts::TAU;
+
+                colour.lerp(pulse, (0.5 - 0.5 * phase.cos()) as f32)
+            },
+            None => colour
+        }
+    }
+}
+
+// Keeps the time every animation is played to, so they all run off one clock. Each object is offset by
+// where it is, so neighbouring objects don't all pulse in step
+pub struct Animator {
+    started: Timestamp,
+    seconds: f64
+}
+
+impl Animator {
+    pub fn new(clock: &Clock) -> Self {
+        Self {
+            started: clock.now(),
+            seconds: 0.0
+        }
+    }
+
+    // `object`, as it looks at the moment
+    pub fn animate(&self, mut object: Object) -> Object {
+        if let Some(animation) = &mut object.animation {
+            let offset = (object.position.x * 3 + object.position.y * 5).rem_euclid(8) as f64 / 8.0;
+
+            animation.seconds = self.seconds + offset * animation.pulse.map_or(animation.frame_seconds, |(_, seconds)| seconds);
+        }
+
+        object
+    }
+}
+
+impl Update for Animator {
+    fn update(&mut self, clock: &Clock) {
+        self.seconds = clock.seconds_since(self.started);
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Object {
+    pub position: Point, // On the map, which the camera turns into a place on screen
+    pub glyph: char,
+    pub colour: RGB,
+    pub layer: Layer,
+    pub animation: Option<Animation>
+}
+
+impl Object {
+    // An object on the item layer, unless put on another with `on`
+    pub fn new(position: Point, glyph: char, colour: RGB) -> Self {
+        Self {
+            position,
+            glyph,
+            colour,
+            layer: Layer::Item,
+            animation: None
+        }
+    }
+
+    pub fn on(mut self, layer: Layer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    // Play `animation` as the object is drawn, through an animator
+    pub fn animated(mut self, animation: Animation) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+}
+
+impl Render for Object {
+    fn layer(&self) -> Layer {
+        self.layer
+    }
+
+    fn render(&self, ctx: &mut BTerm, camera: &Camera) {
+        let (glyph, colour) = match &self.animation {
+            Some(animation) => (animation.glyph(self.glyph), animation.colour(self.colour)),
+            None => (self.glyph, self.colour)
+        };
+
+        if let Some(screen) = camera.to_screen(Position::new(self.position.x, self.position.y)) {
+            ctx.set(
+                screen.x, screen.y,
+                colour,
+                Game::BACKGROUND_COLOUR,
+                bracket_terminal::prelude::to_cp437(glyph)
+            )
+        }
+    }
+}
+
+// Everything to be drawn on the map in a frame. Nothing is drawn until the whole scene has been put
+// together, then it's drawn a layer at a time, so what's added first doesn't decide what ends up on top
+#[derive(Default)]
+pub struct Scene {
+    objects: Vec<Box<dyn Render>>
+}
+
+impl Scene {
+    pub fn add(&mut self, object: impl Render + 'static) {
+        self.objects.push(Box::new(object));
+    }
+
+    pub fn extend<T: Render + 'static>(&mut self, objects: impl IntoIterator<Item = T>) {
+        self.objects.extend(objects.into_iter().map(|object| Box::new(object) as Box<dyn Render>));
+    }
+
+    pub fn render(mut self, ctx: &mut BTerm, camera: &Camera) {
+        self.objects.sort_by_key(|object| object.layer()); // Stable, keeping the order within each layer
+
+        for object in &self.objects {
+            object.render(ctx, camera);
+        }
+    }
+}
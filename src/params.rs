@@ -0,0 +1,85 @@
+use crate::engine::{Config, Grid};
+use crate::engine::mode::Mode;
+use crate::engine::speed::{LengthSpeed, SpeedCurve};
+
+// Settings given in the page's query string, such as `?seed=42&speed=1.5&mode=hardcore&size=30x20`, so
+// a link can fix the seed and settings of a challenge. Anything missing or malformed is left as it was
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct UrlParams {
+    pub seed: Option<u64>, // Every run is played with this seed, not just the first
+    pub speed: Option<f64>,
+    pub speed_curve: Option<SpeedCurve>,
+    pub length_speed: Option<LengthSpeed>,
+    pub grid: Option<Grid>,
+    pub mode: Option<Mode>,
+    pub size: Option<(u32, u32)>
+}
+
+impl UrlParams {
+    pub const SPEED_RANGE: (f64, f64) = (0.25, 4.0);
+    pub const SIZE_RANGE: (u32, u32) = (25, 100); // Big enough for the menus; bigger maps than the window scroll
+
+    // Read the parameters out of a query string, with or without its leading `?`
+    pub fn parse(query: &str) -> Self {
+        let mut params = Self::default();
+
+        for (key, value) in query.trim_start_matches('?').split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "seed" => params.seed = value.parse().ok().or(params.seed),
+                "speed" => if let Ok(speed) = value.parse::<f64>() {
+                    if speed.is_finite() {
+                        params.speed = Some(speed.clamp(Self::SPEED_RANGE.0, Self::SPEED_RANGE.1));
+                    }
+                },
+                "curve" => params.speed_curve = SpeedCurve::named(value).or(params.speed_curve),
+                "length" => params.length_speed = LengthSpeed::named(value).or(params.length_speed),
+                "grid" => params.grid = Grid::parse(value).or(params.grid),
+                "mode" => if let Some(mode) = Mode::ALL.iter().find(|mode| mode.name().eq_ignore_ascii_case(value)) {
+                    params.mode = Some(*mode);
+                },
+                "size" => params.size = parse_size(value).or(params.size),
+                _ => {}
+            }
+        }
+
+        params
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(seed) = self.seed {
+            config.seed = seed;
+        }
+
+        if let Some(speed) = self.speed {
+            config.speed = speed;
+        }
+
+        if let Some(speed_curve) = self.speed_curve {
+            config.speed_curve = speed_curve;
+        }
+
+        if let Some(length_speed) = self.length_speed {
+            config.length_speed = length_speed;
+        }
+
+        if let Some(grid) = self.grid {
+            config.grid = grid;
+        }
+
+        if let Some(mode) = self.mode {
+            config.mode = mode;
+        }
+
+        if let Some(size) = self.size {
+            config.dimensions = size;
+        }
+    }
+}
+
+// Either `<width>x<height>` or a single number for a square map
+fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let (width, height) = value.split_once('x').unwrap_or((value, value));
+    let clamp = |side: u32| side.clamp(UrlParams::SIZE_RANGE.0, UrlParams::SIZE_RANGE.1);
+
+    Some((clamp(width.parse().ok()?), clamp(height.parse().ok()?)))
+}
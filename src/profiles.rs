@@ -0,0 +1,112 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::save::{Profile, SaveData};
+use crate::ui::TextField;
+
+// The profile screen, for picking who is playing, adding a new player or removing one
+pub struct ProfileMenu {
+    selected: usize,
+    naming: Option<TextField>, // The name of a profile being added
+    error: Option<&'static str> // Why the last name entered was turned away
+}
+
+impl ProfileMenu {
+    pub fn new(save_data: &SaveData) -> Self {
+        Self {
+            selected: save_data.active(),
+            naming: None,
+            error: None
+        }
+    }
+
+    // Whether a name is being typed, so text should reach the screen
+    pub fn is_naming(&self) -> bool {
+        self.naming.is_some()
+    }
+
+    pub fn type_character(&mut self, character: char) {
+        if let Some(name) = &mut self.naming {
+            if name.type_character(character) {
+                self.error = None;
+            }
+        }
+    }
+
+    // Respond to a key press, switching profiles in `save_data` as asked. Returns false once the screen
+    // has been closed
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode, save_data: &mut SaveData) -> bool {
+        if let Some(name) = &mut self.naming {
+            match key_code {
+                VirtualKeyCode::Back => name.backspace(),
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                    match save_data.add_profile(name.text()) {
+                        Ok(()) => return false,
+                        Err(error) => self.error = Some(error)
+                    }
+                },
+                VirtualKeyCode::Escape => {
+                    self.naming = None;
+                    self.error = None;
+                },
+                _ => {}
+            }
+
+            return true;
+        }
+
+        let profiles = save_data.profiles().len();
+
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.selected = (self.selected + profiles - 1) % profiles,
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.selected = (self.selected + 1) % profiles,
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                save_data.switch_profile(self.selected);
+                return false;
+            },
+            VirtualKeyCode::N => self.naming = Some(TextField::new(Profile::MAX_NAME_LENGTH, Profile::allows)),
+            VirtualKeyCode::Delete => {
+                save_data.remove_profile(self.selected);
+                self.selected = self.selected.min(save_data.profiles().len() - 1);
+            },
+            VirtualKeyCode::Escape => return false,
+            _ => {}
+        }
+
+        true
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32), save_data: &SaveData) {
+        let white = bracket_terminal::prelude::WHITE;
+        let top = centre.1 - 10;
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "PROFILES");
+
+        for (row, profile) in save_data.profiles().iter().enumerate() {
+            let marker = if row == self.selected { '>' } else { ' ' };
+            let active = if row == save_data.active() { '*' } else { ' ' };
+
+            ctx.print_color_centered_at(centre.0, top + 2 + row as u32, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("{} {:<12} {}", marker, profile.name, active));
+        }
+
+        let bottom = centre.1 + 6;
+
+        if let Some(error) = self.error {
+            ctx.print_color_centered_at(centre.0, bottom - 2, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, error);
+        }
+
+        match &self.naming {
+            Some(name) => {
+                ctx.print_color_centered_at(centre.0, bottom, white, Game::BACKGROUND_COLOUR, format!("Name: {}", name.display()));
+                ctx.print_color_centered_at(centre.0, bottom + 2, white, Game::BACKGROUND_COLOUR, "[Enter] Add [Esc] Cancel");
+            },
+            None => {
+                ctx.print_color_centered_at(centre.0, bottom + 1, white, Game::BACKGROUND_COLOUR, "[Enter] Play as [N] New");
+                ctx.print_color_centered_at(centre.0, bottom + 2, white, Game::BACKGROUND_COLOUR, "[Del] Remove [Esc] Back");
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB,
+    VirtualKeyCode
+};
+
+use crate::game::Game;
+use crate::campaign::{Campaign, LevelChoice};
+use crate::engine::{Config, Engine};
+use crate::engine::mode::{GameMode, Mode};
+use crate::engine::puzzle::{PuzzleMode, Scenario};
+use crate::level::Level;
+
+// The puzzles, in the order they're listed. Each lays out the snake and its fruit in advance
+const LEVELS: [&str; 4] = [
+    include_str!("../levels/puzzles/1-first-steps.lvl"),
+    include_str!("../levels/puzzles/2-hairpin.lvl"),
+    include_str!("../levels/puzzles/3-coiled-up.lvl"),
+    include_str!("../levels/puzzles/4-round-the-back.lvl")
+];
+
+// A run through the puzzles, one at a time, starting from the one picked on the puzzle select screen.
+// Each is solved by eating every fruit without dying, and rated by how many moves it took against the
+// fewest it can be solved in
+pub struct Puzzles {
+    levels: Vec<Level>,
+    current: usize, // The puzzle being played
+    par: usize // The fewest moves the current puzzle can be solved in
+}
+
+impl Puzzles {
+    pub const MAX_STARS: u8 = 3;
+
+    // Play the puzzles from the one at `index`
+    pub fn new(index: usize) -> Self {
+        let levels = Self::levels();
+        let current = index.min(levels.len() - 1);
+
+        Self {
+            par: Self::solve(&levels[current]),
+            current,
+            levels
+        }
+    }
+
+    pub fn levels() -> Vec<Level> {
+        LEVELS.iter()
+            .map(|text| Level::parse(text).expect("The puzzles are valid"))
+            .collect()
+    }
+
+    fn solve(level: &Level) -> usize {
+        let arena = level.arena.mask(Engine::MAP_DIMENSIONS).with_edges(level.edges);
+
+        level.puzzle.as_ref()
+            .and_then(|puzzle| puzzle.solve(&arena))
+            .map_or(0, |moves| moves.len())
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.levels[self.current]
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        self.level().puzzle.as_ref().expect("Every puzzle has a scenario")
+    }
+
+    pub fn par(&self) -> usize {
+        self.par
+    }
+
+    // How well the puzzle was solved, in `moves`: three stars at par, two within half as many moves again,
+    // and one otherwise
+    pub fn stars(&self, moves: usize) -> u8 {
+        match moves {
+            moves if moves <= self.par => Self::MAX_STARS,
+            moves if moves <= self.par + self.par / 2 => 2,
+            _ => 1
+        }
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 == self.levels.len()
+    }
+
+    // Move on to the next puzzle, once the current one has been solved
+    pub fn advance(&mut self) {
+        self.current = (self.current + 1).min(self.levels.len() - 1);
+        self.par = Self::solve(self.level());
+    }
+
+    // The settings the current puzzle is played with: laid out by the level on the standard map, without
+    // power-ups, keeping to the player's skin and scoring
+    pub fn config(&self, base: &Config) -> Config {
+        let level = self.level().clone();
+
+        Config {
+            seed: base.seed,
+            dimensions: Engine::MAP_DIMENSIONS,
+            mode: Mode::Classic,
+            scoring_policy: base.scoring_policy,
+            skin: base.skin,
+            power_ups: false,
+            tags: level.tags,
+            fruits: level.fruits,
+            arena: level.arena,
+            edges: level.edges,
+            scenario: level.puzzle,
+            ..Config::default()
+        }
+    }
+
+    // A fresh copy of the current puzzle's rules, for a new attempt
+    pub fn rules(&self) -> Box<dyn GameMode> {
+        Box::new(PuzzleMode::new(self.scenario()))
+    }
+
+    // The moves made so far against par, along the top of the HUD layer
+    pub fn render(&self, ctx: &mut BTerm, dimensions: (u32, u32), moves: usize) {
+        ctx.print_color_centered_at(dimensions.0 / 2, 1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("Moves: {} Par: {}", moves, self.par));
+    }
+
+    // How the attempt went, in place of the game over screen, with the stars earned if it was solved
+    pub fn render_result(&self, ctx: &mut BTerm, centre: (u32, u32), moves: usize, stars: Option<u8>) {
+        let white = bracket_terminal::prelude::WHITE;
+        let solved = stars.is_some();
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 4, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("{}. {}", self.current + 1, self.level().name));
+        ctx.print_color_centered_at(centre.0, centre.1 - 2, white, Game::BACKGROUND_COLOUR, if solved { "PUZZLE SOLVED" } else { "PUZZLE FAILED" });
+
+        if let Some(stars) = stars {
+            ctx.print_color_centered_at(centre.0, centre.1 - 1, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, Campaign::describe_stars(stars));
+            ctx.print_color_centered_at(centre.0, centre.1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("Moves: {} Par: {}", moves, self.par));
+        }
+
+        if solved && self.is_last() {
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, "All puzzles solved!");
+        } else if solved {
+            ctx.print_color_centered_at(centre.0, centre.1 + 2, white, Game::BACKGROUND_COLOUR, "[Enter] Next puzzle");
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 4, white, Game::BACKGROUND_COLOUR, "[R] Retry [F4] Puzzles");
+    }
+}
+
+// Lists the puzzles to pick one to play, with the stars earned on those solved. Every puzzle can be
+// played from the start, so they can be tried in any order
+pub struct PuzzleSelect {
+    levels: Vec<Level>,
+    stars: Vec<u8>, // Earned on each puzzle, in order, 0 for those not yet solved
+    selected: usize
+}
+
+impl PuzzleSelect {
+    pub fn new(stars: &[u8]) -> Self {
+        let levels = Puzzles::levels();
+
+        Self {
+            selected: stars.iter().position(|stars| *stars == 0).unwrap_or(stars.len()).min(levels.len() - 1),
+            stars: stars.to_vec(),
+            levels
+        }
+    }
+
+    // Respond to a key press: `None` while the screen stays open, then what was picked
+    pub fn handle_input(&mut self, key_code: VirtualKeyCode) -> Option<LevelChoice> {
+        match key_code {
+            VirtualKeyCode::Up | VirtualKeyCode::W => self.selected = self.selected.saturating_sub(1),
+            VirtualKeyCode::Down | VirtualKeyCode::S => self.selected = (self.selected + 1).min(self.levels.len() - 1),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => return Some(LevelChoice::Play(self.selected)),
+            VirtualKeyCode::Escape | VirtualKeyCode::F4 => return Some(LevelChoice::Close),
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+        let top = centre.1 - 7;
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "PUZZLES");
+
+        for (index, level) in self.levels.iter().enumerate() {
+            let marker = if index == self.selected { '>' } else { ' ' };
+            let (label, colour) = match self.stars.get(index).copied().filter(|stars| *stars > 0) {
+                Some(stars) => (format!("{} {}. {:14} {}", marker, index + 1, level.name, Campaign::describe_stars(stars)), Game::FRUIT_COLOUR),
+                None => (format!("{} {}. {}", marker, index + 1, level.name), RGB::named(white))
+            };
+
+            ctx.print_color(centre.0.saturating_sub(11), top + 2 + index as u32, colour, Game::BACKGROUND_COLOUR, label);
+        }
+
+        let fruit = self.levels[self.selected].puzzle.as_ref().map_or(0, |puzzle| puzzle.fruit.len());
+
+        ctx.print_color_centered_at(centre.0, top + 3 + self.levels.len() as u32, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("Eat all {} fruit", fruit));
+        ctx.print_color_centered_at(centre.0, centre.1 + 6, white, Game::BACKGROUND_COLOUR, "[Enter] Play [Esc] Back");
+    }
+}
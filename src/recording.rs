@@ -0,0 +1,167 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::frame::{Area, Font, Frame, Image};
+use crate::gif::GifEncoder;
+
+// Keeps a picture of the screen from every slither of the run, to be saved as an animated GIF
+#[derive(Default)]
+pub struct Recorder {
+    frames: VecDeque<(Frame, f64)> // Each frame, and the run time it was captured at
+}
+
+// Part of a run, lifted out of the recorder to be encoded away from the game loop
+pub struct Recording {
+    frames: Vec<(Frame, f64)>
+}
+
+impl Recorder {
+    pub const MAX_FRAMES: usize = 9000; // Fifteen minutes at ten slithers a second, so long runs can't eat all the memory
+    pub const CLIP_SECONDS: f64 = 30.0;
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // The run time of the latest frame
+    pub fn last_time(&self) -> Option<f64> {
+        self.frames.back().map(|(_, time)| *time)
+    }
+
+    pub fn record(&mut self, frame: Frame, time: f64) {
+        if self.frames.len() == Self::MAX_FRAMES {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back((frame, time));
+    }
+
+    // The whole recording, or only the last `seconds` of it
+    pub fn clip(&self, seconds: Option<f64>) -> Recording {
+        let start = match (seconds, self.last_time()) {
+            (Some(seconds), Some(end)) => end - seconds,
+            _ => f64::NEG_INFINITY
+        };
+
+        Recording {
+            frames: self.frames.iter()
+                .filter(|(_, time)| *time >= start)
+                .cloned()
+                .collect()
+        }
+    }
+}
+
+impl Recording {
+    pub const SCALE: u32 = 2; // Image pixels per font pixel
+    pub const FINAL_FRAME_DELAY: u16 = 200; // Hundredths of a second to hold the last frame before looping
+    pub const MIN_DELAY: u16 = 2; // Browsers slow down anything quicker than this
+
+    // Encode the recording as a looping GIF. After the first frame, only the part of the screen that
+    // changed is stored, and frames where nothing changed just lengthen the one before
+    pub fn to_gif(&self) -> Result<Vec<u8>, String> {
+        let (first, _) = self.frames.first().ok_or_else(|| String::from("Nothing has been recorded"))?;
+        let font = Font::load()?;
+        let palette = Palette::new(self.frames.iter().flat_map(|(frame, _)| frame.colours()));
+
+        let tile_size = Font::GLYPH_SIZE * Self::SCALE;
+        let (width, height) = (first.dimensions().0 * tile_size, first.dimensions().1 * tile_size);
+        let mut encoder = GifEncoder::new(width as u16, height as u16, palette.colours());
+
+        // Which frames to store, with the area that changed in tiles and how long each is held for
+        let mut shown: Vec<(usize, Area, u16)> = Vec::new();
+
+        for (index, (frame, time)) in self.frames.iter().enumerate() {
+            let area = match index.checked_sub(1) {
+                Some(previous) => frame.changes_since(&self.frames[previous].0),
+                None => Some(((0, 0), frame.dimensions()))
+            };
+
+            let delay = match self.frames.get(index + 1) {
+                Some((_, next)) => ((next - time) * 100.0).round().max(0.0) as u16,
+                None => Self::FINAL_FRAME_DELAY
+            };
+
+            match (area, shown.last_mut()) {
+                (Some(area), _) => shown.push((index, area, delay)),
+                (None, Some(last)) => last.2 = last.2.saturating_add(delay),
+                (None, None) => {}
+            }
+        }
+
+        for (index, (origin, size), delay) in shown {
+            let image = self.frames[index].0.rasterise(&font, Self::SCALE);
+            let area = image.crop((origin.0 * tile_size, origin.1 * tile_size), (size.0 * tile_size, size.1 * tile_size));
+
+            encoder.add_frame(
+                ((origin.0 * tile_size) as u16, (origin.1 * tile_size) as u16),
+                (area.width as u16, area.height as u16),
+                &palette.indices(&area),
+                delay.max(Self::MIN_DELAY)
+            );
+        }
+
+        Ok(encoder.finish())
+    }
+}
+
+// The colours of a recording, in the order they first appear. Past the 256 a GIF can hold, colours are
+// drawn with the closest one that made it in
+struct Palette {
+    colours: Vec<[u8; 3]>,
+    lookup: HashMap<[u8; 3], u8>
+}
+
+impl Palette {
+    fn new(colours: impl Iterator<Item = [u8; 3]>) -> Self {
+        let mut palette = Self {
+            colours: Vec::new(),
+            lookup: HashMap::new()
+        };
+        let mut overflow = Vec::new();
+
+        for colour in colours {
+            if !palette.lookup.contains_key(&colour) {
+                if palette.colours.len() < GifEncoder::MAX_COLOURS {
+                    palette.lookup.insert(colour, palette.colours.len() as u8);
+                    palette.colours.push(colour);
+                } else {
+                    palette.lookup.insert(colour, 0); // Placeholder until the palette is complete
+                    overflow.push(colour);
+                }
+            }
+        }
+
+        for colour in overflow {
+            let closest = palette.closest(colour);
+            palette.lookup.insert(colour, closest);
+        }
+
+        palette
+    }
+
+    fn colours(&self) -> &[[u8; 3]] {
+        &self.colours
+    }
+
+    fn closest(&self, colour: [u8; 3]) -> u8 {
+        let distance = |other: &[u8; 3]| -> i32 {
+            (0..3).map(|channel| (colour[channel] as i32 - other[channel] as i32).pow(2)).sum()
+        };
+
+        self.colours.iter()
+            .enumerate()
+            .min_by_key(|(_, other)| distance(other))
+            .map_or(0, |(index, _)| index as u8)
+    }
+
+    fn indices(&self, image: &Image) -> Vec<u8> {
+        image.pixels
+            .chunks(3)
+            .map(|pixel| self.lookup.get(pixel).copied().unwrap_or(0))
+            .collect()
+    }
+}
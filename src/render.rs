@@ -0,0 +1,185 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    Point,
+    RGB
+};
+
+use crate::object::{Animation, Layer, Object};
+use crate::game::Game;
+use crate::camera::Camera;
+use crate::engine::{Direction, Position};
+use crate::engine::snake::Snake;
+use crate::engine::skin::Skin;
+use crate::engine::frenzy::Frenzy;
+use crate::engine::fruit::FruitKind;
+use crate::engine::power_up::PowerUp;
+use crate::engine::hazard::{Hazard, HazardKind};
+use crate::engine::tags::CellTags;
+use crate::engine::arena::{ArenaMask, Edge};
+
+// How the engine's entities look on the map layer. The engine has no notion of colour or terminals,
+// so their colours are kept here alongside the drawing code. Glyphs all come from the skin being played.
+// Entities are turned into objects on their layers, for the scene to draw in order
+
+impl From<Position> for Point {
+    fn from(position: Position) -> Self {
+        Point::new(position.x, position.y)
+    }
+}
+
+impl Snake {
+    pub const COLOUR: RGB = RGB {r: 0.5, g: 1.0, b: 0.5};
+    pub const DEAD_COLOUR: RGB = RGB {r: 0.5, g: 0.5, b: 0.5};
+    pub const FADE: f32 = 0.5; // How far towards the background a phasing snake fades, as if see-through
+
+    // The colours of the snakes after the player's, in the order of `Engine::snakes`
+    pub const OTHER_COLOURS: [RGB; 7] = [
+        RGB {r: 1.0, g: 0.85, b: 0.4},
+        RGB {r: 0.5, g: 0.85, b: 1.0},
+        RGB {r: 1.0, g: 0.55, b: 0.85},
+        RGB {r: 1.0, g: 0.6, b: 0.3},
+        RGB {r: 0.75, g: 0.6, b: 1.0},
+        RGB {r: 0.4, g: 1.0, b: 0.9},
+        RGB {r: 0.9, g: 0.9, b: 0.9}
+    ];
+
+    // The colour of the snake at `index` of `Engine::snakes`, before any tint
+    pub fn player_colour(index: usize) -> RGB {
+        match index {
+            0 => Self::COLOUR,
+            _ => Self::OTHER_COLOURS[(index - 1) % Self::OTHER_COLOURS.len()]
+        }
+    }
+
+    // The snake at `index` of `Engine::snakes`, tinted by whichever power-up took effect last
+    pub fn colour(&self, index: usize) -> RGB {
+        match self.effects().last() {
+            _ if !self.alive => Self::DEAD_COLOUR,
+            Some(effect) if effect.power_up == PowerUp::Phase => Self::player_colour(index).lerp(Game::BACKGROUND_COLOUR, Self::FADE),
+            Some(effect) => effect.power_up.colour(),
+            None => Self::player_colour(index)
+        }
+    }
+
+    pub fn objects(&self, index: usize) -> impl Iterator<Item = Object> + '_ {
+        let colour = self.colour(index);
+
+        self.iter().map(move |segment| Object::new(segment.position.into(), segment.glyph, colour).on(Layer::Body))
+    }
+}
+
+impl FruitKind {
+    pub const ROTTEN_COLOUR: RGB = RGB {r: 0.35, g: 0.3, b: 0.15};
+    pub const SHIMMER_SECONDS: f64 = 1.5; // Each glint of a fresh fruit
+    pub const SHIMMER: f32 = 0.35; // How far towards white a fruit glints
+
+    // Fresh fruit glints now and then, pulsing a little towards white
+    pub fn shimmer(self) -> Animation {
+        Animation::pulse(self.colour().lerp(RGB::named(bracket_terminal::prelude::WHITE), Self::SHIMMER), Self::SHIMMER_SECONDS)
+    }
+
+    // Darkening towards `ROTTEN_COLOUR` as the fruit rots, given how far gone it is (see `Engine::fruit_rot`)
+    pub fn rotten_colour(self, rot: Option<f64>) -> RGB {
+        match rot {
+            Some(rot) => self.colour().lerp(Self::ROTTEN_COLOUR, 0.5 + 0.5 * rot as f32),
+            None => self.colour()
+        }
+    }
+
+    pub fn colour(self) -> RGB {
+        match self {
+            Self::Apple => RGB {r: 1.0, g: 0.5, b: 0.5},
+            Self::Cherry => RGB {r: 0.95, g: 0.2, b: 0.4},
+            Self::Grape => RGB {r: 0.7, g: 0.45, b: 1.0},
+            Self::Mouse => RGB {r: 0.8, g: 0.75, b: 0.65}
+        }
+    }
+}
+
+impl PowerUp {
+    pub const BLINK_SECONDS: f64 = 3.0; // Pickups blink for the last of their lifetime, as a warning they're about to go
+    pub const BLINK_FRAME_SECONDS: f64 = 0.2;
+
+    pub fn colour(self) -> RGB {
+        match self {
+            Self::Shield => RGB {r: 0.45, g: 0.8, b: 1.0},
+            Self::Phase => RGB {r: 0.75, g: 0.6, b: 1.0},
+            Self::Magnet => RGB {r: 1.0, g: 0.35, b: 0.35},
+            Self::Confusion => RGB {r: 0.85, g: 1.0, b: 0.3}
+        }
+    }
+}
+
+impl Frenzy {
+    pub const COLOUR: RGB = RGB {r: 1.0, g: 0.85, b: 0.3};
+
+    pub fn objects(&self, skin: Skin) -> impl Iterator<Item = Object> + '_ {
+        self.fruits().iter().map(move |fruit| Object::new((*fruit).into(), skin.bonus_fruit, Self::COLOUR))
+    }
+}
+
+impl Hazard {
+    pub const COLOUR: RGB = RGB {r: 1.0, g: 0.25, b: 0.25};
+    pub const WARNING_COLOUR: RGB = RGB {r: 0.6, g: 0.3, b: 0.3};
+    pub const BLINKS_PER_SECOND: f64 = 4.0;
+}
+
+impl Hazard {
+    // The glyph and colour of the hazard's tiles at the moment, or nothing while it's blinked off
+    pub fn appearance(&self, skin: Skin) -> Option<(char, RGB)> {
+        let colour = if self.is_armed() {
+            Self::COLOUR
+        } else if (self.age() * Self::BLINKS_PER_SECOND) as i32 % 2 == 0 {
+            Self::WARNING_COLOUR
+        } else {
+            return None;
+        };
+
+        let glyph = match self.kind {
+            HazardKind::LaserRow(_) | HazardKind::LaserColumn(_) => skin.laser,
+            HazardKind::Bomb(_) => skin.bomb
+        };
+
+        Some((glyph, colour))
+    }
+
+    // The hazard's tiles on a map of `dimensions`, as they look at the moment
+    pub fn objects(&self, skin: Skin, dimensions: (u32, u32)) -> Vec<Object> {
+        match self.appearance(skin) {
+            Some((glyph, colour)) => self.tiles(dimensions).into_iter()
+                .map(|tile| Object::new(tile.into(), glyph, colour).on(Layer::Hazard))
+                .collect(),
+            None => Vec::new()
+        }
+    }
+}
+
+impl ArenaMask {
+    pub const COLOUR: RGB = RGB {r: 0.45, g: 0.5, b: 0.55};
+    pub const WRAP_COLOUR: RGB = RGB {r: 0.1, g: 0.2, b: 0.35}; // Behind the open tiles along edges that wrap around
+
+    // Edges that wrap around are safe to cross, so they're picked out from those that are walls. Only the
+    // background is drawn, under everything in the scene
+    pub fn render_edges(&self, ctx: &mut BTerm, camera: &Camera) {
+        for side in Direction::ALL.iter().copied().filter(|side| self.edges().get(*side) == Edge::Wrap) {
+            for tile in self.edge_tiles(side).into_iter().filter(|tile| self.is_open(*tile)) {
+                if let Some(screen) = camera.to_screen(tile) {
+                    ctx.set_bg(screen.x, screen.y, Self::WRAP_COLOUR);
+                }
+            }
+        }
+    }
+
+    pub fn objects(&self, skin: Skin) -> impl Iterator<Item = Object> + '_ {
+        self.walls().map(move |wall| Object::new(wall.into(), skin.wall, Self::COLOUR).on(Layer::Floor))
+    }
+}
+
+impl CellTags {
+    pub const DECORATION_COLOUR: RGB = RGB {r: 0.35, g: 0.4, b: 0.45};
+
+    pub fn objects(&self, skin: Skin) -> impl Iterator<Item = Object> + '_ {
+        self.tagged(Self::DECORATION)
+            .filter_map(move |(position, glyph)| Some(Object::new(position.into(), skin.displayable(glyph.chars().next()?), Self::DECORATION_COLOUR).on(Layer::Floor)))
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use std::io;
+
+use crate::engine::{Config, Direction, Engine, Grid, Steer};
+use crate::engine::mode::Mode;
+use crate::engine::scoring::ScoringPolicy;
+use crate::engine::speed::{LengthSpeed, SpeedCurve};
+use crate::engine::difficulty::Difficulty;
+use crate::engine::versus::WinCondition;
+use crate::engine::arena::Symmetry;
+
+// A run that can be played back exactly: the settings it started with and every change of direction
+// asked of its snakes. Stored as text, starting with a `snake-replay <format>` line, then `key=value`
+// settings (including the version of the game that recorded it), then a `steering` line followed by
+// one `<tick> <snake> <N|NE|E|SE|S|SW|W|NW>` line per change of direction. Only the built-in modes are covered, so
+// runs of levels and mods can't be replayed
+#[derive(Clone)]
+pub struct Replay {
+    pub version: String, // Of the game that recorded it
+    pub config: Config,
+    pub steering: Vec<Steer>
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    NotAReplay,
+    NewerFormat(u32), // Written in a format added after this version of the game
+    Incompatible(String), // Recorded by a version of the game that plays out runs differently
+    Invalid(usize, String) // Something on a line (counting from 1) couldn't be read
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read replay: {}", error),
+            Self::NotAReplay => write!(f, "Not a replay file"),
+            Self::NewerFormat(format) => write!(f, "Replay format {} needs a newer version of the game (this one reads up to {})", format, Replay::FORMAT),
+            Self::Incompatible(version) => write!(f, "Replay recorded by version {}, which can't be played back by version {}", version, Replay::VERSION),
+            Self::Invalid(line, message) => write!(f, "Invalid replay, line {}: {}", line, message)
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl Replay {
+    pub const HEADER: &'static str = "snake-replay";
+    pub const FORMAT: u32 = 1;
+    pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    // The run so far
+    pub fn of(engine: &Engine) -> Self {
+        Self {
+            version: Self::VERSION.to_string(),
+            config: engine.config().clone(),
+            steering: engine.steering().to_vec()
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &str) -> Result<Self, ReplayError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    // Runs only play out the same under the same major and minor version
+    pub fn is_compatible(version: &str) -> bool {
+        let release = |version: &str| version.split('.').take(2).map(str::to_string).collect::<Vec<String>>();
+
+        release(version) == release(Self::VERSION)
+    }
+
+    // A fresh engine for the run, to be steered through `steering`
+    pub fn start(&self) -> Engine {
+        Engine::new(self.config.clone())
+    }
+
+    // Apply the steering due before the engine's next slither
+    pub fn steer(&self, engine: &mut Engine) {
+        let tick = engine.ticks();
+        let start = self.steering.partition_point(|steer| steer.tick < tick);
+
+        for steer in self.steering[start..].iter().take_while(|steer| steer.tick == tick) {
+            engine.steer_snake(steer.snake, steer.direction);
+        }
+    }
+
+    // Play the run back up to just before slither `tick`, or to its end if that comes first
+    pub fn play_to(&self, engine: &mut Engine, tick: usize) {
+        while engine.ticks() < tick && !engine.is_over() {
+            self.steer(engine);
+            engine.tick();
+        }
+    }
+
+    pub fn serialise(&self) -> String {
+        let config = &self.config;
+        let curve = config.speed_curve;
+        let length_speed = config.length_speed;
+
+        let mut contents = format!("{} {}\n", Self::HEADER, Self::FORMAT);
+
+        contents.push_str(&format!("version={}\n", self.version));
+        contents.push_str(&format!("seed={}\n", config.seed));
+        contents.push_str(&format!("size={}x{}\n", config.dimensions.0, config.dimensions.1));
+        contents.push_str(&format!("mode={}\n", config.mode.name()));
+        contents.push_str(&format!("scoring={}\n", config.scoring_policy.name()));
+        contents.push_str(&format!("speed={}\n", config.speed));
+        contents.push_str(&format!("curve={},{},{},{}\n", curve.base, curve.increment, curve.every, curve.maximum));
+        contents.push_str(&format!("length_speed={},{},{}\n", length_speed.per_segment, length_speed.from, length_speed.maximum));
+        contents.push_str(&format!("power_ups={}\n", config.power_ups as u8));
+        contents.push_str(&format!("grid={}\n", config.grid.name()));
+        contents.push_str(&format!("difficulty={}\n", config.difficulty.name()));
+        contents.push_str(&format!("win_condition={}\n", config.win_condition.describe()));
+        contents.push_str(&format!("obstacles={}\n", config.obstacles));
+        contents.push_str(&format!("symmetry={}\n", config.symmetry.name()));
+        contents.push_str("steering\n");
+
+        for steer in &self.steering {
+            contents.push_str(&format!("{} {} {}\n", steer.tick, steer.snake, direction_letter(steer.direction)));
+        }
+
+        contents
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line.trim()));
+
+        let format = match lines.next().and_then(|(_, line)| line.split_once(' ')) {
+            Some((header, format)) if header == Self::HEADER => format.parse::<u32>().map_err(|_| ReplayError::NotAReplay)?,
+            _ => return Err(ReplayError::NotAReplay)
+        };
+
+        if format > Self::FORMAT {
+            return Err(ReplayError::NewerFormat(format));
+        }
+
+        let mut version = None;
+        let mut config = Config::default();
+        let mut steering = Vec::new();
+        let mut in_steering = false;
+
+        for (line_number, line) in lines.filter(|(_, line)| !line.is_empty()) {
+            let invalid = |message: &str| ReplayError::Invalid(line_number, message.to_string());
+
+            if in_steering {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+
+                match fields.as_slice() {
+                    [tick, snake, direction] => steering.push(Steer {
+                        tick: tick.parse().map_err(|_| invalid("expected a tick"))?,
+                        snake: snake.parse().map_err(|_| invalid("expected a snake"))?,
+                        direction: parse_direction(direction).ok_or_else(|| invalid("expected a compass direction, such as N or NE"))?
+                    }),
+                    _ => return Err(invalid("expected `<tick> <snake> <direction>`"))
+                }
+
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None if line == "steering" => {
+                    in_steering = true;
+                    continue;
+                },
+                None => return Err(invalid("expected `key=value`"))
+            };
+
+            match key {
+                "version" => version = Some(value.to_string()),
+                "seed" => config.seed = value.parse().map_err(|_| invalid("expected a seed"))?,
+                "size" => config.dimensions = value.split_once('x')
+                    .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+                    .ok_or_else(|| invalid("expected a size such as 25x25"))?,
+                "mode" => config.mode = *Mode::ALL.iter().find(|mode| mode.name() == value).ok_or_else(|| invalid("unknown mode"))?,
+                "scoring" => config.scoring_policy = *ScoringPolicy::ALL.iter().find(|policy| policy.name() == value).ok_or_else(|| invalid("unknown scoring"))?,
+                "speed" => config.speed = value.parse().map_err(|_| invalid("expected a speed"))?,
+                "curve" => config.speed_curve = parse_curve(value).ok_or_else(|| invalid("expected a speed curve"))?,
+                "length_speed" => config.length_speed = parse_length_speed(value).ok_or_else(|| invalid("expected a length speed-up"))?,
+                "power_ups" => config.power_ups = value == "1",
+                "grid" => config.grid = Grid::parse(value).ok_or_else(|| invalid("unknown grid"))?,
+                "difficulty" => config.difficulty = *Difficulty::ALL.iter().find(|difficulty| difficulty.name() == value).ok_or_else(|| invalid("unknown difficulty"))?,
+                "win_condition" => config.win_condition = *WinCondition::ALL.iter().find(|condition| condition.describe() == value).ok_or_else(|| invalid("unknown win condition"))?,
+                "obstacles" => config.obstacles = value.parse().map_err(|_| invalid("expected an obstacle density"))?,
+                "symmetry" => config.symmetry = *Symmetry::ALL.iter().find(|symmetry| symmetry.name() == value).ok_or_else(|| invalid("unknown symmetry"))?,
+                _ => {} // Left for later formats
+            }
+        }
+
+        let version = version.ok_or(ReplayError::NotAReplay)?;
+
+        if !Self::is_compatible(&version) {
+            return Err(ReplayError::Incompatible(version));
+        }
+
+        steering.sort_by_key(|steer: &Steer| steer.tick);
+
+        Ok(Self {
+            version,
+            config,
+            steering
+        })
+    }
+}
+
+fn direction_letter(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "N",
+        Direction::East => "E",
+        Direction::South => "S",
+        Direction::West => "W",
+        Direction::NorthEast => "NE",
+        Direction::SouthEast => "SE",
+        Direction::SouthWest => "SW",
+        Direction::NorthWest => "NW"
+    }
+}
+
+fn parse_direction(letter: &str) -> Option<Direction> {
+    Direction::EIGHT_WAYS.iter().copied().find(|direction| letter == direction_letter(*direction))
+}
+
+fn parse_curve(value: &str) -> Option<SpeedCurve> {
+    let fields: Vec<&str> = value.split(',').collect();
+
+    match fields.as_slice() {
+        [base, increment, every, maximum] => Some(SpeedCurve {
+            base: base.parse().ok()?,
+            increment: increment.parse().ok()?,
+            every: every.parse().ok()?,
+            maximum: maximum.parse().ok()?
+        }),
+        _ => None
+    }
+}
+
+fn parse_length_speed(value: &str) -> Option<LengthSpeed> {
+    let fields: Vec<&str> = value.split(',').collect();
+
+    match fields.as_slice() {
+        [per_segment, from, maximum] => Some(LengthSpeed {
+            per_segment: per_segment.parse().ok()?,
+            from: from.parse().ok()?,
+            maximum: maximum.parse().ok()?
+        }),
+        _ => None
+    }
+}
@@ -0,0 +1,357 @@
+use crate::engine::mode::Mode;
+use crate::engine::scoring::ScoringPolicy;
+use crate::engine::skin::Skin;
+use crate::engine::difficulty::Difficulty;
+use crate::engine::versus::WinCondition;
+use crate::engine::arena::Symmetry;
+use crate::engine::speed::LengthSpeed;
+use crate::engine::{Grid, Position};
+use crate::highscores::{Leaderboard, HighScores, Metric, ScoreRecord};
+use crate::heatmap::DeathMap;
+use crate::display::DisplaySettings;
+use crate::storage::Storage;
+
+// Data persisted between launches of the game
+#[derive(Clone)]
+pub struct SaveData {
+    pub last_seen_version: Option<String>, // The version of the game that was last launched
+    pub display: DisplaySettings, // How the window is drawn, shared by every profile
+    profiles: Vec<Profile>, // Never empty
+    active: usize // The profile being played
+}
+
+// A player's own settings, high scores and stats, kept apart from everyone else's who plays on the
+// same machine
+#[derive(Clone)]
+pub struct Profile {
+    pub name: String,
+    pub mode: Mode,
+    pub scoring_policy: ScoringPolicy,
+    pub skin: Skin,
+    pub difficulty: Difficulty,
+    pub win_condition: WinCondition,
+    pub fruit_hint: bool, // Whether an arrow by the snake's head points the way to the nearest fruit
+    pub campaign: Vec<u8>, // The stars earned on each campaign level passed, in order, the next one being unlocked
+    pub puzzles: Vec<u8>, // The best stars earned on each puzzle, in order, 0 for those not yet solved
+    pub obstacles: u32, // The obstacle density custom games are played with, in percent
+    pub symmetry: Symmetry, // How custom games match up the obstacles and starting points across the map
+    pub length_speed: LengthSpeed, // How custom games pick up the pace as the snake grows
+    pub grid: Grid, // The grid custom games are played on
+    pub high_scores: HighScores,
+    pub deaths: DeathMap // Where the player has died, for the heatmap
+}
+
+impl Profile {
+    pub const DEFAULT_NAME: &'static str = "Player";
+    pub const MAX_NAME_LENGTH: usize = 12;
+
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            mode: Mode::default(),
+            scoring_policy: ScoringPolicy::default(),
+            skin: Skin::default(),
+            difficulty: Difficulty::default(),
+            win_condition: WinCondition::default(),
+            fruit_hint: false,
+            campaign: Vec::new(),
+            puzzles: Vec::new(),
+            obstacles: 0,
+            symmetry: Symmetry::default(),
+            length_speed: LengthSpeed::default(),
+            grid: Grid::default(),
+            high_scores: HighScores::default(),
+            deaths: DeathMap::default()
+        }
+    }
+
+    pub fn levels_passed(&self) -> usize {
+        self.campaign.len()
+    }
+
+    // Record a campaign level as passed with `stars`, keeping the best rating of a level passed before
+    pub fn pass_level(&mut self, index: usize, stars: u8) {
+        if let Some(best) = self.campaign.get_mut(index) {
+            *best = (*best).max(stars);
+        } else if index == self.campaign.len() {
+            self.campaign.push(stars);
+        }
+    }
+
+    // Record a puzzle as solved with `stars`, keeping the best rating of a puzzle solved before
+    pub fn solve_puzzle(&mut self, index: usize, stars: u8) {
+        if self.puzzles.len() <= index {
+            self.puzzles.resize(index + 1, 0);
+        }
+
+        self.puzzles[index] = self.puzzles[index].max(stars);
+    }
+
+    // Whether the mode can be picked yet, some being unlocked by passing levels of the campaign
+    pub fn is_unlocked(&self, mode: Mode) -> bool {
+        self.levels_passed() >= mode.levels_to_unlock()
+    }
+
+    // Whether `character` can go in a profile's name, which is kept to what the save file can hold
+    pub fn allows(character: char) -> bool {
+        character.is_ascii_alphanumeric() || character == ' ' || character == '-' || character == '_'
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::named(Self::DEFAULT_NAME)
+    }
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            last_seen_version: None,
+            display: DisplaySettings::default(),
+            profiles: vec![Profile::default()],
+            active: 0
+        }
+    }
+}
+
+impl SaveData {
+    pub const MAX_PROFILES: usize = 12; // As many as the profile screen has room to list
+
+    pub fn profile(&self) -> &Profile {
+        &self.profiles[self.active]
+    }
+
+    pub fn profile_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.active]
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn switch_profile(&mut self, index: usize) {
+        if index < self.profiles.len() {
+            self.active = index;
+        }
+    }
+
+    // Add a fresh profile and play as it, or say why it can't be added
+    pub fn add_profile(&mut self, name: &str) -> Result<(), &'static str> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err("Name can't be blank");
+        } else if self.profiles.iter().any(|profile| profile.name.eq_ignore_ascii_case(name)) {
+            return Err("Name already taken");
+        } else if self.profiles.len() >= Self::MAX_PROFILES {
+            return Err("No room for more");
+        }
+
+        self.profiles.push(Profile::named(name));
+        self.active = self.profiles.len() - 1;
+
+        Ok(())
+    }
+
+    // Remove a profile, as long as it isn't the last one left
+    pub fn remove_profile(&mut self, index: usize) {
+        if self.profiles.len() > 1 && index < self.profiles.len() {
+            self.profiles.remove(index);
+
+            if self.active >= index && self.active > 0 {
+                self.active -= 1;
+            }
+        }
+    }
+
+    // Read the save data from storage, falling back to the defaults if there is none (or it can't be read)
+    pub fn load(storage: &impl Storage) -> Self {
+        storage.read()
+            .ok()
+            .flatten()
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &impl Storage) -> Result<(), String> {
+        storage.write(&self.serialise())
+    }
+
+    // Save data is stored as one `key=value` pair per line, ignoring unknown keys and values. Each profile
+    // starts with a `profile=<name>` line, followed by its own settings and stats, and saves from before
+    // profiles belong to a default one. High-score entries are listed best first as
+    // `high_score=<leaderboard>,<metric>,<score>,<length>,<assisted>,<name>`, the tiles died on as
+    // `death=<x>,<y>,<count>`, the stars earned on the campaign's levels as `level_stars=<stars>,...`, and
+    // those earned on the puzzles as `puzzle_stars=<stars>,...`. The display settings come before any
+    // profile, as they're shared by all of them
+    fn parse(contents: &str) -> Self {
+        let mut data = Self::default();
+        let mut profiles: Vec<Profile> = Vec::new();
+        let mut active = None;
+
+        for (key, value) in contents.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            let key = key.trim();
+
+            match key {
+                "last_seen_version" => data.last_seen_version = Some(value.to_string()),
+                "tile_size" | "scale" | "font" | "font_file" | "vsync" | "fps_cap" => data.display.parse(key, value),
+                "active_profile" => active = Some(value.to_string()),
+                "profile" => profiles.push(Profile::named(value)),
+                _ => {
+                    if profiles.is_empty() {
+                        profiles.push(Profile::default());
+                    }
+
+                    if let Some(profile) = profiles.last_mut() {
+                        profile.parse(key, value);
+                    }
+                }
+            }
+        }
+
+        if !profiles.is_empty() {
+            data.active = active
+                .and_then(|name| profiles.iter().position(|profile| profile.name == name))
+                .unwrap_or(0);
+            data.profiles = profiles;
+        }
+
+        data
+    }
+
+    fn serialise(&self) -> String {
+        let mut contents = String::new();
+
+        if let Some(version) = &self.last_seen_version {
+            contents.push_str(&format!("last_seen_version={}\n", version));
+        }
+
+        contents.push_str(&self.display.serialise());
+        contents.push_str(&format!("active_profile={}\n", self.profile().name));
+
+        for profile in &self.profiles {
+            contents.push_str(&profile.serialise());
+        }
+
+        contents
+    }
+}
+
+impl Profile {
+    fn parse(&mut self, key: &str, value: &str) {
+        match key {
+            "mode" => if let Some(mode) = Mode::ALL.iter().find(|mode| mode.name() == value) {
+                self.mode = *mode;
+            },
+            "scoring" => if let Some(policy) = ScoringPolicy::ALL.iter().find(|policy| policy.name() == value) {
+                self.scoring_policy = *policy;
+            },
+            "skin" => if let Some(skin) = Skin::named(value) {
+                self.skin = skin;
+            },
+            "difficulty" => if let Some(difficulty) = Difficulty::ALL.iter().find(|difficulty| difficulty.name() == value) {
+                self.difficulty = *difficulty;
+            },
+            "win_condition" => if let Some(condition) = WinCondition::ALL.iter().find(|condition| condition.describe() == value) {
+                self.win_condition = *condition;
+            },
+            "fruit_hint" => self.fruit_hint = value == "1",
+            "symmetry" => if let Some(symmetry) = Symmetry::ALL.iter().find(|symmetry| symmetry.name() == value) {
+                self.symmetry = *symmetry;
+            },
+            "length_speed" => if let Some(length_speed) = LengthSpeed::named(value) {
+                self.length_speed = length_speed;
+            },
+            "grid" => if let Some(grid) = Grid::parse(value) {
+                self.grid = grid;
+            },
+            "obstacles" => self.obstacles = value.parse::<u32>().map_or(0, |density| density.min(100)),
+            "level_stars" => self.campaign = value.split(',').filter_map(|stars| stars.trim().parse().ok()).collect(),
+            "puzzle_stars" => self.puzzles = value.split(',').filter_map(|stars| stars.trim().parse().ok()).collect(),
+            // Saves from before star ratings only counted the levels passed, each worth one star
+            "campaign" => self.campaign = vec![1; value.parse().unwrap_or(0)],
+            "high_score" => if let Some((leaderboard, metric, record)) = parse_high_score(value) {
+                self.high_scores.push(leaderboard, metric, record);
+            },
+            "death" => if let Some((position, count)) = parse_death(value) {
+                self.deaths.add(position, count);
+            },
+            _ => {}
+        }
+    }
+
+    fn serialise(&self) -> String {
+        let mut contents = format!("profile={}\n", self.name);
+
+        contents.push_str(&format!("mode={}\n", self.mode.name()));
+        contents.push_str(&format!("scoring={}\n", self.scoring_policy.name()));
+        contents.push_str(&format!("skin={}\n", self.skin.name));
+        contents.push_str(&format!("difficulty={}\n", self.difficulty.name()));
+        contents.push_str(&format!("win_condition={}\n", self.win_condition.describe()));
+        contents.push_str(&format!("fruit_hint={}\n", self.fruit_hint as u8));
+        contents.push_str(&format!("obstacles={}\n", self.obstacles));
+        contents.push_str(&format!("symmetry={}\n", self.symmetry.name()));
+        contents.push_str(&format!("length_speed={}\n", self.length_speed.name()));
+        contents.push_str(&format!("grid={}\n", self.grid.name()));
+
+        if !self.campaign.is_empty() {
+            let stars: Vec<String> = self.campaign.iter().map(u8::to_string).collect();
+
+            contents.push_str(&format!("level_stars={}\n", stars.join(",")));
+        }
+
+        if !self.puzzles.is_empty() {
+            let stars: Vec<String> = self.puzzles.iter().map(u8::to_string).collect();
+
+            contents.push_str(&format!("puzzle_stars={}\n", stars.join(",")));
+        }
+
+        for leaderboard in Leaderboard::all() {
+            for metric in Metric::ALL.iter().copied() {
+                for record in self.high_scores.table(leaderboard, metric) {
+                    contents.push_str(&format!(
+                        "high_score={},{},{},{},{},{}\n",
+                        leaderboard.key(), metric.name(), record.score, record.length, record.assisted as u8, record.name
+                    ));
+                }
+            }
+        }
+
+        for (position, count) in self.deaths.tiles() {
+            contents.push_str(&format!("death={},{},{}\n", position.x, position.y, count));
+        }
+
+        contents
+    }
+}
+
+fn parse_high_score(value: &str) -> Option<(Leaderboard, Metric, ScoreRecord)> {
+    let mut fields = value.split(',').map(str::trim);
+
+    let leaderboard = Leaderboard::parse(fields.next()?)?;
+    let metric = fields.next().and_then(|name| Metric::ALL.iter().find(|metric| metric.name() == name))?;
+    let score = fields.next()?.parse().ok()?;
+    let length = fields.next()?.parse().ok()?;
+    let assisted = fields.next()? == "1";
+    let record = ScoreRecord {
+        name: fields.next().unwrap_or_default().to_string(), // Missing from saves made before runs were named
+        score,
+        length,
+        assisted
+    };
+
+    Some((leaderboard, *metric, record))
+}
+
+fn parse_death(value: &str) -> Option<(Position, usize)> {
+    let mut fields = value.split(',').map(str::trim);
+    let position = Position::new(fields.next()?.parse().ok()?, fields.next()?.parse().ok()?);
+
+    Some((position, fields.next()?.parse().ok()?))
+}
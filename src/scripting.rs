@@ -0,0 +1,275 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+use bracket_terminal::console;
+use rhai::{CallFnOptions, Dynamic, FuncArgs, Map, Scope, AST};
+use rhai::module_resolvers::DummyModuleResolver;
+
+use crate::engine::{Position, Status};
+use crate::engine::mode::{Classic, GameMode, RunState, Spawn};
+use crate::engine::spawn::{self, SpawnPolicy};
+
+// A game mode written as a Rhai script, loaded from the `mods/` directory. The mode is named after its
+// file, and the script defines any of these functions, each replacing the classic rule of the same name:
+//
+//   lives(), slithers_per_second(), can_pause()
+//   fruit_points(points)        Points awarded for a fruit the scoring policy valued at `points`
+//   spawn_weight(x, y, weight)  Relative chance of fruit spawning on a free tile the level gave `weight`
+//   spawn_policy()              "uniform", "fair" to keep fruit from spawning right by the snake's head,
+//                               or "clusters" or "lines" for fruit in groups to clear
+//   on_start(run), on_tick(run) Called once the run is set up, and after every slither
+//   status(run)                 "won", "lost" or "playing"
+//
+// `run` is a map of the run so far (time, snake_length, alive, lives, points, fruits_eaten and cells),
+// and scripts can call `spawn_fruit(x, y)` and `spawn_bomb(x, y)` to place things on the map. Functions
+// can keep state between calls in `this`. Scripts can't reach anything outside the game, are cut off if
+// they run for too long, and a function that fails is dropped for the rest of the run
+#[derive(Clone)]
+pub struct Mod {
+    name: String,
+    ast: Rc<AST>,
+    functions: HashSet<String>
+}
+
+#[derive(Debug)]
+pub enum ModError {
+    Io(io::Error),
+    Script(String)
+}
+
+impl fmt::Display for ModError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read mod: {}", error),
+            Self::Script(error) => write!(f, "Invalid mod script, {}", error)
+        }
+    }
+}
+
+impl From<io::Error> for ModError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl Mod {
+    pub const DIRECTORY: &'static str = "mods";
+    pub const EXTENSION: &'static str = "rhai";
+
+    pub const MAX_OPERATIONS: u64 = 100_000; // Per function call
+    pub const MAX_CALL_LEVELS: usize = 32;
+    pub const MAX_COLLECTION_SIZE: usize = 1024; // For strings, arrays and maps alike
+
+    // Load the mod called `name` from the mods directory
+    pub fn named(name: &str) -> Result<Self, ModError> {
+        Self::load(Path::new(Self::DIRECTORY).join(name).with_extension(Self::EXTENSION))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModError> {
+        let path = path.as_ref();
+        let name = path.file_stem()
+            .map_or_else(|| String::from("Mod"), |stem| stem.to_string_lossy().into_owned());
+
+        Self::parse(name, &fs::read_to_string(path)?)
+    }
+
+    pub fn parse(name: String, source: &str) -> Result<Self, ModError> {
+        let ast = sandbox().compile(source).map_err(|error| ModError::Script(error.to_string()))?;
+        let functions = ast.iter_functions().map(|function| function.name.to_string()).collect();
+
+        Ok(Self {
+            name,
+            ast: Rc::new(ast),
+            functions
+        })
+    }
+
+    // Names of the mods in the mods directory
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(Self::DIRECTORY)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == Self::EXTENSION))
+            .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // A fresh copy of the mod's rules, for a new run
+    pub fn rules(&self) -> Box<dyn GameMode> {
+        let spawns = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = sandbox();
+
+        let queue = spawns.clone();
+        engine.register_fn("spawn_fruit", move |x: i64, y: i64| {
+            queue.borrow_mut().push(Spawn::Fruit(Position::new(x as i32, y as i32)));
+        });
+
+        let queue = spawns.clone();
+        engine.register_fn("spawn_bomb", move |x: i64, y: i64| {
+            queue.borrow_mut().push(Spawn::Bomb(Position::new(x as i32, y as i32)));
+        });
+
+        Box::new(ScriptedMode {
+            name: self.name.clone(),
+            engine,
+            ast: self.ast.clone(),
+            functions: RefCell::new(self.functions.clone()),
+            state: RefCell::new(Dynamic::from_map(Map::new())),
+            spawns
+        })
+    }
+}
+
+// A script engine with no access to anything outside the game, and limits on how much work a script
+// can do
+fn sandbox() -> rhai::Engine {
+    let mut engine = rhai::Engine::new();
+
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(Mod::MAX_OPERATIONS);
+    engine.set_max_call_levels(Mod::MAX_CALL_LEVELS);
+    engine.set_max_string_size(Mod::MAX_COLLECTION_SIZE);
+    engine.set_max_array_size(Mod::MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(Mod::MAX_COLLECTION_SIZE);
+
+    engine
+}
+
+struct ScriptedMode {
+    name: String,
+    engine: rhai::Engine,
+    ast: Rc<AST>,
+    functions: RefCell<HashSet<String>>, // Functions the script defines that haven't failed yet
+    state: RefCell<Dynamic>, // Bound to `this` in every call
+    spawns: Rc<RefCell<Vec<Spawn>>>
+}
+
+impl ScriptedMode {
+    // Call a function of the script, if it defines one, or nothing if it fails
+    fn call(&self, function: &str, args: impl FuncArgs) -> Option<Dynamic> {
+        if !self.functions.borrow().contains(function) {
+            return None;
+        }
+
+        let mut state = self.state.borrow_mut();
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut state);
+
+        match self.engine.call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, function, args) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                console::log(format!("Mod {}: {}() failed and has been switched off: {}", self.name, function, error));
+                self.functions.borrow_mut().remove(function);
+
+                None
+            }
+        }
+    }
+
+    fn call_int(&self, function: &str, args: impl FuncArgs) -> Option<i64> {
+        self.call(function, args).and_then(|value| value.as_int().ok())
+    }
+
+    fn call_number(&self, function: &str, args: impl FuncArgs) -> Option<f64> {
+        self.call(function, args).and_then(|value| {
+            value.as_float().ok().or_else(|| value.as_int().ok().map(|value| value as f64))
+        })
+    }
+}
+
+fn run_map(run: &RunState) -> Map {
+    let mut map = Map::new();
+
+    map.insert("time".into(), Dynamic::from(run.time));
+    map.insert("snake_length".into(), Dynamic::from(run.snake_length as i64));
+    map.insert("alive".into(), Dynamic::from(run.alive));
+    map.insert("lives".into(), Dynamic::from(run.lives as i64));
+    map.insert("points".into(), Dynamic::from(run.points as i64));
+    map.insert("fruits_eaten".into(), Dynamic::from(run.fruits_eaten as i64));
+    map.insert("cells".into(), Dynamic::from(run.cells as i64));
+
+    map
+}
+
+impl GameMode for ScriptedMode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn slithers_per_second(&self) -> u32 {
+        self.call_int("slithers_per_second", ())
+            .map_or_else(|| Classic.slithers_per_second(), |rate| rate.max(1) as u32)
+    }
+
+    fn can_pause(&self) -> bool {
+        self.call("can_pause", ())
+            .and_then(|value| value.as_bool().ok())
+            .unwrap_or_else(|| Classic.can_pause())
+    }
+
+    fn lives(&self) -> usize {
+        self.call_int("lives", ())
+            .map_or_else(|| Classic.lives(), |lives| lives.max(1) as usize)
+    }
+
+    fn fruit_points(&self, points: usize) -> usize {
+        self.call_int("fruit_points", (points as i64,))
+            .map_or(points, |points| points.max(0) as usize)
+    }
+
+    fn weighs_spawns(&self) -> bool {
+        self.functions.borrow().contains("spawn_weight")
+    }
+
+    fn spawn_weight(&self, position: Position, weight: f64) -> f64 {
+        self.call_number("spawn_weight", (position.x as i64, position.y as i64, weight))
+            .map_or(weight, |weight| weight.max(0.0))
+    }
+
+    fn spawn_policy(&self) -> Box<dyn SpawnPolicy> {
+        self.call("spawn_policy", ())
+            .and_then(|value| value.into_string().ok())
+            .and_then(|name| spawn::named(&name))
+            .unwrap_or_else(|| Classic.spawn_policy())
+    }
+
+    fn take_spawns(&mut self) -> Vec<Spawn> {
+        self.spawns.borrow_mut().drain(..).collect()
+    }
+
+    fn on_start(&mut self, run: &RunState) {
+        self.call("on_start", (run_map(run),));
+    }
+
+    fn on_tick(&mut self, run: &RunState) {
+        self.call("on_tick", (run_map(run),));
+    }
+
+    fn status(&self, run: &RunState) -> Status {
+        let status = self.call("status", (run_map(run),))
+            .and_then(|value| value.into_string().ok());
+
+        match status.as_deref() {
+            Some("won") => Status::Won,
+            Some("lost") => Status::Lost,
+            Some("playing") => Status::Playing,
+            _ => Classic.status(run)
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use bracket_terminal::prelude::BTerm;
+
+use crate::game::Game;
+use crate::clock;
+
+// How the runs since the game was launched went, summed up when it closes
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Session {
+    games_played: usize,
+    best_score: usize,
+    total_length: usize, // Of every run, as long as the snake grew
+    total_seconds: f64 // Spent playing, leaving out pauses and menus
+}
+
+impl Session {
+    pub fn record(&mut self, score: usize, length: usize, seconds: f64) {
+        self.games_played += 1;
+        self.best_score = self.best_score.max(score);
+        self.total_length += length;
+        self.total_seconds += seconds;
+    }
+
+    pub fn games_played(&self) -> usize {
+        self.games_played
+    }
+
+    pub fn best_score(&self) -> usize {
+        self.best_score
+    }
+
+    pub fn average_length(&self) -> f64 {
+        match self.games_played {
+            0 => 0.0,
+            games => self.total_length as f64 / games as f64
+        }
+    }
+
+    pub fn total_seconds(&self) -> f64 {
+        self.total_seconds
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!("Games played: {}", self.games_played),
+            format!("Best score: {}", self.best_score),
+            format!("Average length: {:.1}", self.average_length()),
+            format!("Time played: {}", clock::format_duration(self.total_seconds))
+        ]
+    }
+
+    // The summary, shown once the game has been asked to close
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+
+        ctx.print_color_centered_at(centre.0, centre.1 - 4, white, Game::BACKGROUND_COLOUR, "THIS SESSION");
+
+        for (row, line) in self.lines().into_iter().enumerate() {
+            ctx.print_color_centered_at(centre.0, centre.1 - 2 + row as u32, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, line);
+        }
+
+        ctx.print_color_centered_at(centre.0, centre.1 + 4, white, Game::BACKGROUND_COLOUR, "Press any key to quit");
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::engine::{Engine, Config};
+use crate::engine::autopilot::Autopilot;
+use crate::engine::controller::Controller;
+
+// Long-running stability check: an autopilot plays game after game on a bare engine, while tick
+// timings, memory usage and the size of every internal buffer are reported and checked for growth
+pub const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+pub const MAX_MEMORY_GROWTH: f64 = 1.5; // Resident memory may not grow beyond this factor of the first report
+pub const TICK_INTERVAL: Duration = Duration::from_millis(1);
+
+pub fn run(duration: Duration) {
+    let mut engine = new_engine();
+    let mut autopilot = Autopilot::default();
+    let start = Instant::now();
+    let mut report_start = Instant::now();
+    let mut baseline_memory = None;
+    let mut games_played = 0;
+    let mut ticks = 0u64;
+    let mut total_tick_time = Duration::default();
+    let mut longest_tick = Duration::default();
+
+    println!("Soak testing for {:.2} hours", duration.as_secs_f64() / 3600.0);
+
+    while start.elapsed() < duration {
+        if let Some(direction) = autopilot.decide(&engine.view()) {
+            engine.steer(direction);
+        }
+
+        let tick_start = Instant::now();
+        engine.tick();
+        let tick_time = tick_start.elapsed();
+
+        ticks += 1;
+        total_tick_time += tick_time;
+        longest_tick = longest_tick.max(tick_time);
+
+        if engine.is_over() {
+            games_played += 1;
+            engine = new_engine();
+        }
+
+        for buffer in engine.buffer_sizes() {
+            assert!(buffer.len <= buffer.capacity, "Buffer '{}' grew to {} (capacity {})", buffer.name, buffer.len, buffer.capacity);
+        }
+
+        if report_start.elapsed() >= REPORT_INTERVAL {
+            let memory = resident_memory();
+
+            println!(
+                "[{:>6}s] games: {}, ticks: {}, mean tick: {:?}, longest tick: {:?}, memory: {}",
+                start.elapsed().as_secs(),
+                games_played,
+                ticks,
+                total_tick_time / ticks as u32,
+                longest_tick,
+                memory.map_or("unknown".to_string(), |bytes| format!("{} KiB", bytes / 1024))
+            );
+
+            for buffer in engine.buffer_sizes() {
+                println!("          {}: {}/{}", buffer.name, buffer.len, buffer.capacity);
+            }
+
+            if let (Some(baseline), Some(memory)) = (baseline_memory, memory) {
+                assert!(
+                    memory as f64 <= baseline as f64 * MAX_MEMORY_GROWTH,
+                    "Memory grew from {} to {} bytes", baseline, memory
+                );
+            } else {
+                baseline_memory = memory;
+            }
+
+            report_start = Instant::now();
+            ticks = 0;
+            total_tick_time = Duration::default();
+            longest_tick = Duration::default();
+        }
+
+        thread::sleep(TICK_INTERVAL);
+    }
+
+    println!("Soak test passed after {} games", games_played);
+}
+
+fn new_engine() -> Engine {
+    Engine::new(Config {
+        seed: rand::thread_rng().gen(),
+        ..Config::default()
+    })
+}
+
+// The resident set size of this process in bytes, where the platform makes it available
+fn resident_memory() -> Option<usize> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: usize = statm.split_whitespace().nth(1)?.parse().ok()?;
+
+    Some(pages * 4096)
+}
@@ -0,0 +1,100 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+
+// Somewhere the save data can be kept between launches of the game
+pub trait Storage {
+    // The stored contents, or `None` if nothing has been stored yet
+    fn read(&self) -> Result<Option<String>, String>;
+    fn write(&self, contents: &str) -> Result<(), String>;
+}
+
+// Saves to a file in the working directory
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+    path: &'static str
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    pub const PATH: &'static str = "snake.sav";
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for FileStorage {
+    fn default() -> Self {
+        Self {
+            path: Self::PATH
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn read(&self) -> Result<Option<String>, String> {
+        match fs::read_to_string(self.path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.to_string())
+        }
+    }
+
+    fn write(&self, contents: &str) -> Result<(), String> {
+        fs::write(self.path, contents).map_err(|error| error.to_string())
+    }
+}
+
+// Saves to the page's `window.localStorage`, which outlives a refresh
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    key: &'static str
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    pub const KEY: &'static str = "snake.sav";
+
+    fn storage() -> Result<web_sys::Storage, String> {
+        web_sys::window()
+            .ok_or_else(|| String::from("No window"))?
+            .local_storage()
+            .map_err(|error| format!("{:?}", error))?
+            .ok_or_else(|| String::from("localStorage is unavailable"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Default for LocalStorage {
+    fn default() -> Self {
+        Self {
+            key: Self::KEY
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn read(&self) -> Result<Option<String>, String> {
+        Self::storage()?
+            .get_item(self.key)
+            .map_err(|error| format!("{:?}", error))
+    }
+
+    fn write(&self, contents: &str) -> Result<(), String> {
+        Self::storage()?
+            .set_item(self.key, contents)
+            .map_err(|error| format!("{:?}", error))
+    }
+}
+
+// Where this platform keeps its save data
+#[cfg(not(target_arch = "wasm32"))]
+pub fn platform() -> impl Storage {
+    FileStorage::default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn platform() -> impl Storage {
+    LocalStorage::default()
+}
@@ -0,0 +1,134 @@
+#[cfg(target_arch = "wasm32")]
+use std::cell::RefCell;
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread::{self, JoinHandle};
+
+use crate::frame::Frame;
+use crate::save::SaveData;
+use crate::export;
+use crate::recording::Recording;
+use crate::storage;
+
+// Storage work for the task queue to carry out, from saving progress to writing out screenshots, recordings,
+// replays and levels
+pub enum Task {
+    Load,
+    Save(Box<SaveData>), // Boxed, being far larger than the other tasks
+    Screenshot(Frame),
+    SaveRecording(Recording),
+    SaveReplay(String), // The replay, serialised
+    SaveLevel(String) // The level file's text
+}
+
+// The outcome of a task, delivered back to the game on a later tick
+pub enum TaskEvent {
+    Loaded(Box<SaveData>),
+    Saved(Result<(), String>),
+    Exported(Result<String, String>) // The name of the screenshot, recording, replay or level's file
+}
+
+// Runs storage tasks away from the game loop, which never waits on them: results are collected with
+// `poll` once per tick instead. On desktop the tasks run in order on a worker thread, which finishes
+// any still queued when the game closes. In the browser they are spawned as futures on the page's
+// event loop, ready for storage that only completes asynchronously
+pub struct TaskQueue {
+    #[cfg(not(target_arch = "wasm32"))]
+    tasks: Option<Sender<Task>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    events: Receiver<TaskEvent>,
+    #[cfg(not(target_arch = "wasm32"))]
+    worker: Option<JoinHandle<()>>,
+    #[cfg(target_arch = "wasm32")]
+    events: Rc<RefCell<Vec<TaskEvent>>>
+}
+
+impl TaskQueue {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new() -> Self {
+        let (task_sender, task_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+
+        let worker = thread::spawn(move || {
+            for task in task_receiver {
+                if event_sender.send(run(task)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            tasks: Some(task_sender),
+            events: event_receiver,
+            worker: Some(worker)
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn new() -> Self {
+        Self {
+            events: Rc::new(RefCell::new(Vec::new()))
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn submit(&mut self, task: Task) {
+        if let Some(tasks) = &self.tasks {
+            tasks.send(task).ok();
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn submit(&mut self, task: Task) {
+        let events = Rc::clone(&self.events);
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let event = run(task);
+
+            events.borrow_mut().push(event);
+        });
+    }
+
+    // The results of every task that has finished since the last poll
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self) -> Vec<TaskEvent> {
+        self.events.try_iter().collect()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self) -> Vec<TaskEvent> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+}
+
+// Let the worker get through whatever is still queued, so that no save is lost on the way out
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for TaskQueue {
+    fn drop(&mut self) {
+        self.tasks.take();
+
+        if let Some(worker) = self.worker.take() {
+            worker.join().ok();
+        }
+    }
+}
+
+impl Default for TaskQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn run(task: Task) -> TaskEvent {
+    match task {
+        Task::Load => TaskEvent::Loaded(Box::new(SaveData::load(&storage::platform()))),
+        Task::Save(data) => TaskEvent::Saved(data.save(&storage::platform())),
+        Task::Screenshot(frame) => TaskEvent::Exported(export::screenshot(&frame)),
+        Task::SaveRecording(recording) => TaskEvent::Exported(export::recording(&recording)),
+        Task::SaveReplay(replay) => TaskEvent::Exported(export::replay(&replay)),
+        Task::SaveLevel(level) => TaskEvent::Exported(export::level(&level))
+    }
+}
@@ -0,0 +1,450 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bracket_terminal::prelude::{VirtualKeyCode, RGB};
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, SetAttribute, SetBackgroundColor, SetForegroundColor, ResetColor};
+use rand::Rng;
+
+use crate::game::Game;
+use crate::camera::Camera;
+use crate::engine::{Config, Engine, Grid, Position, Status};
+use crate::engine::controller::{Controller, KeyboardController};
+use crate::engine::frenzy::Frenzy;
+use crate::engine::skin::Skin;
+use crate::engine::snake::Snake;
+use crate::engine::speed::Timestep;
+use crate::engine::power_up::PowerUp;
+use crate::engine::tags::CellTags;
+use crate::engine::arena::ArenaMask;
+use crate::input::Action;
+
+// A frontend for plain terminals, drawn with crossterm in place of a window so the game can be played
+// over SSH or wherever there's no OpenGL. It plays the same engine with the same controls as the
+// windowed game, less the high-score tables and debug overlay, and Q quits. Terminals with a UTF-8
+// locale can also be drawn in skins two columns wide, such as emoji
+pub struct TerminalGame {
+    config: Config,
+    engine: Engine,
+    keyboard: KeyboardController,
+    partner: KeyboardController, // The second player's keys, in modes for two
+    autopilot: Box<dyn Controller>,
+    bots: Vec<Box<dyn Controller>>, // Steering the snakes no player does, such as rivals in versus
+    ascii_only: bool,
+    wide_glyphs: bool, // Whether the terminal can be trusted with emoji
+    assist: bool,
+    paused: bool,
+    title: String, // The terminal's title as it was last set, so it's only set again when it changes
+    previous_frame_time: Instant,
+    timestep: Timestep
+}
+
+impl TerminalGame {
+    pub const FRAMES_PER_SECOND: f64 = 30.0;
+    pub const STATUS_ROWS: u32 = 2; // Lines kept below the map for the status and game over lines
+
+    // xterm's escape sequences for keeping the terminal's own title aside while the game retitles it, and
+    // putting it back afterwards. Terminals without a title stack ignore them
+    const PUSH_TITLE: &'static str = "\x1b[22;0t";
+    const POP_TITLE: &'static str = "\x1b[23;0t";
+
+    pub fn new() -> Self {
+        let config = Config {
+            seed: rand::thread_rng().gen(),
+            ..Config::default()
+        };
+
+        let engine = Engine::new(config.clone());
+
+        Self {
+            bots: config.difficulty.bots_for(&engine),
+            engine,
+            autopilot: config.difficulty.controller(),
+            config,
+            keyboard: KeyboardController::default(),
+            partner: KeyboardController::default(),
+            ascii_only: false,
+            wide_glyphs: supports_wide_glyphs(),
+            assist: false,
+            paused: false,
+            title: String::new(),
+            previous_frame_time: Instant::now(),
+            timestep: Timestep::default()
+        }
+    }
+
+    // Draw in nothing but 7-bit ASCII, as with the windowed game's `Game::restrict_to_ascii`
+    pub fn restrict_to_ascii(&mut self) {
+        self.ascii_only = true;
+        self.set_skin(self.config.skin);
+    }
+
+    // Take over the terminal and play until Q is pressed, putting the terminal back as it was afterwards
+    pub fn run(mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide, event::EnableFocusChange, Print(Self::PUSH_TITLE))?;
+
+        let result = self.play(&mut stdout);
+
+        execute!(stdout, Print(Self::POP_TITLE), event::DisableFocusChange, ResetColor, cursor::Show, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+
+        result
+    }
+
+    fn play(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let frame_length = Duration::from_secs_f64(1.0 / Self::FRAMES_PER_SECOND);
+
+        loop {
+            let frame_start = Instant::now();
+
+            while event::poll(Duration::ZERO)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Release => {},
+                    Event::Key(key) if is_quit(key) => return Ok(()),
+                    Event::Key(key) => if let Some(action) = virtual_key(key.code).and_then(|key| Action::from_key_for(key, self.engine.rules().players(), self.engine.config().grid != Grid::Square && !self.engine.is_over())) {
+                        self.execute_action(action);
+                    },
+                    // Terminals that report focus pause the run when switched away from, as the window does
+                    Event::FocusLost if !self.engine.is_over() => self.paused = true,
+                    _ => {}
+                }
+            }
+
+            if self.paused {
+                self.previous_frame_time = Instant::now(); // Time spent paused isn't owed to the simulation
+            } else {
+                self.update_snake();
+            }
+
+            self.update_title(out)?;
+            self.render(out)?;
+
+            thread::sleep(frame_length.saturating_sub(frame_start.elapsed()));
+        }
+    }
+
+    // Retitle the terminal with the mode and score while a run is being played, as the windowed game does
+    fn update_title(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let title = match self.engine.is_over() {
+            true => Game::TITLE.to_string(),
+            false => Game::run_title(&self.engine)
+        };
+
+        if title != self.title {
+            queue!(out, terminal::SetTitle(&title))?;
+            self.title = title;
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.config.seed = rand::thread_rng().gen();
+        self.engine = Engine::new(self.config.clone());
+        self.keyboard.clear();
+        self.partner.clear();
+        self.assist = false;
+        self.autopilot = self.config.difficulty.controller();
+        self.bots = self.config.difficulty.bots_for(&self.engine);
+        self.paused = false;
+        self.previous_frame_time = Instant::now();
+        self.timestep.reset();
+    }
+
+    fn can_draw(&self, skin: Skin) -> bool {
+        (skin.width == 1 || self.wide_glyphs) && (!self.ascii_only || skin.is_ascii())
+    }
+
+    // Switch to `skin`, or the first skin that can be drawn in its place
+    fn set_skin(&mut self, skin: Skin) {
+        let skin = if self.can_draw(skin) {
+            skin
+        } else {
+            skin.next(|skin| self.can_draw(skin))
+        };
+
+        self.config.skin = skin;
+        self.engine.set_skin(skin);
+    }
+
+    fn execute_action(&mut self, action: Action) {
+        if !self.engine.is_over() {
+            match action {
+                Action::Steer(direction) if !self.paused => {
+                    self.assist = false; // Steering hands control back to the player
+                    self.keyboard.press(direction);
+                },
+                Action::SteerPartner(direction) if !self.paused => self.partner.press(direction),
+                Action::ToggleAssist if !self.paused => self.assist = !self.assist,
+                Action::TogglePause if self.paused || self.engine.rules().can_pause() => self.paused = !self.paused,
+                _ => {}
+            }
+        } else {
+            match action {
+                Action::Restart => self.reset(),
+                Action::NextMode => self.config.mode = self.config.mode.next(),
+                Action::NextScoring => self.config.scoring_policy = self.config.scoring_policy.next(),
+                Action::NextSkin => self.set_skin(self.config.skin.next(|skin| self.can_draw(skin))),
+                Action::NextDifficulty => self.config.difficulty = self.config.difficulty.next(),
+                Action::NextWinCondition => self.config.win_condition = self.config.win_condition.next(),
+                _ => {}
+            }
+        }
+    }
+
+    // Slither as many times as real time calls for, on the same fixed timestep as the windowed game
+    fn update_snake(&mut self) {
+        let tick_length = self.engine.seconds_per_tick();
+        let elapsed = self.previous_frame_time.elapsed().as_secs_f64();
+
+        self.previous_frame_time = Instant::now();
+        self.timestep.bank(elapsed, tick_length);
+
+        while self.timestep.take(tick_length) {
+            let controller: &mut dyn Controller = if self.assist {
+                self.engine.mark_assisted();
+                self.autopilot.as_mut()
+            } else {
+                &mut self.keyboard
+            };
+
+            if let Some(direction) = controller.decide(&self.engine.view()) {
+                self.engine.steer(direction);
+            }
+
+            if self.engine.rules().players() > 1 {
+                if let Some(direction) = self.partner.decide(&self.engine.view_of(1)) {
+                    self.engine.steer_snake(1, direction);
+                }
+            }
+
+            for (index, bot) in (self.engine.rules().players()..).zip(&mut self.bots) {
+                if let Some(direction) = bot.decide(&self.engine.view_of(index)) {
+                    self.engine.steer_snake(index, direction);
+                }
+            }
+
+            self.engine.tick();
+        }
+    }
+
+    // The part of the map that fits in the terminal, scrolling to follow the player's head when the map
+    // doesn't fit
+    fn camera(&self) -> Camera {
+        let map = self.engine.config().dimensions;
+        let view = match terminal::size() {
+            Ok((columns, rows)) => (
+                (columns as u32 / self.config.skin.width as u32).clamp(1, map.0),
+                (rows as u32).saturating_sub(Self::STATUS_ROWS).clamp(1, map.1)
+            ),
+            Err(_) => map
+        };
+        let focus = self.engine.snake().front()
+            .map(|head| head.position)
+            .unwrap_or(Position::new(map.0 as i32 / 2, map.1 as i32 / 2));
+
+        Camera::following(map, view, focus)
+    }
+
+    // The glyph and colour of every tile in view, in row-major order
+    fn tiles(&self, camera: &Camera) -> Vec<Option<(char, RGB)>> {
+        let (width, height) = camera.view();
+        let map = camera.map();
+        let mut tiles = vec![None; (width * height) as usize];
+
+        let mut draw = |position: Position, glyph: char, colour: RGB| {
+            if let Some(screen) = camera.to_screen(position) {
+                tiles[(screen.y as u32 * width + screen.x as u32) as usize] = Some((glyph, colour));
+            }
+        };
+
+        for wall in self.engine.arena().walls() {
+            draw(wall, self.config.skin.wall, ArenaMask::COLOUR);
+        }
+
+        for (position, glyph) in self.engine.cell_tags().tagged(CellTags::DECORATION) {
+            if let Some(glyph) = glyph.chars().next() {
+                draw(position, self.config.skin.displayable(glyph), CellTags::DECORATION_COLOUR);
+            }
+        }
+
+        if !self.engine.is_over() {
+            for hazard in self.engine.hazards() {
+                if let Some((glyph, colour)) = hazard.appearance(self.config.skin) {
+                    for tile in hazard.tiles(map) {
+                        draw(tile, glyph, colour);
+                    }
+                }
+            }
+
+            for fruit in self.engine.frenzy().fruits() {
+                draw(*fruit, self.config.skin.bonus_fruit, Frenzy::COLOUR);
+            }
+
+            for pellet in self.engine.pellets() {
+                draw(pellet.position, self.config.skin.bonus_fruit, Snake::player_colour(pellet.snake));
+            }
+
+            if let Some(pickup) = self.engine.pickup() {
+                draw(pickup.position, self.config.skin.power_up(pickup.power_up), pickup.power_up.colour());
+            }
+
+            for fruit in self.engine.fruits().iter().chain(self.engine.cluster_fruits()) {
+                draw(fruit.position, self.config.skin.fruit(fruit.kind), fruit.kind.rotten_colour(self.engine.rot(fruit)));
+            }
+        }
+
+        for (index, snake) in self.engine.snakes().iter().enumerate() {
+            for segment in snake.iter() {
+                draw(segment.position, segment.glyph, snake.colour(index));
+            }
+        }
+
+        tiles
+    }
+
+    fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        let camera = self.camera();
+        let (width, height) = camera.view();
+        let skin = self.config.skin;
+        let background = colour(Game::BACKGROUND_COLOUR);
+
+        queue!(out, cursor::MoveTo(0, 0), SetBackgroundColor(background))?;
+
+        for (index, tile) in self.tiles(&camera).into_iter().enumerate() {
+            let (glyph, foreground) = tile.unwrap_or((' ', Game::BACKGROUND_COLOUR));
+
+            queue!(out, SetForegroundColor(colour(foreground)), Print(glyph))?;
+
+            // Anything narrower than the skin, such as a decoration or empty tile, is padded out to its width
+            if skin.width > 1 && !skin.is_own(glyph) {
+                queue!(out, Print(" ".repeat(skin.width as usize - 1)))?;
+            }
+
+            // Clearing the rest of the row, in case the last map drawn was wider
+            if index as u32 % width == width - 1 {
+                queue!(out, ResetColor, terminal::Clear(terminal::ClearType::UntilNewLine), Print("\r\n"), SetBackgroundColor(background))?;
+            }
+        }
+
+        queue!(out, ResetColor, cursor::MoveTo(0, height as u16), terminal::Clear(terminal::ClearType::FromCursorDown))?;
+
+        let mut status = format!("Score: {}", self.engine.score().points());
+
+        if self.engine.score().multiplier() > 1 {
+            status += &format!(" x{}", self.engine.score().multiplier());
+        }
+
+        if self.engine.rules().lives() > 1 {
+            status += &format!("  Lives: {}", self.engine.lives());
+        }
+
+        if !self.engine.config().length_speed.is_off() {
+            status += &format!("  Pace x{:.1}", self.engine.length_factor());
+        }
+
+        for effect in self.engine.snake().effects() {
+            status += &format!("  {} {}", skin.power_up(effect.power_up), effect.describe());
+        }
+
+        if self.engine.rules().rivals() {
+            let run = self.engine.run_state();
+
+            status += &format!("  {}", run.win_condition.progress(&run));
+        }
+
+        if self.assist {
+            status += "  AUTOPILOT";
+        }
+
+        if self.paused {
+            status += "  PAUSED";
+        }
+
+        // Confusion turns the status line's colours inside out, to go with the controls
+        if self.engine.snake().has_effect(PowerUp::Confusion) {
+            queue!(out, SetAttribute(Attribute::Reverse), Print(status), SetAttribute(Attribute::NoReverse), Print("\r\n"))?;
+        } else {
+            queue!(out, Print(status), Print("\r\n"))?;
+        }
+
+        if self.engine.is_over() {
+            let result = if self.engine.status() == Status::Won {
+                "You won!"
+            } else {
+                "GAME OVER"
+            };
+
+            queue!(out,
+                Print(format!("{}  [R] Restart  [M] Mode: {}  [F] Scoring: {}  [K] Skin: {}  [B] Bot: {}  [V] Versus: {}  [Q] Quit", result, self.config.mode.name(), self.config.scoring_policy.name(), self.config.skin.name, self.config.difficulty.name(), self.config.win_condition.describe())),
+                Print("\r\n")
+            )?;
+        }
+
+        out.flush()
+    }
+}
+
+impl Default for TerminalGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Going by the locale, as there's no asking a terminal how wide it draws a glyph
+fn supports_wide_glyphs() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"].iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .is_some_and(|locale| {
+            let locale = locale.to_ascii_lowercase();
+
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+}
+
+fn is_quit(key: KeyEvent) -> bool {
+    key.code == KeyCode::Char('q') || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+// The windowed game's name for a key, so both frontends share one set of controls
+fn virtual_key(code: KeyCode) -> Option<VirtualKeyCode> {
+    match code {
+        KeyCode::Up => Some(VirtualKeyCode::Up),
+        KeyCode::Down => Some(VirtualKeyCode::Down),
+        KeyCode::Left => Some(VirtualKeyCode::Left),
+        KeyCode::Right => Some(VirtualKeyCode::Right),
+        KeyCode::Esc => Some(VirtualKeyCode::Escape),
+        KeyCode::Tab => Some(VirtualKeyCode::Tab),
+        KeyCode::F(3) => Some(VirtualKeyCode::F3),
+        KeyCode::Char(character) => match character.to_ascii_lowercase() {
+            'w' => Some(VirtualKeyCode::W),
+            'a' => Some(VirtualKeyCode::A),
+            's' => Some(VirtualKeyCode::S),
+            'd' => Some(VirtualKeyCode::D),
+            'i' => Some(VirtualKeyCode::I),
+            'p' => Some(VirtualKeyCode::P),
+            'r' => Some(VirtualKeyCode::R),
+            'm' => Some(VirtualKeyCode::M),
+            'f' => Some(VirtualKeyCode::F),
+            'n' => Some(VirtualKeyCode::N),
+            'k' => Some(VirtualKeyCode::K),
+            'b' => Some(VirtualKeyCode::B),
+            'v' => Some(VirtualKeyCode::V),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn colour(rgb: RGB) -> Color {
+    Color::Rgb {
+        r: (rgb.r * 255.0) as u8,
+        g: (rgb.g * 255.0) as u8,
+        b: (rgb.b * 255.0) as u8
+    }
+}
@@ -0,0 +1,102 @@
+use bracket_terminal::prelude::{BTerm, RGB};
+
+use crate::engine::{Direction, Position};
+use crate::engine::skin::Skin;
+use crate::input::Action;
+
+// Buttons drawn over the board on touch screens, in place of the keyboard. The D-pad sits in the bottom
+// right corner and the pause button (restart, once the run is over) in the bottom left, clear of the HUD
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TouchButton {
+    Steer(Direction),
+    Pause,
+    Restart
+}
+
+impl TouchButton {
+    pub const SIZE: i32 = 2; // Tiles along each side, so buttons are big enough to tap
+    pub const COLOUR: RGB = RGB {r: 0.85, g: 0.85, b: 0.85};
+    pub const BACKGROUND_COLOUR: RGB = RGB {r: 0.3, g: 0.34, b: 0.38};
+
+    pub const PLAYING: [TouchButton; 5] = [
+        TouchButton::Steer(Direction::North),
+        TouchButton::Steer(Direction::East),
+        TouchButton::Steer(Direction::South),
+        TouchButton::Steer(Direction::West),
+        TouchButton::Pause
+    ];
+    pub const OVER: [TouchButton; 1] = [TouchButton::Restart];
+
+    // The buttons on screen, depending on whether the run is over
+    pub fn shown(over: bool) -> &'static [TouchButton] {
+        if over {
+            &Self::OVER
+        } else {
+            &Self::PLAYING
+        }
+    }
+
+    // The button under a tap, given as fractions of the width and height of a map of `dimensions`
+    pub fn at(tap: (f64, f64), over: bool, dimensions: (u32, u32)) -> Option<Self> {
+        let tile = Position::new(
+            (tap.0 * dimensions.0 as f64).floor() as i32,
+            (tap.1 * dimensions.1 as f64).floor() as i32
+        );
+
+        Self::shown(over).iter()
+            .copied()
+            .find(|button| button.covers(tile, dimensions))
+    }
+
+    // The top left tile of the button
+    pub fn origin(self, dimensions: (u32, u32)) -> Position {
+        let (width, height) = (dimensions.0 as i32, dimensions.1 as i32);
+        let centre = Position::new(width - 2 * Self::SIZE - 1, height - 2 * Self::SIZE - 2); // The middle of the D-pad
+
+        match self {
+            Self::Steer(direction) => centre + Position::from(direction) * Self::SIZE,
+            Self::Pause | Self::Restart => Position::new(1, height - Self::SIZE - 2)
+        }
+    }
+
+    pub fn covers(self, tile: Position, dimensions: (u32, u32)) -> bool {
+        let origin = self.origin(dimensions);
+
+        (origin.x..origin.x + Self::SIZE).contains(&tile.x) && (origin.y..origin.y + Self::SIZE).contains(&tile.y)
+    }
+
+    pub fn action(self) -> Action {
+        match self {
+            Self::Steer(direction) => Action::Steer(direction),
+            Self::Pause => Action::TogglePause,
+            Self::Restart => Action::Restart
+        }
+    }
+
+    fn glyph(self, skin: Skin) -> char {
+        match self {
+            Self::Steer(direction) => skin.arrow(direction),
+            Self::Pause => 'P',
+            Self::Restart => 'R'
+        }
+    }
+
+    pub fn render(self, ctx: &mut BTerm, dimensions: (u32, u32), skin: Skin) {
+        let origin = self.origin(dimensions);
+
+        for y in origin.y..origin.y + Self::SIZE {
+            for x in origin.x..origin.x + Self::SIZE {
+                ctx.set(x, y, Self::COLOUR, Self::BACKGROUND_COLOUR, bracket_terminal::prelude::to_cp437(self.glyph(skin)));
+            }
+        }
+    }
+}
+
+// Whether the game is being played on a touch screen
+pub fn is_available() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    return crate::web::is_touch_device();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    return false;
+}
@@ -0,0 +1,156 @@
+use bracket_terminal::prelude::BTerm;
+
+use crate::game::Game;
+
+// A hot-seat tournament: players take turns at a run on the same board, the highest score taking the
+// round, until one of them has won most of the rounds. Tied rounds go to no one, and if the rounds run
+// out without a clear leader, more are played until there is one
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Tournament {
+    players: usize,
+    best_of: usize,
+    seed: u64, // Every round is played on a board of its own, seeded from this
+    rounds: Vec<Vec<usize>> // The points of each player who has played each round, in turn order
+}
+
+impl Tournament {
+    pub const PLAYERS: usize = 2;
+    pub const BEST_OF: usize = 3;
+    pub const MAX_PLAYERS: usize = 4; // As many columns of scores as fit on the board
+    pub const ROWS: usize = 7; // Rounds listed on the standings
+
+    pub fn new(players: usize, best_of: usize, seed: u64) -> Self {
+        Self {
+            players: players.clamp(2, Self::MAX_PLAYERS),
+            best_of: best_of.max(1),
+            seed,
+            rounds: Vec::new()
+        }
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    pub fn best_of(&self) -> usize {
+        self.best_of
+    }
+
+    pub fn rounds(&self) -> &[Vec<usize>] {
+        &self.rounds
+    }
+
+    // The round being played or about to be, counting from 0
+    pub fn round(&self) -> usize {
+        match self.rounds.last() {
+            Some(scores) if scores.len() < self.players => self.rounds.len() - 1,
+            _ => self.rounds.len()
+        }
+    }
+
+    // The seed every player's run of the current round is played with
+    pub fn seed(&self) -> u64 {
+        self.seed.wrapping_add(self.round() as u64)
+    }
+
+    // Whose turn it is, or `None` once the tournament has been won
+    pub fn current_player(&self) -> Option<usize> {
+        if self.winner().is_some() {
+            return None;
+        }
+
+        match self.rounds.last() {
+            Some(scores) if scores.len() < self.players => Some(scores.len()),
+            _ => Some(0)
+        }
+    }
+
+    // Score the run just played for whoever's turn it was
+    pub fn record(&mut self, points: usize) {
+        if self.current_player().is_none() {
+            return;
+        }
+
+        match self.rounds.last_mut() {
+            Some(scores) if scores.len() < self.players => scores.push(points),
+            _ => self.rounds.push(vec![points])
+        }
+    }
+
+    // Who took a finished round, if anyone did
+    pub fn round_winner(&self, round: usize) -> Option<usize> {
+        let scores = self.rounds.get(round).filter(|scores| scores.len() == self.players)?;
+        let best = *scores.iter().max()?;
+
+        match scores.iter().filter(|points| **points == best).count() {
+            1 => scores.iter().position(|points| *points == best),
+            _ => None
+        }
+    }
+
+    pub fn wins(&self, player: usize) -> usize {
+        (0..self.rounds.len()).filter(|round| self.round_winner(*round) == Some(player)).count()
+    }
+
+    pub fn winner(&self) -> Option<usize> {
+        let wins: Vec<usize> = (0..self.players).map(|player| self.wins(player)).collect();
+        let most = *wins.iter().max()?;
+        let finished = self.rounds.iter().filter(|scores| scores.len() == self.players).count();
+
+        if most > self.best_of / 2 || (finished >= self.best_of && wins.iter().filter(|won| **won == most).count() == 1) {
+            wins.iter().position(|won| *won == most)
+        } else {
+            None
+        }
+    }
+
+    // Whose turn it is, for the HUD
+    pub fn describe_turn(&self) -> String {
+        match self.current_player() {
+            Some(player) => format!("Round {}: Player {}", self.round() + 1, player + 1),
+            None => "Tournament over".to_string()
+        }
+    }
+
+    // The standings, in place of the game over screen
+    pub fn render(&self, ctx: &mut BTerm, centre: (u32, u32)) {
+        let white = bracket_terminal::prelude::WHITE;
+        let top = centre.1 - 10;
+
+        ctx.print_color_centered_at(centre.0, top, white, Game::BACKGROUND_COLOUR, "TOURNAMENT");
+        ctx.print_color_centered_at(centre.0, top + 1, white, Game::BACKGROUND_COLOUR, format!("Best of {}", self.best_of));
+
+        let header: String = (0..self.players).map(|player| format!("{:>4} ", format!("P{}", player + 1))).collect();
+        ctx.print_color_centered_at(centre.0, top + 3, white, Game::BACKGROUND_COLOUR, format!("   {}", header));
+
+        // The latest rounds, if there have been more than fit
+        let shown = self.rounds.len().saturating_sub(Self::ROWS);
+
+        for (row, scores) in self.rounds.iter().enumerate().skip(shown) {
+            let round_winner = self.round_winner(row);
+            let cells: String = (0..self.players).map(|player| match scores.get(player) {
+                Some(points) if round_winner == Some(player) => format!("{:>4}*", points),
+                Some(points) => format!("{:>4} ", points),
+                None => "   - ".to_string()
+            }).collect();
+
+            ctx.print_color_centered_at(centre.0, top + 4 + (row - shown) as u32, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("R{:<2}{}", row + 1, cells));
+        }
+
+        let wins: String = (0..self.players).map(|player| format!("{:>4} ", self.wins(player))).collect();
+        ctx.print_color_centered_at(centre.0, top + 5 + Self::ROWS as u32, white, Game::BACKGROUND_COLOUR, format!("Won{}", wins));
+
+        let bottom = centre.1 + 4;
+
+        match self.winner() {
+            Some(winner) => {
+                ctx.print_color_centered_at(centre.0, bottom, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("Player {} wins!", winner + 1));
+                ctx.print_color_centered_at(centre.0, bottom + 2, white, Game::BACKGROUND_COLOUR, "[R] Leave [T] Rematch");
+            },
+            None => {
+                ctx.print_color_centered_at(centre.0, bottom, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, format!("Player {} is up", self.current_player().unwrap_or(0) + 1));
+                ctx.print_color_centered_at(centre.0, bottom + 2, white, Game::BACKGROUND_COLOUR, "[R] Play [T] Leave");
+            }
+        }
+    }
+}
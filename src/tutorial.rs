@@ -0,0 +1,204 @@
+use bracket_terminal::prelude::BTerm;
+
+use crate::game::Game;
+use crate::engine::{Config, Engine, Direction, Position};
+use crate::engine::mode::Mode;
+use crate::engine::event::GameEvent;
+
+// The steps of the tutorial, each with a milestone to reach before moving on to the next
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Lesson {
+    Steer, // Make a few turns
+    Eat, // Eat fruit put just ahead of the snake
+    Walls, // Eat fruit put against a wall, turning away before running into it
+    UTurn, // Head back the way the snake came, with two quick turns the same way
+    ChaseTail, // Loop around right behind the end of the tail
+    Done
+}
+
+impl Lesson {
+    pub const ALL: [Lesson; 6] = [Lesson::Steer, Lesson::Eat, Lesson::Walls, Lesson::UTurn, Lesson::ChaseTail, Lesson::Done];
+
+    // What the player is asked to do, over two lines
+    pub fn prompt(self) -> [&'static str; 2] {
+        match self {
+            Self::Steer => ["Steer with the arrows", "or WASD: make 3 turns"],
+            Self::Eat => ["Eat the fruit to grow", "and score points"],
+            Self::Walls => ["Walls are deadly: eat", "and turn away in time"],
+            Self::UTurn => ["Make a U-turn: turn", "twice the same way, fast"],
+            Self::ChaseTail => ["Chase your tail: loop", "right behind its end"],
+            Self::Done => ["Tutorial complete!", "[Enter] Play"]
+        }
+    }
+
+    // How much progress the milestone takes: turns, fruit, U-turns or slithers next to the tail
+    pub fn goal(self) -> usize {
+        match self {
+            Self::Steer => 3,
+            Self::Eat => 2,
+            Self::Walls | Self::UTurn => 1,
+            Self::ChaseTail => 8,
+            Self::Done => 0
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|lesson| *lesson == self).unwrap();
+
+        Self::ALL[(index + 1).min(Self::ALL.len() - 1)]
+    }
+}
+
+// A guided run that teaches the basics a lesson at a time, placing fruit where each lesson needs it and
+// waiting for the player to reach its milestone before moving on
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Tutorial {
+    lesson: Lesson,
+    progress: usize, // Towards the lesson's goal
+    heading: Option<Direction>, // Which way the snake was heading after the last slither
+    last_turn: Option<(usize, Direction)>, // The slither of the latest turn, and the heading before it
+    fruit: Option<Position> // Where the lesson last put the fruit, so it's only moved once it's been eaten
+}
+
+impl Tutorial {
+    pub const FRUIT_AHEAD: i32 = 4; // How far in front of the snake fruit is put in the eating lesson
+    pub const U_TURN_SLITHERS: usize = 3; // The most slithers between the two turns of a U-turn
+
+    pub fn new() -> Self {
+        Self {
+            lesson: Lesson::Steer,
+            progress: 0,
+            heading: None,
+            last_turn: None,
+            fruit: None
+        }
+    }
+
+    // The settings tutorial runs are played with: classic rules on the same map, with nothing else going on
+    pub fn config(base: &Config) -> Config {
+        Config {
+            seed: base.seed,
+            dimensions: base.dimensions,
+            skin: base.skin,
+            mode: Mode::Classic,
+            power_ups: false,
+            ..Config::default()
+        }
+    }
+
+    pub fn lesson(&self) -> Lesson {
+        self.lesson
+    }
+
+    pub fn progress(&self) -> usize {
+        self.progress
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lesson == Lesson::Done
+    }
+
+    // Start the current lesson over on a fresh run, as after the snake dies
+    pub fn retry(&mut self) {
+        self.progress = 0;
+        self.heading = None;
+        self.last_turn = None;
+        self.fruit = None;
+    }
+
+    // Count what the latest slither did towards the lesson's milestone, moving on to the next lesson once
+    // it's reached, and put the fruit where the lesson wants it. Returns whether a lesson was completed
+    pub fn update(&mut self, engine: &mut Engine) -> bool {
+        let tick = engine.ticks();
+
+        for event in engine.events() {
+            match *event {
+                GameEvent::Turned(direction) => {
+                    let u_turn = self.last_turn.is_some_and(|(turned_at, before)| {
+                        direction == before.opposite() && tick - turned_at <= Self::U_TURN_SLITHERS
+                    });
+
+                    if self.lesson == Lesson::Steer || (self.lesson == Lesson::UTurn && u_turn) {
+                        self.progress += 1;
+                    }
+
+                    self.last_turn = self.heading.map(|heading| (tick, heading));
+                    self.heading = Some(direction);
+                },
+                GameEvent::FruitEaten {..} if self.lesson == Lesson::Eat || self.lesson == Lesson::Walls => self.progress += 1,
+                _ => {}
+            }
+        }
+
+        if let (Lesson::ChaseTail, Some(head), Some(tail)) = (self.lesson, engine.snake().front(), engine.snake().back()) {
+            if head.position.manhattan_distance(tail.position) == 1 {
+                self.progress += 1;
+            }
+        }
+
+        self.heading = Some(engine.snake().direction());
+
+        let completed = self.lesson != Lesson::Done && self.progress >= self.lesson.goal();
+
+        if completed {
+            self.lesson = self.lesson.next();
+            self.progress = 0;
+            self.last_turn = None;
+            self.fruit = None;
+        }
+
+        self.place_fruit(engine);
+
+        completed
+    }
+
+    // Move freshly spawned fruit to where the lesson needs it: just ahead of the snake, or against the
+    // wall it's heading for
+    fn place_fruit(&mut self, engine: &mut Engine) {
+        if self.fruit == Some(engine.fruit()) {
+            return;
+        }
+
+        let head = match engine.snake().front() {
+            Some(head) => head.position,
+            None => return
+        };
+        let ahead = Position::from(engine.snake().direction());
+        let dimensions = engine.config().dimensions;
+
+        let target = match self.lesson {
+            Lesson::Eat => (1..=Self::FRUIT_AHEAD).rev()
+                .map(|distance| head + ahead * distance)
+                .find(|position| position.in_bounds(dimensions)),
+            Lesson::Walls => (1..).map(|distance| head + ahead * distance)
+                .take_while(|position| position.in_bounds(dimensions))
+                .last(),
+            _ => return
+        };
+
+        if let Some(target) = target.filter(|target| engine.is_pathable(*target)) {
+            engine.set_fruit(target);
+            self.fruit = Some(target);
+        }
+    }
+
+    // The lesson's prompt along the top of the map, over the run
+    pub fn render(&self, ctx: &mut BTerm, dimensions: (u32, u32)) {
+        let centre = dimensions.0 / 2;
+        let [first, second] = self.lesson.prompt();
+
+        ctx.print_color_centered_at(centre, 1, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, first);
+        ctx.print_color_centered_at(centre, 2, Game::ASSIST_COLOUR, Game::BACKGROUND_COLOUR, second);
+
+        if !self.is_finished() {
+            ctx.print_color_centered_at(centre, 3, Game::FRUIT_COLOUR, Game::BACKGROUND_COLOUR, format!("{}/{}", self.progress, self.lesson.goal()));
+            ctx.print_color_centered_at(centre, dimensions.1 - 1, bracket_terminal::prelude::WHITE, Game::BACKGROUND_COLOUR, "[F1] Skip");
+        }
+    }
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
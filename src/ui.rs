@@ -0,0 +1,145 @@
+use bracket_terminal::prelude::{
+    BTerm,
+    RGB
+};
+
+use crate::game::Game;
+use crate::engine::skin::Skin;
+
+// A block of text wrapped to a fixed width, which can be scrolled through a window of rows
+pub struct DocumentView {
+    lines: Vec<String>,
+    width: usize,
+    height: usize,
+    scroll: usize
+}
+
+impl DocumentView {
+    pub const TEXT_COLOUR: RGB = RGB {r: 1.0, g: 1.0, b: 1.0};
+    pub const SCROLLBAR_COLOUR: RGB = RGB {r: 0.5, g: 0.5, b: 0.5};
+
+    // Lay out paragraphs of text into a `width` x `height` window. Paragraphs beginning with "- " are
+    // rendered as bullet points, with their wrapped lines indented to match
+    pub fn new(paragraphs: &[&str], width: usize, height: usize) -> Self {
+        let mut lines = Vec::new();
+
+        for paragraph in paragraphs {
+            let (first_prefix, prefix, text) = match paragraph.strip_prefix("- ") {
+                Some(text) => ("- ", "  ", text),
+                None => ("", "", *paragraph)
+            };
+
+            let mut line = first_prefix.to_string();
+            let mut line_has_words = false;
+
+            for word in text.split_whitespace() {
+                if line_has_words && line.chars().count() + 1 + word.chars().count() > width {
+                    lines.push(line);
+                    line = prefix.to_string();
+                    line_has_words = false;
+                }
+
+                if line_has_words {
+                    line.push(' ');
+                }
+
+                line.push_str(word);
+                line_has_words = true;
+            }
+
+            lines.push(line);
+        }
+
+        Self {
+            lines,
+            width,
+            height,
+            scroll: 0
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll = (self.scroll + 1).min(self.max_scroll());
+    }
+
+    // Draw the visible lines with their top-left corner at (x, y), plus a scrollbar if the text overflows
+    pub fn render(&self, ctx: &mut BTerm, x: u32, y: u32, skin: Skin) {
+        for (row, line) in self.lines.iter().skip(self.scroll).take(self.height).enumerate() {
+            ctx.print_color(x, y + row as u32, Self::TEXT_COLOUR, Game::BACKGROUND_COLOUR, line);
+        }
+
+        if self.max_scroll() > 0 {
+            let bar_x = x + self.width as u32;
+            let thumb = self.scroll * (self.height - 1) / self.max_scroll();
+
+            for row in 0..self.height {
+                let glyph = if row == thumb { skin.scrollbar.0 } else { skin.scrollbar.1 };
+
+                ctx.set(bar_x, y + row as u32, Self::SCROLLBAR_COLOUR, Game::BACKGROUND_COLOUR, bracket_terminal::prelude::to_cp437(glyph));
+            }
+        }
+    }
+}
+
+// A line of text typed in by the player, such as a name or a code, kept to the characters it accepts
+pub struct TextField {
+    text: String,
+    max_length: usize,
+    accepts: fn(char) -> bool
+}
+
+impl TextField {
+    pub fn new(max_length: usize, accepts: fn(char) -> bool) -> Self {
+        Self {
+            text: String::new(),
+            max_length,
+            accepts
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    // Add a character to the end, returning whether it was accepted
+    pub fn type_character(&mut self, character: char) -> bool {
+        if (self.accepts)(character) && self.text.chars().count() < self.max_length {
+            self.text.push(character);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.text.pop();
+    }
+
+    // Step the last character through the alphabet, starting one off if the field is empty. Anything
+    // other than a letter steps to 'A'
+    pub fn cycle(&mut self, forward: bool) {
+        let letter = match self.text.pop() {
+            Some(last) if last.is_ascii_alphabetic() => {
+                let index = last.to_ascii_uppercase() as u8 - b'A';
+
+                (b'A' + (index + if forward { 1 } else { 25 }) % 26) as char
+            },
+            _ => 'A'
+        };
+
+        self.type_character(letter);
+    }
+
+    // The text with a cursor after it, for drawing
+    pub fn display(&self) -> String {
+        format!("{}_", self.text)
+    }
+}
@@ -0,0 +1,182 @@
+use std::cell::{Cell, RefCell};
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, HtmlCanvasElement, TouchEvent, Url};
+
+// The element bracket-terminal draws into
+const CANVAS_ID: &str = "canvas";
+
+thread_local! {
+    // Taps on the canvas since the last frame, as fractions of its width and height
+    static TAPS: RefCell<Vec<(f64, f64)>> = RefCell::new(Vec::new());
+
+    // Whether the page has been hidden since the last frame, such as by switching tabs
+    static HIDDEN: Cell<bool> = Cell::new(false);
+}
+
+fn canvas() -> Option<HtmlCanvasElement> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(CANVAS_ID)?
+        .dyn_into::<HtmlCanvasElement>()
+        .ok()
+}
+
+pub fn is_touch_device() -> bool {
+    web_sys::window().is_some_and(|window| window.navigator().max_touch_points() > 0)
+}
+
+// Start collecting taps on the canvas, to be picked up with `take_taps`
+pub fn listen_for_taps() {
+    let canvas = match canvas() {
+        Some(canvas) => canvas,
+        None => return
+    };
+
+    let target = canvas.clone();
+    let on_touch = Closure::wrap(Box::new(move |event: TouchEvent| {
+        event.prevent_default(); // No scrolling, zooming or emulated mouse clicks
+        let bounds = target.get_bounding_client_rect();
+
+        if bounds.width() <= 0.0 || bounds.height() <= 0.0 {
+            return;
+        }
+
+        let touches = event.changed_touches();
+
+        TAPS.with(|taps| {
+            for touch in (0..touches.length()).filter_map(|index| touches.item(index)) {
+                taps.borrow_mut().push((
+                    (touch.client_x() as f64 - bounds.left()) / bounds.width(),
+                    (touch.client_y() as f64 - bounds.top()) / bounds.height()
+                ));
+            }
+        });
+    }) as Box<dyn FnMut(TouchEvent)>);
+
+    canvas.add_event_listener_with_callback("touchstart", on_touch.as_ref().unchecked_ref()).ok();
+    on_touch.forget(); // Needed for as long as the page is open
+}
+
+pub fn take_taps() -> Vec<(f64, f64)> {
+    TAPS.with(|taps| taps.borrow_mut().drain(..).collect())
+}
+
+// Start watching for the page being hidden, to be picked up with `take_hidden`. Browsers don't pass on
+// the canvas losing focus reliably, so the page visibility API stands in for it
+pub fn listen_for_visibility() {
+    let document = match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return
+    };
+
+    let target = document.clone();
+    let on_change = Closure::wrap(Box::new(move || {
+        if target.hidden() {
+            HIDDEN.with(|hidden| hidden.set(true));
+        }
+    }) as Box<dyn FnMut()>);
+
+    document.add_event_listener_with_callback("visibilitychange", on_change.as_ref().unchecked_ref()).ok();
+    on_change.forget(); // Needed for as long as the page is open
+}
+
+pub fn take_hidden() -> bool {
+    HIDDEN.with(|hidden| hidden.replace(false))
+}
+
+// How many of the screen's pixels each CSS pixel takes up, which is more than one on high-DPI screens
+pub fn device_pixel_ratio() -> f64 {
+    web_sys::window().map_or(1.0, |window| window.device_pixel_ratio())
+}
+
+// Retitle the page, which names the tab it's open in
+pub fn set_title(title: &str) {
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        document.set_title(title);
+    }
+}
+
+// Offer `bytes` to the player as a file download named `name`
+pub fn download(name: &str, bytes: &[u8], mime_type: &str) -> Result<(), String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| String::from("No document"))?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let mut options = BlobPropertyBag::new();
+    options.type_(mime_type);
+
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(|error| format!("{:?}", error))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(|error| format!("{:?}", error))?;
+
+    let anchor = document.create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlAnchorElement>().ok())
+        .ok_or_else(|| String::from("Couldn't create a link"))?;
+
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+
+    Url::revoke_object_url(&url).ok();
+
+    Ok(())
+}
+
+// Scale the game's canvas to fill as much of the browser window as it can without changing shape, and
+// again whenever the window is resized (which includes zooming, as that changes the device pixel
+// ratio). The canvas keeps the resolution bracket-terminal drew it at and is stretched with CSS
+pub fn fit_canvas_to_window() {
+    fit_canvas();
+
+    let on_resize = Closure::wrap(Box::new(fit_canvas) as Box<dyn FnMut()>);
+
+    if let Some(window) = web_sys::window() {
+        window.set_onresize(Some(on_resize.as_ref().unchecked_ref()));
+    }
+
+    on_resize.forget(); // Needed for as long as the page is open
+}
+
+fn fit_canvas() {
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return
+    };
+
+    let canvas = match canvas() {
+        Some(canvas) => canvas,
+        None => return
+    };
+
+    let viewport = (
+        window.inner_width().ok().and_then(|width| width.as_f64()).unwrap_or(0.0),
+        window.inner_height().ok().and_then(|height| height.as_f64()).unwrap_or(0.0)
+    );
+
+    let scale = canvas_scale((canvas.width(), canvas.height()), viewport, window.device_pixel_ratio());
+    let style = canvas.style();
+
+    style.set_property("width", &format!("{}px", canvas.width() as f64 * scale)).ok();
+    style.set_property("height", &format!("{}px", canvas.height() as f64 * scale)).ok();
+    style.set_property("image-rendering", "pixelated").ok();
+}
+
+// How many CSS pixels each canvas pixel should take up to fit the viewport. Where there's room, this is
+// rounded down to a whole number of device pixels so every tile comes out the same size and stays crisp
+fn canvas_scale(canvas: (u32, u32), viewport: (f64, f64), device_pixel_ratio: f64) -> f64 {
+    if canvas.0 == 0 || canvas.1 == 0 {
+        return 1.0;
+    }
+
+    let fit = (viewport.0 / canvas.0 as f64).min(viewport.1 / canvas.1 as f64);
+    let device_pixels = (fit * device_pixel_ratio).floor();
+
+    if device_pixels >= 1.0 {
+        device_pixels / device_pixel_ratio
+    } else {
+        fit
+    }
+}
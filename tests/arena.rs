@@ -0,0 +1,188 @@
+use snake_lib::{Config, DeathCause, Direction, Engine, GameEvent, Position};
+use snake_lib::engine::arena::{Arena, ArenaShape, Edge, Symmetry};
+use snake_lib::engine::controller::BoardView;
+use snake_lib::engine::difficulty::Difficulty;
+use snake_lib::engine::mode::{Mode, Obstacles};
+use snake_lib::level::{Level, LevelError};
+
+#[test]
+fn generated_arenas_wall_off_the_map_outside_their_shape() {
+    let circle = Arena::Shape(ArenaShape::Circle).mask((25, 25));
+    let cross = Arena::Shape(ArenaShape::Cross).mask((25, 25));
+    let donut = Arena::Shape(ArenaShape::Donut).mask((25, 25));
+
+    assert!(circle.is_wall(Position::new(0, 0)) && circle.is_open(Position::new(12, 12)) && circle.is_open(Position::new(0, 12)));
+    assert!(cross.is_wall(Position::new(2, 2)) && cross.is_open(Position::new(12, 0)) && cross.is_open(Position::new(0, 12)));
+    assert!(donut.is_wall(Position::new(12, 12)) && donut.is_open(Position::new(12, 2)));
+    assert!(!Arena::default().mask((25, 25)).has_walls());
+    assert!(!circle.is_open(Position::new(-1, 12))); // Off the map is never open
+}
+
+#[test]
+fn running_into_a_wall_of_the_arena_loses_the_game() {
+    // A wall across row 3, which the snake heads straight for
+    let mut rows = vec![".".repeat(12); 12];
+
+    rows[3] = "#".repeat(12);
+
+    let mut engine = Engine::new(Config {
+        dimensions: (12, 12),
+        arena: Arena::Mask(rows),
+        ..Config::default()
+    });
+
+    engine.advance(Some(Direction::North));
+
+    while !engine.is_over() {
+        engine.advance(None);
+    }
+
+    assert!(engine.events().iter().any(|event| matches!(event, GameEvent::Died {
+        cause: DeathCause::Wall,
+        position: Position {y: 3, ..},
+        ..
+    })));
+}
+
+#[test]
+fn snakes_start_clear_of_the_walls() {
+    let engine = Engine::new(Config {
+        arena: Arena::Shape(ArenaShape::Donut),
+        ..Config::default()
+    });
+
+    assert!(engine.snake().iter().all(|segment| engine.arena().is_open(segment.position)));
+    assert!(engine.empty_points().iter().all(|point| engine.arena().is_open(*point)));
+    assert!(engine.arena().is_open(engine.fruit()));
+}
+
+#[test]
+fn levels_can_shape_the_arena() {
+    let level = Level::parse("arena cross").unwrap();
+
+    assert_eq!(level.arena, Arena::Shape(ArenaShape::Cross));
+
+    let level = Level::parse("mask ..#\nmask ...").unwrap();
+    let mask = level.arena.mask((3, 3));
+
+    assert!(mask.is_wall(Position::new(2, 0)));
+    assert!(mask.is_open(Position::new(2, 1)));
+    assert!(mask.is_wall(Position::new(0, 2))); // Past the rows drawn
+    assert!(Level::parse("arena triangle").is_err());
+}
+
+#[test]
+fn snakes_start_on_the_spawn_points_of_a_level() {
+    let level = Level::parse("tag 3 20 spawn").unwrap();
+    let engine = Engine::new(Config {
+        tags: level.tags,
+        ..Config::default()
+    });
+
+    assert!(engine.snake().iter().any(|segment| segment.position == Position::new(3, 20)));
+    assert!(engine.snake().iter().all(|segment| segment.position.y == 20));
+}
+
+#[test]
+fn levels_that_cant_be_played_are_turned_away() {
+    let walled_off = Level::parse("mask ..#..\nmask ..#..\nmask ..#..").unwrap();
+    let blocked = Level::parse("arena donut\ntag 12 12 spawn").unwrap();
+    let too_big = Level::parse(&format!("mask {}", ".".repeat(30))).unwrap();
+
+    assert!(matches!(walled_off.validate((5, 3)), Err(LevelError::Unreachable(Position {x: 3, y: 0}))));
+    assert!(matches!(blocked.validate((25, 25)), Err(LevelError::SpawnBlocked(Position {x: 12, y: 12}))));
+    assert!(matches!(too_big.validate((25, 25)), Err(LevelError::TooBig {mask: (30, 1), ..})));
+    assert!(matches!(Level::parse("mask #").unwrap().validate((1, 1)), Err(LevelError::NoFloor)));
+    assert!(Level::parse("arena cross").unwrap().validate((25, 25)).is_ok());
+}
+
+#[test]
+fn obstacles_close_in_without_walling_off_the_arena() {
+    let mut engine = Engine::new(Config {
+        mode: Mode::Obstacles,
+        dimensions: (12, 12),
+        seed: 7,
+        ..Config::default()
+    });
+    let mut autopilot = Difficulty::NearPerfect.controller();
+
+    while !engine.is_over() && engine.run_state().time < Obstacles::WALL_SECONDS * 4.0 {
+        let direction = autopilot.decide(&BoardView::new(&engine, 0));
+
+        engine.advance(direction);
+    }
+
+    assert!(engine.arena().walls().count() >= 3);
+    assert_eq!(engine.arena().unreachable(), None);
+}
+
+#[test]
+fn obstacle_density_scatters_walls_without_walling_off_the_arena() {
+    let engine_with = |obstacles| Engine::new(Config {
+        seed: 3,
+        obstacles,
+        ..Config::default()
+    });
+    let sparse = engine_with(30);
+    let dense = engine_with(100);
+
+    assert_eq!(engine_with(0).arena().walls().count(), 0);
+    assert!(sparse.arena().walls().count() > 0);
+    assert!(dense.arena().walls().count() > sparse.arena().walls().count());
+    assert_eq!(dense.arena().unreachable(), None);
+    assert!(!dense.is_over());
+}
+
+#[test]
+fn edges_that_wrap_around_lead_to_the_opposite_edge() {
+    let level = Level::parse("edge east wrap\nedge west wrap").unwrap();
+    let mut engine = Engine::new(Config {
+        dimensions: (12, 12),
+        edges: level.edges,
+        ..Config::default()
+    });
+    let row = engine.snake()[0].position.y;
+
+    assert_eq!((level.edges.east, level.edges.north), (Edge::Wrap, Edge::Wall));
+
+    for _ in 0..30 {
+        engine.advance(None);
+    }
+
+    assert!(!engine.is_over());
+    assert_eq!(engine.snake()[0].position.y, row);
+    assert!(engine.snake().iter().all(|segment| engine.arena().is_open(segment.position)));
+    assert!(Level::parse("edge up wrap").is_err());
+
+    // Split down the middle, but joined back up round the edges
+    assert!(Level::parse("mask ..#..\nmask ..#..\nedge all wrap").unwrap().validate((5, 2)).is_ok());
+}
+
+#[test]
+fn symmetric_maps_give_rival_snakes_the_same_terrain() {
+    for symmetry in [Symmetry::Rotational, Symmetry::Mirror].iter().copied() {
+        let engine = Engine::new(Config {
+            mode: Mode::Versus,
+            seed: 5,
+            obstacles: 60,
+            symmetry,
+            ..Config::default()
+        });
+        let dimensions = engine.config().dimensions;
+        let image = |position| symmetry.image(position, dimensions).unwrap();
+        let heads: Vec<Position> = engine.snakes().iter().map(|snake| snake[0].position).collect();
+
+        assert!(engine.arena().walls().count() > 0);
+        assert!(engine.arena().walls().all(|wall| engine.arena().is_wall(image(wall))));
+        assert_eq!(heads[1], image(heads[0]));
+        assert_eq!(engine.snakes()[1].direction(), symmetry.facing(engine.snakes()[0].direction()));
+    }
+}
+
+#[test]
+fn stepping_back_through_symmetries_undoes_stepping_forward() {
+    for symmetry in Symmetry::ALL.iter().copied() {
+        assert_eq!(symmetry.next().previous(), symmetry);
+        assert_ne!(symmetry.previous(), symmetry);
+    }
+}
@@ -0,0 +1,78 @@
+use snake_lib::{Config, Direction, Engine, Position};
+use snake_lib::engine::Grid;
+use snake_lib::engine::board::Board;
+
+const BOARD: &str = "\
+..........
+.╔══>.....
+.║....*...
+.╚═╗..+...
+...║..#...
+";
+
+#[test]
+fn boards_round_trip_through_text() {
+    let board = Board::parse(BOARD).unwrap();
+
+    assert_eq!(board.snake.first(), Some(&Position::new(4, 1)));
+    assert_eq!(board.snake.last(), Some(&Position::new(3, 4)));
+    assert_eq!(board.snake.len(), 9);
+    assert_eq!(board.heading, Direction::East);
+    assert_eq!(board.fruit, Some(Position::new(6, 2)));
+    assert_eq!(board.to_string(), BOARD);
+}
+
+#[test]
+fn captured_boards_round_trip_through_text() {
+    let mut engine = Engine::new(Config {
+        dimensions: (12, 12),
+        ..Config::default()
+    });
+
+    for direction in [None, Some(Direction::South), None, Some(Direction::West), None, None].iter() {
+        engine.advance(*direction);
+    }
+
+    let board = Board::capture(&engine);
+
+    assert_eq!(board.snake.len(), engine.snake().len());
+    assert_eq!(Board::parse(&board.to_string()).unwrap(), board);
+}
+
+#[test]
+fn diagonal_boards_round_trip_through_text() {
+    let text = "\
+......
+...9..
+../...
+./....
+";
+    let board = Board::parse(text).unwrap();
+
+    assert_eq!(board.snake, [Position::new(3, 1), Position::new(2, 2), Position::new(1, 3)]);
+    assert_eq!(board.heading, Direction::NorthEast);
+    assert_eq!(board.to_string(), text);
+
+    let mut engine = Engine::new(Config {
+        dimensions: (20, 20),
+        grid: Grid::Diagonal,
+        ..Config::default()
+    });
+
+    for direction in [Some(Direction::SouthEast), None, None, None, None].iter() {
+        engine.advance(*direction);
+    }
+
+    let board = Board::capture(&engine);
+
+    assert_eq!(board.heading, Direction::SouthEast);
+    assert_eq!(board.snake.len(), engine.snake().len());
+    assert_eq!(Board::parse(&board.to_string()).unwrap(), board);
+}
+
+#[test]
+fn broken_boards_are_rejected() {
+    assert!(Board::parse("..>..\n.....\n..*.*\n").is_err()); // Two fruit
+    assert!(Board::parse("..>..\n....\n").is_err()); // A short row
+    assert!(Board::parse("..═>.\n.....\n║....\n").is_err()); // A segment not joined to the head
+}
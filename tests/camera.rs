@@ -0,0 +1,53 @@
+use bracket_terminal::prelude::Point;
+use snake_lib::Position;
+use snake_lib::engine::Grid;
+use snake_lib::camera::Camera;
+
+#[test]
+fn the_camera_keeps_the_focus_in_the_middle_of_the_view() {
+    let camera = Camera::following((100, 100), (25, 25), Position::new(50, 40));
+
+    assert!(camera.is_scrolling());
+    assert_eq!(camera.offset(), Position::new(38, 28));
+
+    let screen = camera.to_screen(Position::new(50, 40)).unwrap();
+
+    assert_eq!((screen.x, screen.y), (12, 12));
+    assert!(camera.to_screen(Position::new(10, 10)).is_none());
+}
+
+#[test]
+fn the_camera_stops_at_the_edges_of_the_map() {
+    let camera = Camera::following((100, 100), (25, 25), Position::new(2, 98));
+
+    assert_eq!(camera.offset(), Position::new(0, 75));
+}
+
+#[test]
+fn maps_that_fit_the_view_are_not_scrolled() {
+    let camera = Camera::following((25, 25), (25, 25), Position::new(24, 0));
+
+    assert!(!camera.is_scrolling());
+    assert_eq!(camera.offset(), Position::new(0, 0));
+
+    let screen = camera.to_screen(Position::new(24, 0)).unwrap();
+
+    assert_eq!((screen.x, screen.y), (24, 0));
+}
+
+#[test]
+fn hex_grids_are_drawn_two_columns_a_tile_with_the_odd_rows_shifted() {
+    let camera = Camera::new((10, 10), (25, 25)).with_grid(Grid::Hex);
+
+    let even = camera.to_screen(Position::new(3, 2)).unwrap();
+    let odd = camera.to_screen(Position::new(3, 3)).unwrap();
+
+    assert_eq!((even.x, even.y), (6, 2));
+    assert_eq!((odd.x, odd.y), (7, 3));
+    assert_eq!(camera.to_map(Point::new(7, 3)), Some(Position::new(3, 3)));
+    assert_eq!(camera.to_map(Point::new(7, 2)), None);
+
+    // Twelve tiles fit across the view, so wider maps scroll
+    assert!(!camera.is_scrolling());
+    assert!(Camera::new((13, 10), (25, 25)).with_grid(Grid::Hex).is_scrolling());
+}
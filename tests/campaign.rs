@@ -0,0 +1,80 @@
+use snake_lib::{Config, Engine, Position, Snake, Status};
+use snake_lib::campaign::Campaign;
+use snake_lib::engine::boss::{BossKind, Cutter};
+use snake_lib::engine::controller::{BoardView, Controller};
+use snake_lib::engine::goal::{Goal, GoalMode};
+use snake_lib::level::Level;
+
+#[test]
+fn every_campaign_level_has_a_goal_and_can_be_played() {
+    for level in Campaign::levels() {
+        assert!(level.goal.is_some(), "{} has no goal", level.name);
+        assert!(level.validate(Engine::MAP_DIMENSIONS).is_ok(), "{} isn't valid", level.name);
+    }
+}
+
+#[test]
+fn meeting_the_goal_passes_the_level() {
+    let mut engine = Engine::with_rules(Config::default(), Box::new(GoalMode::new(Goal::Length(Snake::STARTING_LENGTH))));
+
+    engine.advance(None);
+
+    assert_eq!(engine.status(), Status::Won);
+}
+
+#[test]
+fn running_out_of_time_fails_the_level() {
+    let goal = Goal::Score {points: 100, seconds: Some(1.0)};
+    let mut engine = Engine::with_rules(Config::default(), Box::new(GoalMode::new(goal)));
+
+    while !engine.is_over() {
+        engine.advance(None);
+    }
+
+    assert_eq!(engine.status(), Status::Lost);
+    assert!(engine.run_state().time < 2.0);
+}
+
+#[test]
+fn levels_can_set_a_goal() {
+    let level = Level::parse("goal score 20 within 90s").unwrap();
+
+    assert_eq!(level.goal, Some(Goal::Score {points: 20, seconds: Some(90.0)}));
+    assert_eq!(Level::parse("goal length 12").unwrap().goal, Some(Goal::Length(12)));
+    assert!(Level::parse("goal length").is_err());
+    assert!(Level::parse("goal score 5 within soon").is_err());
+}
+
+#[test]
+fn quicker_passes_earn_more_stars() {
+    let campaign = Campaign::new(0);
+    let par = campaign.level().par.unwrap();
+
+    assert_eq!(campaign.stars(par), Campaign::MAX_STARS);
+    assert_eq!(campaign.stars(par * 1.5), 2);
+    assert_eq!(campaign.stars(par * 3.0), 1);
+    assert!(Level::parse("par = never").is_err());
+}
+
+#[test]
+fn boss_levels_pit_the_player_against_a_rival() {
+    let level = Level::parse("boss guard\ngoal outeat 3").unwrap();
+    let engine = Engine::with_rules(Config::default(), Box::new(GoalMode::against_boss(level.goal.unwrap())));
+
+    assert_eq!(level.boss, Some(BossKind::Guard));
+    assert_eq!(engine.snakes().len(), 2);
+    assert!(engine.rules().rivals());
+    assert!(Level::parse("boss dragon").is_err());
+}
+
+#[test]
+fn the_cutter_heads_for_the_tiles_ahead_of_the_player() {
+    let engine = Engine::with_rules(Config::default(), Box::new(GoalMode::against_boss(Goal::Survive(60.0))));
+    let player = engine.snakes()[0].front().unwrap().position;
+    let target = player + Position::from(engine.snakes()[0].direction()) * Cutter::AHEAD;
+    let view = BoardView::new(&engine, 1);
+    let head = view.head().unwrap();
+    let direction = Cutter.decide(&view).unwrap();
+
+    assert!((head + Position::from(direction)).manhattan_distance(target) < head.manhattan_distance(target));
+}
@@ -0,0 +1,40 @@
+use snake_lib::Config;
+use snake_lib::engine::mode::Mode;
+use snake_lib::engine::difficulty::Difficulty;
+use snake_lib::engine::versus::WinCondition;
+use snake_lib::challenge::{Challenge, ChallengeError};
+
+#[test]
+fn a_challenge_code_sets_up_the_same_run() {
+    let played = Config {
+        seed: u64::MAX - 7,
+        dimensions: (30, 40),
+        mode: Mode::Versus,
+        power_ups: false,
+        difficulty: Difficulty::Greedy,
+        win_condition: WinCondition::LastAlive,
+        ..Config::default()
+    };
+    let code = Challenge::of(&played).code();
+    let mut config = Config::default();
+
+    assert!(code.len() <= Challenge::MAX_LENGTH);
+
+    Challenge::parse(&format!(" {} ", code)).unwrap().apply(&mut config);
+
+    assert_eq!(Challenge::of(&config), Challenge::of(&played));
+}
+
+#[test]
+fn mistyped_challenge_codes_are_turned_away() {
+    let code = Challenge::of(&Config {
+        seed: 42,
+        ..Config::default()
+    }).code();
+    let last = code.chars().last().unwrap();
+    let mistyped = format!("{}{}", &code[..code.len() - 1], if last == 'z' { 'y' } else { 'z' });
+
+    assert_eq!(Challenge::parse(&mistyped), Err(ChallengeError::Mistyped));
+    assert_eq!(Challenge::parse("0OIl"), Err(ChallengeError::NotACode));
+    assert_eq!(Challenge::parse(""), Err(ChallengeError::NotACode));
+}
@@ -0,0 +1,79 @@
+use snake_lib::{Config, Direction, Engine, Outcome, Position};
+
+// A headless driver for the engine: plays scripted input against a seeded run on a map of any size,
+// and keeps the outcome of every slither for tests to check
+pub struct Driver {
+    pub engine: Engine,
+    pub outcomes: Vec<Outcome>
+}
+
+impl Driver {
+    pub const PARKED_FRUIT: Position = Position::new(0, 0); // Out of the way of the snake's starting path
+
+    pub fn new(config: Config) -> Self {
+        let mut engine = Engine::new(config);
+
+        engine.set_fruit(Self::PARKED_FRUIT);
+
+        Self {
+            engine,
+            outcomes: Vec::new()
+        }
+    }
+
+    pub fn on_map(width: u32, height: u32) -> Self {
+        Self::new(Config {
+            dimensions: (width, height),
+            ..Config::default()
+        })
+    }
+
+    // The snake's head, which starts one tile east of the map's centre
+    pub fn head(&self) -> Position {
+        self.engine.snake()[0].position
+    }
+
+    pub fn advance(&mut self, direction: Option<Direction>) -> Outcome {
+        let outcome = self.engine.advance(direction);
+
+        self.outcomes.push(outcome);
+
+        outcome
+    }
+
+    // Play a script with one character per slither: 'N', 'E', 'S' or 'W' to steer, '9', '3', '1' or '7' to
+    // steer diagonally as on a numpad, '.' to carry on
+    pub fn play(&mut self, script: &str) -> Vec<Outcome> {
+        script.chars()
+            .map(|step| {
+                let direction = match step {
+                    'N' => Some(Direction::North),
+                    'E' => Some(Direction::East),
+                    'S' => Some(Direction::South),
+                    'W' => Some(Direction::West),
+                    '9' => Some(Direction::NorthEast),
+                    '3' => Some(Direction::SouthEast),
+                    '1' => Some(Direction::SouthWest),
+                    '7' => Some(Direction::NorthWest),
+                    '.' => None,
+                    _ => panic!("Unknown script step '{}'", step)
+                };
+
+                self.advance(direction)
+            })
+            .collect()
+    }
+
+    // Carry on straight until the run is over, returning how many slithers that took
+    pub fn play_until_over(&mut self, max_slithers: usize) -> usize {
+        for slithers in 1..=max_slithers {
+            self.advance(None);
+
+            if self.engine.is_over() {
+                return slithers;
+            }
+        }
+
+        panic!("Run still going after {} slithers", max_slithers);
+    }
+}
@@ -0,0 +1,109 @@
+use bracket_terminal::prelude::EMBED;
+use snake_lib::display::{CustomFont, DisplaySettings, FontChoice, Scale};
+
+#[test]
+fn fonts_are_parsed_by_name() {
+    for font in FontChoice::ALL.iter().copied() {
+        assert_eq!(FontChoice::parse(font.name()), Some(font));
+    }
+
+    assert_eq!(FontChoice::parse("vga"), Some(FontChoice::Vga));
+    assert_eq!(FontChoice::parse("Comic Sans"), None);
+}
+
+#[test]
+fn display_settings_survive_a_round_trip() {
+    let settings = DisplaySettings {
+        tile_size: 32,
+        scale: Scale::Percent(150),
+        font: FontChoice::Custom,
+        font_file: Some("fonts/curly.png".to_string()),
+        vsync: false,
+        fps_cap: None
+    };
+    let mut parsed = DisplaySettings::default();
+
+    for (key, value) in settings.serialise().lines().filter_map(|line| line.split_once('=')) {
+        parsed.parse(key, value);
+    }
+
+    assert_eq!(parsed, settings);
+
+    // Sizes far outside those the window can be built with are ignored
+    parsed.parse("tile_size", "1000");
+    parsed.parse("scale", "0%");
+    parsed.parse("fps_cap", "0");
+    parsed.parse("font", "Wingdings");
+    assert_eq!(parsed, settings);
+}
+
+#[test]
+fn tile_sizes_step_through_those_offered() {
+    let mut settings = DisplaySettings::default();
+
+    settings.step_tile_size(true);
+    assert_eq!(settings.tile_size, 32);
+
+    for _ in 0..DisplaySettings::TILE_SIZES.len() {
+        settings.step_tile_size(false);
+    }
+
+    assert_eq!(settings.tile_size, DisplaySettings::TILE_SIZES[0]);
+}
+
+#[test]
+fn high_dpi_screens_are_scaled_up_to_suit() {
+    let uhd = Some((3840, 2160));
+    let window = (625, 625);
+
+    // A 4K screen the backend reports as ordinary is made up for, unless the backend already scales it
+    assert_eq!(Scale::Auto.factor(1.0, Some(2160)), 2.0);
+    assert_eq!(Scale::Auto.window_factor(1.0, uhd, window), 2.0);
+    assert_eq!(Scale::Auto.window_factor(2.0, uhd, (1250, 1250)), 1.0);
+    assert_eq!(Scale::Auto.factor(1.0, Some(1080)), 1.0);
+    assert_eq!(Scale::Auto.factor(1.0, Some(1440)), 1.25);
+
+    // A scale that's been set wins out over the screen's, but the window still fits on it
+    assert_eq!(Scale::Percent(150).window_factor(1.0, uhd, window), 1.5);
+    assert_eq!(Scale::Percent(100).window_factor(2.0, uhd, (1250, 1250)), 0.5);
+    assert!(Scale::Percent(300).window_factor(1.0, Some((1920, 1080)), window) * 625.0 <= 1080.0);
+}
+
+#[test]
+fn scales_step_round_from_automatic() {
+    assert_eq!(Scale::Auto.step(true), Scale::Percent(Scale::PERCENTS[0]));
+    assert_eq!(Scale::Auto.step(false), Scale::Percent(300));
+    assert_eq!(Scale::Percent(300).step(true), Scale::Auto);
+    assert_eq!(Scale::parse("125%"), Some(Scale::Percent(125)));
+    assert_eq!(Scale::parse("AUTO"), Some(Scale::Auto));
+    assert_eq!(Scale::parse("big"), None);
+}
+
+#[test]
+fn frame_rate_changes_wait_for_a_restart() {
+    let window = DisplaySettings::default();
+    let mut settings = window.clone();
+
+    settings.font = FontChoice::Vga;
+    assert!(!settings.needs_restart_from(&window));
+
+    settings.step_fps_cap(true);
+    assert_eq!(settings.fps_cap, Some(120));
+    assert!(settings.needs_restart_from(&window));
+
+    settings.step_fps_cap(true);
+    settings.step_fps_cap(true);
+    assert_eq!(settings.fps_cap, None);
+    assert_eq!(settings.describe_fps_cap(), "Off");
+}
+
+#[test]
+fn font_sheets_are_measured_by_their_glyphs() {
+    let terminal = EMBED.lock().get_resource("resources/terminal8x8.png".to_string()).unwrap();
+    let vga = EMBED.lock().get_resource("resources/vga8x16.png".to_string()).unwrap();
+
+    assert_eq!(CustomFont::glyph_dimensions(terminal), Ok((8, 8)));
+    assert_eq!(CustomFont::glyph_dimensions(vga), Ok((8, 16)));
+    assert!(CustomFont::glyph_dimensions(b"not a png").is_err());
+    assert!(CustomFont::load("no-such-font.png").is_err());
+}
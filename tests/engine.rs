@@ -0,0 +1,801 @@
+mod common;
+
+use std::convert::TryFrom;
+
+use proptest::prelude::*;
+
+use snake_lib::{Config, DeathCause, Direction, Engine, Game, GameEvent, Outcome, Position, Snake, Status};
+use snake_lib::engine::Grid;
+use snake_lib::engine::mode::{Mode, Royale};
+use snake_lib::engine::fruit::{FruitKind, FruitSpec, FruitTable};
+use snake_lib::engine::hazard::HazardKind;
+use snake_lib::engine::power_up::PowerUp;
+use snake_lib::engine::speed::{LengthSpeed, SpeedCurve, Timestep};
+use snake_lib::engine::puzzle::Scenario;
+use snake_lib::engine::schedule::Schedule;
+use snake_lib::engine::controller::{Controller, KeyboardController};
+use snake_lib::engine::autopilot::Autopilot;
+use snake_lib::engine::difficulty::Difficulty;
+use snake_lib::engine::versus::WinCondition;
+use snake_lib::engine::pathfinding::AStarBot;
+use snake_lib::engine::hamiltonian::{HamiltonianBot, HamiltonianCycle};
+
+use common::Driver;
+
+#[test]
+fn running_into_the_wall_loses_the_game() {
+    let mut driver = Driver::on_map(10, 10);
+
+    // The head starts at x = 6, reaches the edge after 3 slithers, leaves the map on the 4th and the
+    // collision is caught on the 5th
+    assert_eq!(driver.play_until_over(20), 5);
+    assert_eq!(driver.engine.status(), Status::Lost);
+    assert!(matches!(driver.engine.events(), &[GameEvent::Died {
+        cause: DeathCause::Wall,
+        snake: 0,
+        position: Position {x: 9, ..}
+    }]));
+}
+
+#[test]
+fn run_titles_name_the_mode_and_follow_the_score() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+
+    assert_eq!(Game::run_title(&driver.engine), "Snake \u{2014} Classic \u{2014} 0");
+
+    driver.engine.set_fruit(Position::new(head.x + 1, head.y));
+    driver.play(".");
+
+    assert_ne!(driver.engine.score().points(), 0);
+    assert_eq!(Game::run_title(&driver.engine), format!("Snake \u{2014} Classic \u{2014} {}", driver.engine.score().points()));
+}
+
+#[test]
+fn a_shield_survives_one_wall_collision() {
+    let mut driver = Driver::on_map(10, 10);
+    let head = driver.head();
+
+    driver.engine.set_pickup(Position::new(head.x + 1, head.y), PowerUp::Shield);
+    driver.play(".");
+
+    assert_eq!(driver.engine.events(), &[GameEvent::PowerUpCollected(PowerUp::Shield)]);
+    assert!(driver.engine.snake().has_effect(PowerUp::Shield));
+
+    // Instead of leaving the map on the 4th slither the snake stops at the edge, and without the shield
+    // it leaves on the next, caught as before one slither later
+    driver.play("...");
+    assert_eq!(driver.engine.events(), &[GameEvent::Shielded]);
+    assert!(!driver.engine.snake().has_effect(PowerUp::Shield));
+    assert_eq!(driver.play_until_over(20), 2);
+}
+
+#[test]
+fn eating_fruit_grows_the_snake_and_scores() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+
+    driver.engine.set_fruit(Position::new(head.x + 2, head.y));
+
+    let worth = driver.engine.config().fruits.spec(driver.engine.fruit_kind()).points;
+
+    assert_eq!(driver.play(".."), vec![Outcome::Moved, Outcome::Ate]);
+    assert_eq!(driver.engine.snake().len(), Snake::STARTING_LENGTH + 1);
+    assert_eq!(driver.engine.score().points(), worth);
+    assert_ne!(driver.engine.fruit(), driver.head());
+}
+
+#[test]
+fn turning_back_on_the_neck_is_ignored() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+
+    driver.play("W");
+
+    assert_eq!(driver.head(), Position::new(head.x + 1, head.y));
+    assert_eq!(driver.engine.snake().direction(), Direction::East);
+}
+
+#[test]
+fn directions_turn_and_convert_to_and_from_steps() {
+    for direction in Direction::ALL.iter().copied() {
+        let step = Position::from(direction);
+
+        assert_eq!(direction.turn_left().turn_right(), direction);
+        assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+        assert_eq!(<(i32, i32)>::from(direction), (step.x, step.y));
+        assert_eq!(Direction::try_from(step), Ok(direction));
+        assert_eq!(Direction::try_from(bracket_terminal::prelude::Point::new(step.x, step.y)), Ok(direction));
+    }
+
+    assert_eq!(Direction::North.turn_right(), Direction::East);
+    assert_eq!(Direction::North.turn_left(), Direction::West);
+    assert_eq!(Direction::try_from(Position::new(1, 1)), Ok(Direction::SouthEast));
+    assert!(Direction::try_from(Position::new(0, 2)).is_err());
+    assert!(Direction::try_from(Position::new(0, 0)).is_err());
+}
+
+#[test]
+fn diagonal_moves_are_only_taken_on_the_diagonal_grid() {
+    let mut grid = Driver::on_map(25, 25);
+    let mut diagonal = Driver::new(Config {
+        grid: Grid::Diagonal,
+        ..Config::default()
+    });
+    let head = grid.head();
+
+    grid.play("9");
+    diagonal.play("9");
+
+    assert_eq!(grid.head(), Position::new(head.x + 1, head.y));
+    assert_eq!(diagonal.head(), Position::new(head.x + 1, head.y - 1));
+
+    // Turning back down the diagonal onto the neck is ignored, like any other reversal
+    diagonal.play("1");
+
+    assert_eq!(diagonal.head(), Position::new(head.x + 2, head.y - 2));
+    assert_eq!(diagonal.engine.snake().direction(), Direction::NorthEast);
+
+    // Up the diagonal and back down again, peaking in a flat segment
+    diagonal.play("..3.");
+
+    let glyphs: Vec<char> = diagonal.engine.snake().iter().map(|segment| segment.glyph).collect();
+
+    assert_eq!(glyphs, ['\\', '\\', '═', '/', '/']);
+}
+
+#[test]
+fn hex_tiles_are_a_step_apart_across_the_shifted_rows() {
+    let hex = Grid::Hex;
+    let (even, odd) = (Position::new(4, 4), Position::new(4, 5));
+
+    assert_eq!(hex.step(even, Direction::NorthEast), Position::new(4, 3));
+    assert_eq!(hex.step(odd, Direction::NorthEast), Position::new(5, 4));
+    assert_eq!(hex.step(even, Direction::SouthWest), Position::new(3, 5));
+    assert_eq!(hex.direction_between(odd, Position::new(4, 4)), Some(Direction::NorthWest));
+    assert_eq!(hex.direction_between(even, Position::new(4, 5)), Some(Direction::SouthEast));
+    assert_eq!(hex.neighbours(even).filter(|tile| hex.distance(even, *tile) == 1).count(), 6);
+    assert_eq!(hex.distance(even, Position::new(7, 4)), 3);
+    assert_eq!(hex.distance(even, Position::new(5, 8)), 4);
+    assert_eq!(Grid::Diagonal.distance(even, Position::new(7, 8)), 4);
+    assert_eq!(Grid::Square.distance(even, Position::new(7, 8)), 7);
+}
+
+#[test]
+fn hex_moves_have_no_straight_up_or_down() {
+    let mut hex = Driver::new(Config {
+        grid: Grid::Hex,
+        ..Config::default()
+    });
+    let head = hex.head();
+
+    assert_eq!(head.y % 2, 0);
+
+    hex.play("9");
+
+    assert_eq!(hex.head(), Position::new(head.x, head.y - 1));
+
+    hex.play("9");
+
+    assert_eq!(hex.head(), Position::new(head.x + 1, head.y - 2));
+
+    // Up leans the way the snake is heading, and turning back on the neck is ignored
+    hex.play("N");
+
+    assert_eq!(hex.head(), Position::new(head.x + 1, head.y - 3));
+    assert_eq!(hex.engine.snake().direction(), Direction::NorthEast);
+
+    hex.play("1");
+
+    assert_eq!(hex.head(), Position::new(head.x + 2, head.y - 4));
+}
+
+#[test]
+fn the_keyboard_plays_back_the_last_turn_that_can_be_taken() {
+    let driver = Driver::on_map(25, 25);
+    let mut keyboard = KeyboardController::default();
+
+    keyboard.press(Direction::North);
+    keyboard.press(Direction::West); // Back onto the neck
+
+    assert_eq!(keyboard.decide(&driver.engine.view()), Some(Direction::North));
+    assert_eq!(keyboard.decide(&driver.engine.view()), None);
+}
+
+#[test]
+fn confusion_turns_the_keyboard_back_to_front() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+    let mut keyboard = KeyboardController::default();
+
+    driver.engine.set_pickup(Position::new(head.x + 1, head.y), PowerUp::Confusion);
+    driver.play(".");
+
+    keyboard.press(Direction::North);
+
+    assert_eq!(keyboard.decide(&driver.engine.view()), Some(Direction::South));
+}
+
+#[test]
+fn the_autopilot_reaches_the_fruit() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+    let mut autopilot = Autopilot::default();
+
+    driver.engine.set_fruit(Position::new(head.x - 3, head.y + 4));
+
+    for _ in 0..20 {
+        let direction = autopilot.decide(&driver.engine.view());
+
+        if driver.advance(direction) == Outcome::Ate {
+            break;
+        }
+    }
+
+    assert_eq!(driver.outcomes.last(), Some(&Outcome::Ate));
+}
+
+#[test]
+fn bots_of_every_difficulty_reach_the_fruit() {
+    for difficulty in Difficulty::ALL.iter().copied() {
+        let mut driver = Driver::on_map(25, 25);
+        let head = driver.head();
+        let mut bot = difficulty.controller();
+
+        driver.engine.set_fruit(Position::new(head.x - 3, head.y + 4));
+
+        for _ in 0..20 {
+            let direction = bot.decide(&driver.engine.view());
+
+            if driver.advance(direction) == Outcome::Ate {
+                break;
+            }
+        }
+
+        assert_eq!(driver.outcomes.last(), Some(&Outcome::Ate), "{} bot missed the fruit", difficulty.name());
+    }
+}
+
+#[test]
+fn the_pathfinding_bot_grows_long_without_dying_early() {
+    let mut driver = Driver::new(Config {
+        dimensions: (10, 10),
+        fruits: only(&[FruitKind::Apple]), // The planner assumes fruit stays put, so no mice
+        power_ups: false, // Nor magnets
+        ..Config::default()
+    });
+    let mut bot = AStarBot::default();
+
+    driver.engine.set_fruit(Position::new(2, 7));
+
+    for _ in 0..2000 {
+        let direction = bot.decide(&driver.engine.view());
+
+        if driver.engine.is_over() {
+            break;
+        }
+
+        driver.advance(direction);
+    }
+
+    assert!(driver.engine.score().fruits_eaten() >= 60, "Only ate {} fruit", driver.engine.score().fruits_eaten());
+}
+
+#[test]
+fn hamiltonian_cycles_visit_every_tile_once() {
+    for (width, height) in [(2, 2), (4, 3), (3, 4), (6, 6), (25, 24)] {
+        let cycle = HamiltonianCycle::new((width, height)).unwrap();
+        let mut position = Position::new(0, 0);
+
+        for step in 0..cycle.len() {
+            assert_eq!(cycle.index_of(position), Some(step));
+
+            let next = cycle.next(position).unwrap();
+
+            assert_eq!(position.manhattan_distance(next), 1);
+            position = next;
+        }
+
+        assert_eq!(position, Position::new(0, 0));
+        assert_eq!(cycle.len(), (width * height) as usize);
+    }
+
+    assert!(HamiltonianCycle::new((25, 25)).is_none());
+}
+
+#[test]
+fn the_hamiltonian_bot_fills_the_map() {
+    let mut driver = Driver::on_map(10, 10);
+    let mut bot = HamiltonianBot::default();
+
+    for _ in 0..20_000 {
+        if driver.engine.is_over() {
+            break;
+        }
+
+        let direction = bot.decide(&driver.engine.view());
+
+        driver.advance(direction);
+    }
+
+    assert_eq!(driver.engine.status(), Status::Won);
+}
+
+#[test]
+fn turning_into_the_body_is_a_tail_collision() {
+    let mut driver = Driver::on_map(25, 25);
+
+    // Uncoil, then loop back round into the body
+    let outcomes = driver.play("....NWS.");
+
+    assert_eq!(outcomes.last(), Some(&Outcome::Lost));
+    assert!(matches!(driver.engine.events(), &[GameEvent::Died {
+        cause: DeathCause::Tail,
+        snake: 0,
+        ..
+    }]));
+}
+
+#[test]
+fn fleeing_fruit_steps_away_from_the_snake() {
+    let mut driver = Driver::new(Config {
+        fruits: only(&[FruitKind::Mouse]),
+        ..Config::default()
+    });
+    let head = driver.head();
+    let mouse = Position::new(head.x + 5, head.y);
+
+    driver.engine.set_fruit(mouse);
+    driver.play("..");
+
+    assert_eq!(driver.engine.fruit(), mouse); // Not yet time for it to move
+
+    driver.play(".");
+
+    assert_eq!(driver.engine.fruit().manhattan_distance(driver.head()), mouse.manhattan_distance(driver.head()) + 1);
+    assert_eq!(driver.engine.fruit_kind(), FruitKind::Mouse);
+}
+
+#[test]
+fn fruit_left_to_rot_spawns_elsewhere() {
+    let mut fruits = FruitTable::default();
+
+    fruits.set_rot_seconds(Some(1.0));
+
+    let mut driver = Driver::new(Config {
+        fruits,
+        ..Config::default()
+    });
+    let rots_after = 1.0 + FruitTable::ROTTEN_SECONDS;
+    let mut circle = "S...W...N...E...".chars().cycle(); // Well clear of the parked fruit
+
+    while !driver.engine.events().contains(&GameEvent::FruitRotted) {
+        assert_eq!(driver.engine.fruit(), Driver::PARKED_FRUIT);
+        assert!(driver.engine.run_state().time < rots_after + 1.0, "Fruit never rotted");
+
+        driver.play(&circle.next().unwrap().to_string());
+    }
+
+    assert!(driver.engine.run_state().time >= rots_after);
+    assert_ne!(driver.engine.fruit(), Driver::PARKED_FRUIT);
+    assert_eq!(driver.engine.fruit_rot(), None);
+}
+
+#[test]
+fn levels_can_guard_fruit_with_bombs() {
+    let mut schedule = Schedule::default();
+
+    schedule.parse_line(1, "at 0s spawn bomb near fruit").unwrap();
+
+    let mut driver = Driver::new(Config {
+        schedule,
+        ..Config::default()
+    });
+
+    driver.play(".");
+
+    let bombs: Vec<Position> = driver.engine.hazards().iter()
+        .filter_map(|hazard| match hazard.kind {
+            HazardKind::Bomb(position) => Some(position),
+            _ => None
+        })
+        .collect();
+
+    assert_eq!(bombs.len(), 1);
+    assert_eq!(bombs[0].manhattan_distance(driver.engine.fruit()), 1);
+    assert!(!driver.engine.hazards()[0].is_armed()); // Still blinking a warning
+}
+
+// A fruit table where only `kinds` ever spawn
+fn only(kinds: &[FruitKind]) -> FruitTable {
+    let mut table = FruitTable::default();
+
+    for kind in FruitKind::ALL.iter().copied().filter(|kind| !kinds.contains(kind)) {
+        table.set(kind, FruitSpec {
+            weight: 0.0,
+            ..table.spec(kind)
+        });
+    }
+
+    table
+}
+
+#[test]
+fn phasing_passes_through_the_body() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+
+    driver.engine.set_pickup(Position::new(head.x + 1, head.y), PowerUp::Phase);
+
+    // The same loop back into the body as a tail collision, which the snake now slithers through
+    let outcomes = driver.play("....NWS..");
+
+    assert!(!outcomes.contains(&Outcome::Lost));
+    assert!(driver.engine.snake().has_effect(PowerUp::Phase));
+    assert_eq!(driver.engine.status(), Status::Playing);
+}
+
+#[test]
+fn a_magnet_pulls_the_fruit_towards_the_head() {
+    let mut driver = Driver::on_map(25, 25);
+    let head = driver.head();
+
+    driver.engine.set_pickup(Position::new(head.x + 1, head.y), PowerUp::Magnet);
+    driver.play("....");
+
+    // Pulled a tile on each of the three slithers after the magnet was collected
+    assert_eq!(driver.engine.fruit().manhattan_distance(Driver::PARKED_FRUIT), 3);
+}
+
+#[test]
+fn speed_curves_step_up_to_their_maximum() {
+    assert_eq!(SpeedCurve::CONSTANT.factor(100), 1.0);
+    assert_eq!(SpeedCurve::STEPWISE.factor(4), 1.0);
+    assert!((SpeedCurve::STEPWISE.factor(12) - 1.2).abs() < 1e-9);
+    assert_eq!(SpeedCurve::STEPWISE.factor(1000), SpeedCurve::STEPWISE.maximum);
+}
+
+#[test]
+fn longer_snakes_pick_up_the_pace_with_length_speed() {
+    assert_eq!(LengthSpeed::OFF.factor(200), 1.0);
+    assert_eq!(LengthSpeed::STEEP.factor(Snake::STARTING_LENGTH), 1.0);
+    assert!((LengthSpeed::STEEP.factor(25) - 2.0).abs() < 1e-9);
+    assert_eq!(LengthSpeed::GENTLE.factor(1000), LengthSpeed::GENTLE.maximum);
+
+    // A snake laid out across the whole map, to start out long
+    let long = Scenario {
+        snake: (0..25).rev().map(|x| Position::new(x, 12)).collect(),
+        fruit: vec![Position::new(0, 0)]
+    };
+    let config = Config {
+        length_speed: LengthSpeed::STEEP,
+        ..Config::default()
+    };
+    let short = Engine::new(config.clone());
+    let long = Engine::new(Config {
+        scenario: Some(long),
+        ..config
+    });
+
+    assert_eq!(short.slithers_per_second(), Engine::new(Config::default()).slithers_per_second());
+    assert!((long.slithers_per_second() - short.slithers_per_second() * 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn the_timestep_keeps_the_same_pace_at_any_frame_rate() {
+    // Fast enough that a slow frame is owed several slithers at once
+    let tick_length = 1.0 / 240.0;
+    let slithers_at = |fps: f64| {
+        let mut timestep = Timestep::default();
+        let mut slithers = 0;
+
+        for _ in 0..(10.0 * fps) as usize {
+            timestep.bank(1.0 / fps, tick_length);
+
+            while timestep.take(tick_length) {
+                slithers += 1;
+            }
+        }
+
+        slithers
+    };
+
+    for fps in [30.0, 60.0, 144.0].iter().copied() {
+        let slithers: i32 = slithers_at(fps);
+
+        assert!((slithers - 2400).abs() <= 1, "{} slithers at {} FPS", slithers, fps);
+    }
+}
+
+#[test]
+fn the_timestep_catches_up_on_long_frames_but_not_stalls() {
+    let tick_length = 0.1;
+    let mut timestep = Timestep::default();
+
+    // A frame a fifth of a second long is made up in full
+    assert_eq!(timestep.bank(0.2, tick_length), 0.0);
+    assert!(timestep.take(tick_length) && timestep.take(tick_length));
+    assert!(!timestep.take(tick_length));
+
+    // A two-second stall is only made up to the limit, and the rest is let go
+    let dropped = timestep.bank(2.0, tick_length);
+
+    assert!((dropped - (2.0 - tick_length - Timestep::MAX_CATCH_UP_SECONDS)).abs() < 1e-9);
+}
+
+#[test]
+fn coop_puts_two_snakes_on_the_map_with_a_fruit_each() {
+    let driver = Driver::new(Config {
+        mode: Mode::Coop,
+        ..Config::default()
+    });
+
+    assert_eq!(driver.engine.snakes().len(), 2);
+    assert_eq!(driver.engine.fruits().len(), 2);
+    assert_ne!(driver.engine.snakes()[0][0].position, driver.engine.snakes()[1][0].position);
+}
+
+#[test]
+fn running_into_the_other_snake_loses_coop_for_both() {
+    let mut driver = Driver::new(Config {
+        mode: Mode::Coop,
+        ..Config::default()
+    });
+    let mut causes = Vec::new();
+
+    // The first snake heads off west along a row the second then crosses on its way north
+    driver.engine.steer_snake(1, Direction::North);
+    driver.play("SW");
+
+    while !driver.engine.is_over() && driver.outcomes.len() < 20 {
+        driver.advance(None);
+        causes.extend(driver.engine.events().iter().filter_map(|event| match event {
+            GameEvent::Died { cause, .. } => Some(*cause),
+            _ => None
+        }));
+    }
+
+    assert_eq!(causes, vec![DeathCause::Snake]);
+    assert_eq!(driver.engine.status(), Status::Lost);
+    assert!(driver.engine.snakes().iter().all(|snake| !snake.alive));
+}
+
+// Circle a small square with the player's snake, while `rival` (if any) steers the other, until the match
+// is decided
+fn play_versus(driver: &mut Driver, mut rival: Option<Box<dyn Controller>>) {
+    for step in "NNWWSSEE".chars().cycle().take(1000) {
+        if driver.engine.is_over() {
+            return;
+        }
+
+        if let Some(direction) = rival.as_mut().and_then(|rival| rival.decide(&driver.engine.view_of(1))) {
+            driver.engine.steer_snake(1, direction);
+        }
+
+        driver.play(&step.to_string());
+    }
+
+    panic!("Match still going after 1000 slithers");
+}
+
+#[test]
+fn a_rival_first_to_the_target_wins_versus() {
+    let mut driver = Driver::new(Config {
+        mode: Mode::Versus,
+        win_condition: WinCondition::FirstTo(2),
+        power_ups: false,
+        ..Config::default()
+    });
+
+    play_versus(&mut driver, Some(Difficulty::NearPerfect.controller()));
+
+    assert_eq!(driver.engine.status(), Status::Lost);
+    assert_eq!(driver.engine.scores()[1].fruits_eaten(), 2);
+    assert_eq!(driver.engine.score().fruits_eaten(), 0);
+}
+
+#[test]
+fn outliving_the_rival_wins_versus() {
+    let mut driver = Driver::new(Config {
+        mode: Mode::Versus,
+        win_condition: WinCondition::LastAlive,
+        ..Config::default()
+    });
+
+    // Left to itself the rival carries on west into the wall
+    play_versus(&mut driver, None);
+
+    assert_eq!(driver.engine.status(), Status::Won);
+    assert!(driver.engine.snake().alive);
+    assert!(!driver.engine.snakes()[1].alive);
+}
+
+#[test]
+fn a_tie_at_the_time_limit_loses_versus() {
+    let mut driver = Driver::new(Config {
+        mode: Mode::Versus,
+        win_condition: WinCondition::HighestAtTimeout(0.5),
+        ..Config::default()
+    });
+
+    play_versus(&mut driver, None);
+
+    assert_eq!(driver.engine.status(), Status::Lost);
+    assert!(driver.engine.run_state().time >= 0.5);
+}
+
+#[test]
+fn rivals_left_in_a_royale_turn_into_pellets() {
+    let mut driver = Driver::new(Config {
+        mode: Mode::Royale,
+        win_condition: WinCondition::LastAlive,
+        ..Config::default()
+    });
+
+    assert_eq!(driver.engine.snakes().len(), Royale::SNAKES);
+    assert_eq!(driver.engine.config().dimensions, Royale::MAP_DIMENSIONS);
+
+    // Left to themselves the rivals carry on into the walls
+    play_versus(&mut driver, None);
+
+    assert_eq!(driver.engine.status(), Status::Won);
+    assert!(driver.engine.snakes()[1..].iter().all(|snake| snake.is_empty()));
+    assert!(!driver.engine.pellets().is_empty());
+    assert!(driver.engine.pellets().iter().all(|pellet| pellet.snake > 0 && driver.engine.segments_at(pellet.position) == 0));
+}
+
+#[test]
+fn losing_a_life_respawns_the_snake() {
+    let mut driver = Driver::new(Config {
+        dimensions: (10, 10),
+        mode: Mode::Lives,
+        ..Config::default()
+    });
+
+    assert_eq!(driver.play("....."), vec![Outcome::Moved, Outcome::Moved, Outcome::Moved, Outcome::Moved, Outcome::Died]);
+
+    while driver.advance(None) == Outcome::Respawning {}
+
+    assert_eq!(driver.engine.lives(), Mode::Lives.rules().lives() - 1);
+    assert!(driver.engine.snake().alive);
+    assert_eq!(driver.engine.snake().len(), Snake::STARTING_LENGTH);
+    assert_eq!(driver.engine.status(), Status::Playing);
+}
+
+#[test]
+fn a_run_is_lost_once_every_life_is_gone() {
+    let mut driver = Driver::new(Config {
+        dimensions: (10, 10),
+        mode: Mode::Lives,
+        ..Config::default()
+    });
+
+    driver.play_until_over(1000);
+
+    let deaths = driver.outcomes.iter().filter(|outcome| matches!(outcome, Outcome::Died | Outcome::Lost)).count();
+
+    assert_eq!(deaths, Mode::Lives.rules().lives());
+    assert_eq!(driver.engine.status(), Status::Lost);
+}
+
+#[test]
+fn snakes_respawn_on_maps_too_small_for_a_full_runway() {
+    let mut driver = Driver::new(Config {
+        dimensions: (4, 4),
+        mode: Mode::Lives,
+        ..Config::default()
+    });
+
+    driver.play_until_over(1000);
+
+    let deaths = driver.outcomes.iter().filter(|outcome| matches!(outcome, Outcome::Died | Outcome::Lost)).count();
+
+    assert_eq!(deaths, Mode::Lives.rules().lives());
+    assert_eq!(driver.engine.status(), Status::Lost);
+}
+
+fn steering() -> impl Strategy<Value = Vec<Option<Direction>>> {
+    prop::collection::vec(prop::option::of(prop::sample::select(Direction::ALL.to_vec())), 0..300)
+}
+
+proptest! {
+    #[test]
+    fn snake_length_is_starting_length_plus_fruit_eaten(seed: u64, inputs in steering()) {
+        let mut engine = Engine::new(Config {
+            seed,
+            ..Config::default()
+        });
+
+        for direction in inputs {
+            engine.advance(direction);
+
+            if !engine.snake().alive {
+                break;
+            }
+
+            prop_assert_eq!(engine.snake().len(), Snake::STARTING_LENGTH + engine.score().fruits_eaten());
+        }
+    }
+
+    #[test]
+    fn only_the_head_ever_leaves_the_map(seed: u64, inputs in steering()) {
+        let mut engine = Engine::new(Config {
+            seed,
+            ..Config::default()
+        });
+
+        for direction in inputs {
+            engine.advance(direction);
+
+            for segment in engine.snake().iter().skip(1) {
+                prop_assert!(segment.position.in_bounds(engine.config().dimensions));
+            }
+        }
+    }
+
+    #[test]
+    fn runs_with_the_same_seed_and_input_play_out_the_same(seed: u64, inputs in steering()) {
+        let config = Config {
+            seed,
+            ..Config::default()
+        };
+        let mut first = Engine::new(config.clone());
+        let mut second = Engine::new(config);
+
+        for direction in inputs {
+            prop_assert_eq!(first.advance(direction), second.advance(direction));
+            prop_assert_eq!(first.fruit(), second.fruit());
+        }
+
+        prop_assert_eq!(first.score().points(), second.score().points());
+    }
+
+    #[test]
+    fn free_cells_are_exactly_the_uncovered_tiles(seed: u64, inputs in steering()) {
+        let mut engine = Engine::new(Config {
+            seed,
+            ..Config::default()
+        });
+
+        for direction in inputs {
+            engine.advance(direction);
+
+            let (width, height) = engine.config().dimensions;
+            let mut expected: Vec<Position> = (0..height as i32)
+                .flat_map(|y| (0..width as i32).map(move |x| Position::new(x, y)))
+                .filter(|point| {
+                    !engine.snake().iter().any(|segment| segment.position == *point) &&
+                    engine.fruit() != *point &&
+                    !engine.frenzy().fruits().contains(point) &&
+                    !engine.hazards().iter().any(|hazard| hazard.covers(*point))
+                })
+                .collect();
+            let mut free_cells = engine.empty_points().to_vec();
+
+            expected.sort_by_key(|point| (point.y, point.x));
+            free_cells.sort_by_key(|point| (point.y, point.x));
+
+            prop_assert_eq!(free_cells, expected);
+        }
+    }
+
+    #[test]
+    fn the_snake_knows_which_tiles_it_covers(seed: u64, inputs in steering()) {
+        let mut engine = Engine::new(Config {
+            seed,
+            ..Config::default()
+        });
+
+        for direction in inputs {
+            engine.advance(direction);
+
+            let snake = engine.snake();
+
+            for segment in snake.iter().filter(|segment| segment.position.in_bounds(engine.config().dimensions)) {
+                let expected = snake.iter().filter(|other| other.position == segment.position).count();
+
+                prop_assert_eq!(snake.segments_at(segment.position) as usize, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use snake_lib::{Config, Engine, Position};
+use snake_lib::engine::fruit::{FruitKind, FruitSpec};
+use snake_lib::engine::mode::Mode;
+use snake_lib::engine::spawn::{self, Fair, Lines, SpawnContext, SpawnPolicy};
+use snake_lib::level::Level;
+
+#[test]
+fn levels_set_what_fruit_is_worth_and_how_often_it_spawns() {
+    let level = Level::parse("fruit apple weight=0\nfruit grape points=5").unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert_eq!(level.fruits.spec(FruitKind::Grape), FruitSpec {points: 5, weight: 1.0});
+    assert!((0..100).all(|_| level.fruits.choose(&mut rng) != FruitKind::Apple));
+
+    assert!(Level::parse("fruit banana").is_err());
+    assert!(Level::parse("fruit cherry weight=-1").is_err());
+}
+
+#[test]
+fn fair_spawns_favour_tiles_away_from_the_head_and_nearer_than_any_rival() {
+    let heads = [Some(Position::new(5, 5)), Some(Position::new(20, 5))];
+    let context = SpawnContext {
+        snake: 0,
+        heads: &heads
+    };
+    let weight = |x, y| Fair.weight(Position::new(x, y), &context);
+
+    assert!(weight(6, 5) < weight(10, 5));
+    assert_eq!(weight(10, 10), 1.0);
+    assert!(weight(19, 5) < weight(10, 10)); // Right by the rival
+    assert!(weight(19, 5) >= Fair::FLOOR);
+
+    assert!(Mode::Classic.rules().spawn_policy().is_uniform());
+    assert_eq!(Mode::Versus.rules().spawn_policy().name(), "Fair");
+    assert!(spawn::named("fair").is_some() && spawn::named("nearest").is_none());
+}
+
+#[test]
+fn clustered_fruit_spawns_alongside_the_snakes_fruit() {
+    let engine = Engine::new(Config {
+        mode: Mode::Clusters,
+        seed: 11,
+        ..Config::default()
+    });
+    let fruit = engine.fruit();
+    let cluster: Vec<Position> = engine.cluster_fruits().map(|fruit| fruit.position).collect();
+
+    assert!(!cluster.is_empty());
+    assert!(cluster.iter().all(|tile| tile.manhattan_distance(fruit) <= 2 && *tile != fruit));
+    assert!(cluster.iter().all(|tile| engine.arena().is_open(*tile) && engine.snake().iter().all(|segment| segment.position != *tile)));
+
+    let line = Lines.cluster(Position::new(4, 4));
+
+    assert_eq!(line.len() as i32, Lines::LENGTH - 1);
+    assert!(line.iter().all(|tile| tile.y == 4));
+    assert!(Lines.cluster(Position::new(4, 5)).iter().all(|tile| tile.x == 4));
+    assert_eq!(Mode::Clusters.rules().spawn_policy().name(), "Clusters");
+    assert_eq!(Mode::Casual.rules().spawn_policy().name(), "Uniform");
+}
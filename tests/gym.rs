@@ -0,0 +1,55 @@
+use snake_lib::{Config, Snake};
+use snake_lib::engine::gym::{Action, Cell, Env};
+
+fn count(cells: &[u8], cell: Cell) -> usize {
+    cells.iter().filter(|value| **value == cell as u8).count()
+}
+
+#[test]
+fn observations_show_the_whole_board() {
+    let mut env = Env::new(Config::default());
+    let observation = env.reset();
+
+    assert_eq!(observation.cells.len(), (observation.width * observation.height) as usize);
+    assert_eq!(count(&observation.cells, Cell::Head), 1);
+    assert_eq!(count(&observation.cells, Cell::Fruit), 1);
+    assert_eq!(observation.cell(env.engine().fruit()), Some(Cell::Fruit as u8));
+    assert_eq!(observation.heading, Snake::STARTING_DIRECTIN);
+}
+
+#[test]
+fn running_into_the_wall_ends_the_episode_with_a_penalty() {
+    let mut env = Env::new(Config::default());
+    let mut rewards = Vec::new();
+
+    env.reset();
+
+    loop {
+        let (_, reward, done) = env.step(Action::North);
+
+        rewards.push(reward);
+
+        if done {
+            break;
+        }
+    }
+
+    assert_eq!(rewards.last(), Some(&Env::REWARD_DEATH));
+    assert_eq!(env.step(Action::North).1, 0.0); // A finished episode earns nothing more
+}
+
+#[test]
+fn episodes_are_reproducible() {
+    let mut first = Env::new(Config::default());
+    let mut second = Env::new(Config::default());
+
+    for _ in 0..3 {
+        assert_eq!(first.reset(), second.reset());
+
+        for index in 0..50 {
+            let action = Action::from_index(index % Action::ALL.len()).unwrap();
+
+            assert_eq!(first.step(action), second.step(action));
+        }
+    }
+}
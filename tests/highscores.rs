@@ -0,0 +1,27 @@
+use snake_lib::Config;
+use snake_lib::highscores::Leaderboard;
+use snake_lib::level::Level;
+
+#[test]
+fn only_runs_on_the_default_settings_are_ranked() {
+    assert!(Leaderboard::ranks(&Config::default()));
+    assert!(!Leaderboard::ranks(&Config {
+        obstacles: 20,
+        ..Config::default()
+    }));
+
+    // Anything a level lays out takes a run off the tables
+    for text in ["arena cross", "edge all wrap", "fruit Apple points=5", "every 20s spawn bomb at random", "tag 3 3 no_fruit"].iter() {
+        let level = Level::parse(text).unwrap();
+        let config = Config {
+            schedule: level.schedule,
+            tags: level.tags,
+            fruits: level.fruits,
+            arena: level.arena,
+            edges: level.edges,
+            ..Config::default()
+        };
+
+        assert!(!Leaderboard::ranks(&config), "{} was ranked", text);
+    }
+}
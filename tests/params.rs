@@ -0,0 +1,31 @@
+use snake_lib::Config;
+use snake_lib::engine::Grid;
+use snake_lib::engine::mode::Mode;
+use snake_lib::engine::speed::SpeedCurve;
+use snake_lib::params::UrlParams;
+
+#[test]
+fn url_params_set_up_a_challenge() {
+    let params = UrlParams::parse("?seed=42&speed=1.5&curve=stepwise&mode=hardcore&size=30x40&grid=hex");
+    let mut config = Config::default();
+
+    params.apply(&mut config);
+
+    assert_eq!(config.seed, 42);
+    assert_eq!(config.speed, 1.5);
+    assert_eq!(config.speed_curve, SpeedCurve::STEPWISE);
+    assert_eq!(config.mode, Mode::Hardcore);
+    assert_eq!(config.dimensions, (30, 40));
+    assert_eq!(config.grid, Grid::Hex);
+}
+
+#[test]
+fn malformed_url_params_are_ignored_and_out_of_range_ones_clamped() {
+    let params = UrlParams::parse("seed=abc&speed=100&mode=nonsense&size=1000&extra");
+
+    assert_eq!(params, UrlParams {
+        speed: Some(UrlParams::SPEED_RANGE.1),
+        size: Some((UrlParams::SIZE_RANGE.1, UrlParams::SIZE_RANGE.1)),
+        ..UrlParams::default()
+    });
+}
@@ -0,0 +1,67 @@
+use snake_lib::{Config, Engine, Position, Status};
+use snake_lib::engine::puzzle::PuzzleMode;
+use snake_lib::level::{Level, LevelError};
+use snake_lib::puzzle::Puzzles;
+
+#[test]
+fn every_puzzle_can_be_solved() {
+    for (index, level) in Puzzles::levels().into_iter().enumerate() {
+        assert!(level.validate(Engine::MAP_DIMENSIONS).is_ok(), "{} isn't valid", level.name);
+        assert!(Puzzles::new(index).par() > 0, "{} has no par", level.name);
+    }
+}
+
+#[test]
+fn playing_the_solution_solves_the_puzzle_at_par() {
+    for index in 0..Puzzles::levels().len() {
+        let puzzles = Puzzles::new(index);
+        let config = puzzles.config(&Config::default());
+        let arena = puzzles.level().arena.mask(Engine::MAP_DIMENSIONS).with_edges(puzzles.level().edges);
+        let mut engine = Engine::with_rules(config, puzzles.rules());
+
+        assert_eq!(engine.snake().front().map(|head| head.position), puzzles.scenario().snake.first().copied());
+
+        for direction in puzzles.scenario().solve(&arena).unwrap() {
+            assert_eq!(engine.status(), Status::Playing);
+            engine.advance(Some(direction));
+        }
+
+        assert_eq!(engine.status(), Status::Won, "{} wasn't solved", puzzles.level().name);
+        assert_eq!(engine.ticks(), puzzles.par());
+        assert_eq!(puzzles.stars(engine.ticks()), Puzzles::MAX_STARS);
+    }
+}
+
+#[test]
+fn puzzles_lay_out_the_snake_and_every_fruit() {
+    let level = Level::parse("puzzle snake 5 5 5 6 6 6\npuzzle fruit 8 5 9 5\npuzzle fruit 2 2").unwrap();
+    let scenario = level.puzzle.unwrap();
+    let config = Config {
+        scenario: Some(scenario.clone()),
+        ..Config::default()
+    };
+    let engine = Engine::with_rules(config, Box::new(PuzzleMode::new(&scenario)));
+    let body: Vec<Position> = engine.snake().iter().map(|segment| segment.position).collect();
+    let mut fruit: Vec<Position> = engine.fruits().iter().chain(engine.cluster_fruits()).map(|fruit| fruit.position).collect();
+
+    fruit.sort_by_key(|position| (position.x, position.y));
+
+    assert_eq!(body, scenario.snake);
+    assert_eq!(fruit, vec![Position::new(2, 2), Position::new(8, 5), Position::new(9, 5)]);
+    assert!(engine.rules().is_turn_based());
+}
+
+#[test]
+fn broken_puzzles_are_turned_away() {
+    assert!(Level::parse("puzzle snake 5 5 7 5").is_err());
+    assert!(Level::parse("puzzle snake 5 5").is_err());
+    assert!(Level::parse("puzzle fruit 5").is_err());
+    assert!(Level::parse("puzzle worm 1 1").is_err());
+
+    // Fruit behind a snake heading the other way down a dead end
+    let behind = Level::parse("mask ......\npuzzle snake 3 0 4 0\npuzzle fruit 5 0").unwrap();
+    let blocked = Level::parse("puzzle snake 5 5 6 5\npuzzle fruit 6 5").unwrap();
+
+    assert!(matches!(behind.validate(Engine::MAP_DIMENSIONS), Err(LevelError::Unsolvable)));
+    assert!(matches!(blocked.validate(Engine::MAP_DIMENSIONS), Err(LevelError::PuzzleBlocked(_))));
+}
@@ -0,0 +1,102 @@
+use snake_lib::{Config, Direction, Engine};
+use snake_lib::engine::Grid;
+use snake_lib::engine::mode::Mode;
+use snake_lib::engine::difficulty::Difficulty;
+use snake_lib::replay::{Replay, ReplayError};
+
+#[test]
+fn a_saved_replay_plays_out_the_same_run() {
+    let mut engine = Engine::new(Config {
+        seed: 7,
+        mode: Mode::Versus,
+        ..Config::default()
+    });
+    let mut bots = [Difficulty::Greedy.controller(), Difficulty::NearPerfect.controller()];
+
+    while !engine.is_over() && engine.ticks() < 2000 {
+        for (index, bot) in bots.iter_mut().enumerate() {
+            if let Some(direction) = bot.decide(&engine.view_of(index)) {
+                engine.steer_snake(index, direction);
+            }
+        }
+
+        engine.tick();
+    }
+
+    assert!(!engine.steering().is_empty());
+
+    let replay = Replay::parse(&Replay::of(&engine).serialise()).unwrap();
+    let mut playback = replay.start();
+
+    while playback.ticks() < engine.ticks() {
+        replay.steer(&mut playback);
+        playback.tick();
+    }
+
+    assert_eq!(playback.steering(), engine.steering());
+    assert_eq!(playback.status(), engine.status());
+    assert_eq!(playback.scores()[1].points(), engine.scores()[1].points());
+    assert_eq!(playback.snake().iter().collect::<Vec<_>>(), engine.snake().iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn diagonal_steering_is_saved_in_replays() {
+    let mut engine = Engine::new(Config {
+        grid: Grid::Diagonal,
+        ..Config::default()
+    });
+
+    for direction in [Direction::NorthEast, Direction::North, Direction::NorthWest].iter() {
+        engine.advance(Some(*direction));
+    }
+
+    let text = Replay::of(&engine).serialise();
+    let replay = Replay::parse(&text).unwrap();
+
+    assert!(text.contains("0 0 NE\n") && text.contains("2 0 NW\n"));
+    assert_eq!(replay.config.grid, Grid::Diagonal);
+    assert_eq!(replay.steering, engine.steering());
+}
+
+#[test]
+fn replays_from_other_versions_are_turned_away() {
+    let replay = Replay::of(&Engine::new(Config::default())).serialise();
+
+    let newer_format = replay.replacen(&format!("{} {}", Replay::HEADER, Replay::FORMAT), &format!("{} {}", Replay::HEADER, Replay::FORMAT + 1), 1);
+    let older_game = replay.replacen(&format!("version={}", Replay::VERSION), "version=0.0.1", 1);
+
+    assert!(matches!(Replay::parse(&newer_format), Err(ReplayError::NewerFormat(format)) if format == Replay::FORMAT + 1));
+    assert!(matches!(Replay::parse(&older_game), Err(ReplayError::Incompatible(version)) if version == "0.0.1"));
+    assert!(matches!(Replay::parse("seed=42"), Err(ReplayError::NotAReplay)));
+}
+
+#[test]
+fn a_replay_can_be_played_back_part_of_the_way() {
+    let mut engine = Engine::new(Config {
+        seed: 11,
+        mode: Mode::Casual,
+        ..Config::default()
+    });
+    let mut bot = Difficulty::Greedy.controller();
+    let mut halfway = None;
+
+    while !engine.is_over() && engine.ticks() < 400 {
+        if engine.ticks() == 200 {
+            halfway = Some(engine.snake().iter().map(|segment| segment.position).collect::<Vec<_>>());
+        }
+
+        if let Some(direction) = bot.decide(&engine.view()) {
+            engine.steer(direction);
+        }
+
+        engine.tick();
+    }
+
+    let replay = Replay::of(&engine);
+    let mut rewound = replay.start();
+
+    replay.play_to(&mut rewound, 200);
+
+    assert_eq!(rewound.ticks(), 200);
+    assert_eq!(Some(rewound.snake().iter().map(|segment| segment.position).collect::<Vec<_>>()), halfway);
+}
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+
+use snake_lib::display::Scale;
+use snake_lib::save::{Profile, SaveData};
+use snake_lib::storage::Storage;
+
+// Keeps the save data in memory, as a file or the browser's local storage would
+#[derive(Default)]
+struct MemoryStorage {
+    contents: RefCell<Option<String>>
+}
+
+impl Storage for MemoryStorage {
+    fn read(&self) -> Result<Option<String>, String> {
+        Ok(self.contents.borrow().clone())
+    }
+
+    fn write(&self, contents: &str) -> Result<(), String> {
+        *self.contents.borrow_mut() = Some(contents.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn save_data_survives_a_round_trip() {
+    let storage = MemoryStorage::default();
+    let mut data = SaveData::default();
+
+    data.display.scale = Scale::Percent(150);
+    data.display.vsync = false;
+    data.display.fps_cap = None;
+    data.add_profile("Ada").unwrap();
+    data.switch_profile(0);
+    data.save(&storage).unwrap();
+
+    let loaded = SaveData::load(&storage);
+
+    assert_eq!(loaded.display, data.display);
+
+    // The display settings come before any profile, and mustn't start one of their own
+    assert_eq!(loaded.profiles().len(), 2);
+    assert_eq!(loaded.active(), 0);
+    assert_eq!(loaded.profile().name, Profile::DEFAULT_NAME);
+}
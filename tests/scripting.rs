@@ -0,0 +1,64 @@
+use snake_lib::{Config, Direction, Engine, Outcome, Position, Status};
+use snake_lib::scripting::Mod;
+
+fn engine_for(source: &str) -> Engine {
+    let script = Mod::parse(String::from("test"), source).unwrap();
+    let mut engine = Engine::with_rules(Config {
+        dimensions: (10, 10),
+        ..Config::default()
+    }, script.rules());
+
+    engine.set_fruit(Position::new(0, 0)); // Out of the way of the snake's starting path
+
+    engine
+}
+
+#[test]
+fn every_shipped_mod_compiles() {
+    for name in Mod::available() {
+        assert!(Mod::named(&name).is_ok(), "{} failed to compile", name);
+    }
+}
+
+#[test]
+fn scripts_decide_scoring_and_when_the_run_is_won() {
+    let mut engine = engine_for(r#"
+        fn fruit_points(points) { points * 10 }
+        fn status(run) { if run.fruits_eaten > 0 { "won" } else { "playing" } }
+    "#);
+    let head = engine.snake()[0].position;
+
+    engine.set_fruit(head + Position::from(Direction::East));
+
+    assert_eq!(engine.advance(None), Outcome::Won);
+    assert_eq!(engine.score().points(), 10);
+}
+
+#[test]
+fn scripts_can_spawn_hazards_and_keep_state() {
+    let mut engine = engine_for(r#"
+        fn on_start(run) { this.bombs = 0; }
+        fn on_tick(run) {
+            if this.bombs < 2 {
+                spawn_bomb(this.bombs, 9);
+                this.bombs += 1;
+            }
+        }
+    "#);
+
+    engine.advance(None);
+    engine.advance(None);
+    engine.advance(None);
+
+    assert_eq!(engine.hazards().len(), 2);
+}
+
+#[test]
+fn a_failing_script_function_falls_back_to_classic_rules() {
+    let mut engine = engine_for(r#"
+        fn status(run) { throw "broken"; }
+    "#);
+
+    assert_eq!(engine.advance(None), Outcome::Moved);
+    assert_eq!(engine.status(), Status::Playing);
+}
@@ -0,0 +1,17 @@
+use snake_lib::session::Session;
+
+#[test]
+fn a_session_sums_up_every_run() {
+    let mut session = Session::default();
+
+    assert_eq!(session.average_length(), 0.0);
+
+    session.record(12, 9, 30.0);
+    session.record(4, 6, 15.5);
+
+    assert_eq!(session.games_played(), 2);
+    assert_eq!(session.best_score(), 12);
+    assert_eq!(session.average_length(), 7.5);
+    assert_eq!(session.total_seconds(), 45.5);
+    assert_eq!(session.lines()[3], "Time played: 0:45.50");
+}
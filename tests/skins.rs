@@ -0,0 +1,71 @@
+use snake_lib::{Config, Direction, Engine};
+use snake_lib::engine::fruit::FruitKind;
+use snake_lib::engine::skin::Skin;
+
+fn glyphs(skin: Skin, reskin: Option<Skin>) -> Vec<char> {
+    let mut engine = Engine::new(Config {
+        dimensions: (12, 12),
+        skin,
+        ..Config::default()
+    });
+
+    for direction in [None, Some(Direction::South), None, Some(Direction::West), None, None, Some(Direction::North)].iter() {
+        engine.advance(*direction);
+    }
+
+    if let Some(skin) = reskin {
+        engine.set_skin(skin);
+    }
+
+    engine.snake().iter().map(|segment| segment.glyph).collect()
+}
+
+#[test]
+fn every_skin_in_the_data_file_is_loaded() {
+    let names: Vec<&str> = Skin::all().iter().map(|skin| skin.name).collect();
+
+    assert_eq!(names, ["Double", "Single", "Blocks", "ASCII", "Emoji"]);
+    assert_eq!(Skin::default().name, "Double");
+    assert_eq!(Skin::named("ascii").map(|skin| skin.fruit(FruitKind::Apple)), Some('*'));
+    assert_eq!(Skin::all().last().map(|skin| skin.next(|_| true)), Some(Skin::default()));
+    assert_eq!(Skin::named("Single").map(|skin| skin.bonus_fruit), Some(Skin::default().bonus_fruit)); // Left out, so taken from the first
+}
+
+#[test]
+fn cycling_keeps_to_skins_that_can_be_drawn() {
+    let ascii = Skin::named("ASCII").unwrap();
+
+    assert!(Skin::all().iter().filter(|skin| skin.is_ascii()).all(|skin| *skin == ascii));
+    assert_eq!(Skin::default().next(Skin::is_ascii), ascii);
+    assert_eq!(ascii.next(Skin::is_ascii), ascii);
+    assert_eq!(ascii.displayable('▓'), Skin::ASCII_REPLACEMENT);
+    assert_eq!(Skin::default().displayable('▓'), '▓');
+
+    let narrow = |skin: Skin| skin.width == 1;
+
+    assert_eq!(Skin::named("Emoji").map(|skin| skin.width), Some(2));
+    assert_eq!(Skin::named("ASCII").map(|skin| skin.next(narrow)), Some(Skin::default())); // Passing over Emoji
+    assert_eq!(Skin::named("Emoji").map(|skin| skin.next(narrow)), Some(Skin::default()));
+}
+
+#[test]
+fn diagonal_segments_lie_along_the_line_between_their_neighbours() {
+    use Direction::*;
+
+    let skin = Skin::default();
+
+    assert_eq!((skin.straight(NorthEast), skin.straight(SouthEast)), ('/', '\\'));
+    assert_eq!(skin.joining(&[NorthEast, SouthWest]), '/');
+    assert_eq!(skin.joining(&[East, SouthWest]), skin.horizontal); // Bending off a diagonal
+    assert_eq!(skin.joining(&[North, SouthWest]), skin.vertical);
+    assert_eq!(skin.joining(&[South, East]), skin.corners.0); // Corners along the grid are as they were
+    assert!(skin.is_straight('/'));
+}
+
+#[test]
+fn reskinning_a_snake_matches_playing_in_that_skin() {
+    let single = Skin::named("Single").unwrap();
+
+    assert_eq!(glyphs(Skin::default(), Some(single)), glyphs(single, None));
+    assert_eq!(glyphs(single, None), ['│', '└', '─', '─', '─']);
+}
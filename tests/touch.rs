@@ -0,0 +1,31 @@
+use snake_lib::{Direction, Position};
+use snake_lib::touch::TouchButton;
+
+#[test]
+fn touch_buttons_fit_on_the_map_without_overlapping() {
+    let dimensions = (25, 25);
+
+    for button in TouchButton::PLAYING.iter().chain(TouchButton::OVER.iter()) {
+        let origin = button.origin(dimensions);
+        let far_corner = origin + Position::new(TouchButton::SIZE - 1, TouchButton::SIZE - 1);
+
+        assert!(origin.in_bounds(dimensions) && far_corner.in_bounds(dimensions), "{:?} is off the map", button);
+    }
+
+    for (index, button) in TouchButton::PLAYING.iter().enumerate() {
+        for other in &TouchButton::PLAYING[index + 1..] {
+            assert!(!button.covers(other.origin(dimensions), dimensions), "{:?} overlaps {:?}", button, other);
+        }
+    }
+}
+
+#[test]
+fn taps_press_the_button_underneath() {
+    let dimensions = (25, 25);
+    let origin = TouchButton::Steer(Direction::North).origin(dimensions);
+    let tap = ((origin.x as f64 + 0.5) / 25.0, (origin.y as f64 + 0.5) / 25.0);
+
+    assert_eq!(TouchButton::at(tap, false, dimensions), Some(TouchButton::Steer(Direction::North)));
+    assert_eq!(TouchButton::at(tap, true, dimensions), None);
+    assert_eq!(TouchButton::at((0.5, 0.5), false, dimensions), None);
+}
@@ -0,0 +1,42 @@
+use snake_lib::tournament::Tournament;
+
+#[test]
+fn players_take_turns_on_the_same_board_until_one_wins_most_rounds() {
+    let mut tournament = Tournament::new(2, 3, 100);
+
+    assert_eq!(tournament.current_player(), Some(0));
+    let seed = tournament.seed();
+    tournament.record(5);
+    assert_eq!((tournament.current_player(), tournament.seed()), (Some(1), seed));
+    tournament.record(8);
+
+    assert_eq!(tournament.round_winner(0), Some(1));
+    assert_ne!(tournament.seed(), seed);
+
+    tournament.record(3);
+    tournament.record(2);
+    assert_eq!(tournament.winner(), None);
+
+    tournament.record(1);
+    tournament.record(4);
+
+    assert_eq!(tournament.winner(), Some(1));
+    assert_eq!(tournament.current_player(), None);
+}
+
+#[test]
+fn tied_rounds_go_to_no_one_and_extra_rounds_settle_a_draw() {
+    let mut tournament = Tournament::new(2, 1, 0);
+
+    tournament.record(6);
+    tournament.record(6);
+
+    assert_eq!(tournament.round_winner(0), None);
+    assert_eq!(tournament.winner(), None);
+    assert_eq!(tournament.current_player(), Some(0));
+
+    tournament.record(7);
+    tournament.record(2);
+
+    assert_eq!(tournament.winner(), Some(0));
+}
@@ -0,0 +1,26 @@
+use snake_lib::{Config, Direction, Engine};
+use snake_lib::tutorial::{Lesson, Tutorial};
+
+#[test]
+fn the_tutorial_moves_on_once_a_milestone_is_reached() {
+    let mut engine = Engine::new(Tutorial::config(&Config::default()));
+    let mut tutorial = Tutorial::new();
+    let turns = [Direction::North, Direction::West, Direction::South];
+
+    for direction in turns.iter().copied() {
+        engine.steer(direction);
+        engine.tick();
+        tutorial.update(&mut engine);
+        engine.tick();
+        tutorial.update(&mut engine);
+    }
+
+    assert_eq!(tutorial.lesson(), Lesson::Eat);
+
+    // The fruit is put in the snake's path
+    let head = engine.snake().front().unwrap().position;
+    let fruit = engine.fruit();
+
+    assert_eq!(fruit.x, head.x);
+    assert!(fruit.y > head.y);
+}